@@ -0,0 +1,176 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::engine::Stats;
+use crate::i18n::tr;
+use crate::models::UserProfile;
+
+/// A one-off badge for reaching a milestone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstSession,
+    WeekStreak,
+    HundredSessions,
+    TenHours,
+    NightOwl,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstSession,
+        Achievement::WeekStreak,
+        Achievement::HundredSessions,
+        Achievement::TenHours,
+        Achievement::NightOwl,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            Achievement::FirstSession => "achievement.first_session",
+            Achievement::WeekStreak => "achievement.week_streak",
+            Achievement::HundredSessions => "achievement.hundred_sessions",
+            Achievement::TenHours => "achievement.ten_hours",
+            Achievement::NightOwl => "achievement.night_owl",
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        tr(match self {
+            Achievement::FirstSession => "achievement.first_session_desc",
+            Achievement::WeekStreak => "achievement.week_streak_desc",
+            Achievement::HundredSessions => "achievement.hundred_sessions_desc",
+            Achievement::TenHours => "achievement.ten_hours_desc",
+            Achievement::NightOwl => "achievement.night_owl_desc",
+        })
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Achievement::FirstSession => "🌱",
+            Achievement::WeekStreak => "🔥",
+            Achievement::HundredSessions => "💯",
+            Achievement::TenHours => "⏳",
+            Achievement::NightOwl => "🦉",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Achievement::FirstSession => "first_session",
+            Achievement::WeekStreak => "week_streak",
+            Achievement::HundredSessions => "hundred_sessions",
+            Achievement::TenHours => "ten_hours",
+            Achievement::NightOwl => "night_owl",
+        }
+    }
+
+    fn is_earned(&self, profile: &UserProfile, stats: &Stats) -> bool {
+        match self {
+            Achievement::FirstSession => stats.total_sessions >= 1,
+            Achievement::WeekStreak => profile.longest_streak >= 7,
+            Achievement::HundredSessions => stats.total_sessions >= 100,
+            Achievement::TenHours => stats.total_focus_secs >= 10 * 3600,
+            // started between midnight and 5 AM
+            Achievement::NightOwl => stats.hourly_data[..5].iter().any(|&n| n > 0),
+        }
+    }
+}
+
+impl fmt::Display for Achievement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Achievement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Achievement::ALL
+            .into_iter()
+            .find(|a| a.as_str() == s)
+            .ok_or_else(|| format!("unknown achievement '{}'", s))
+    }
+}
+
+/// Achievements earned by now that aren't in `unlocked` yet.
+pub fn evaluate(
+    profile: &UserProfile,
+    stats: &Stats,
+    unlocked: &[Achievement],
+) -> Vec<Achievement> {
+    Achievement::ALL
+        .into_iter()
+        .filter(|a| !unlocked.contains(a) && a.is_earned(profile, stats))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_thresholds() {
+        let profile = UserProfile::default();
+        let mut stats = Stats::default();
+        assert!(evaluate(&profile, &stats, &[]).is_empty());
+
+        stats.total_sessions = 99;
+        assert_eq!(
+            evaluate(&profile, &stats, &[]),
+            vec![Achievement::FirstSession]
+        );
+        stats.total_sessions = 100;
+        assert_eq!(
+            evaluate(&profile, &stats, &[Achievement::FirstSession]),
+            vec![Achievement::HundredSessions]
+        );
+    }
+
+    #[test]
+    fn test_streak_and_time_thresholds() {
+        let mut profile = UserProfile {
+            longest_streak: 6,
+            ..UserProfile::default()
+        };
+        let mut stats = Stats {
+            total_sessions: 1,
+            total_focus_secs: 10 * 3600 - 1,
+            ..Stats::default()
+        };
+        let unlocked = [Achievement::FirstSession];
+        assert!(evaluate(&profile, &stats, &unlocked).is_empty());
+
+        profile.longest_streak = 7;
+        stats.total_focus_secs += 1;
+        assert_eq!(
+            evaluate(&profile, &stats, &unlocked),
+            vec![Achievement::WeekStreak, Achievement::TenHours]
+        );
+    }
+
+    #[test]
+    fn test_night_owl() {
+        let profile = UserProfile::default();
+        let mut stats = Stats {
+            total_sessions: 1,
+            ..Stats::default()
+        };
+        stats.hourly_data[23] = 1;
+        let unlocked = [Achievement::FirstSession];
+        assert!(evaluate(&profile, &stats, &unlocked).is_empty());
+
+        stats.hourly_data[1] = 1;
+        assert_eq!(
+            evaluate(&profile, &stats, &unlocked),
+            vec![Achievement::NightOwl]
+        );
+    }
+
+    #[test]
+    fn test_id_roundtrip() {
+        for achievement in Achievement::ALL {
+            assert_eq!(achievement.as_str().parse(), Ok(achievement));
+        }
+    }
+}
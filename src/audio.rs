@@ -0,0 +1,153 @@
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::models::AmbientSound;
+
+const SAMPLE_RATE: u32 = 44_100;
+
+enum Command {
+    SetVolume(f32),
+    Stop,
+}
+
+/// A looping background noise played on its own thread.
+#[derive(Default)]
+pub struct Ambient {
+    playing: Option<Playing>,
+}
+
+struct Playing {
+    sound: AmbientSound,
+    commands: mpsc::Sender<Command>,
+    thread: JoinHandle<()>,
+}
+
+impl Ambient {
+    /// Starts `sound`, or just adjusts the volume if it's already playing.
+    pub fn play(&mut self, sound: AmbientSound, volume: f32) {
+        if let Some(playing) = self.playing.as_ref().filter(|p| p.sound == sound) {
+            let _ = playing.commands.send(Command::SetVolume(volume));
+            return;
+        }
+        self.stop();
+        if sound == AmbientSound::Off {
+            return;
+        }
+
+        let (commands, rx) = mpsc::channel();
+        // like the completion chime, the output stream must stay on the thread that opened it
+        let thread = std::thread::spawn(move || {
+            let mut stream = match rodio::OutputStreamBuilder::open_default_stream() {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Failed to open audio device: {}", e);
+                    return;
+                }
+            };
+            stream.log_on_drop(false);
+
+            let sink = rodio::Sink::connect_new(stream.mixer());
+            sink.set_volume(volume);
+            sink.append(Noise::new(sound));
+
+            while let Ok(Command::SetVolume(volume)) = rx.recv() {
+                sink.set_volume(volume);
+            }
+            sink.stop();
+        });
+
+        self.playing = Some(Playing {
+            sound,
+            commands,
+            thread,
+        });
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(playing) = self.playing.take() {
+            let _ = playing.commands.send(Command::Stop);
+            let _ = playing.thread.join();
+        }
+    }
+}
+
+impl Drop for Ambient {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Endless white or brown noise from a small xorshift generator.
+struct Noise {
+    sound: AmbientSound,
+    seed: u64,
+    brown: f32,
+}
+
+impl Noise {
+    fn new(sound: AmbientSound) -> Self {
+        Self {
+            sound,
+            seed: 0x2545_f491_4f6c_dd1d,
+            brown: 0.0,
+        }
+    }
+
+    fn white(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        (self.seed >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
+
+impl Iterator for Noise {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let white = self.white();
+        Some(match self.sound {
+            AmbientSound::Off => 0.0,
+            AmbientSound::WhiteNoise => white * 0.5,
+            AmbientSound::BrownNoise => {
+                // leaky integration of white noise, rescaled to roughly unit range
+                self.brown = (self.brown + 0.02 * white) / 1.02;
+                (self.brown * 3.5).clamp(-1.0, 1.0)
+            }
+        })
+    }
+}
+
+impl rodio::Source for Noise {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> rodio::ChannelCount {
+        1
+    }
+
+    fn sample_rate(&self) -> rodio::SampleRate {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noise_stays_in_range() {
+        for sound in [AmbientSound::WhiteNoise, AmbientSound::BrownNoise] {
+            let samples: Vec<f32> = Noise::new(sound).take(SAMPLE_RATE as usize).collect();
+            assert!(samples.iter().all(|s| (-1.0..=1.0).contains(s)));
+            // not stuck at silence
+            assert!(samples.iter().any(|s| s.abs() > 0.1));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::{Local, NaiveDateTime};
+use rusqlite::{Connection, Result};
+use serde::Serialize;
+use std::path::Path;
+
+use crate::db;
+
+const USAGE: &str = "Usage: ferris-focus [stats [--json] | export --json <path>]";
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct StatsReport {
+    pub today_sessions: u32,
+    pub total_sessions: u32,
+    pub total_focus_secs: u32,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub level: u32,
+    pub total_xp: u32,
+}
+
+/// Runs a headless subcommand and returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["stats"] => print_stats(false),
+        ["stats", "--json"] => print_stats(true),
+        ["export", "--json", path] => export_json(Path::new(path)),
+        ["-h" | "--help"] => {
+            println!("{}", USAGE);
+            0
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    }
+}
+
+fn print_stats(json: bool) -> i32 {
+    let now = Local::now().naive_local();
+    let report = match db::init_db().and_then(|conn| stats_report(&conn, now)) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to read stats: {}", e);
+            return 1;
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(s) => println!("{}", s),
+            Err(e) => {
+                eprintln!("Failed to serialize stats: {}", e);
+                return 1;
+            }
+        }
+    } else {
+        println!("Today: {} focus sessions", report.today_sessions);
+        println!(
+            "All time: {} sessions, {}h {}m focused",
+            report.total_sessions,
+            report.total_focus_secs / 3600,
+            (report.total_focus_secs % 3600) / 60
+        );
+        println!(
+            "Streak: {} days (best: {} days)",
+            report.current_streak, report.longest_streak
+        );
+        println!("Level: {} ({} XP)", report.level, report.total_xp);
+    }
+    0
+}
+
+fn export_json(path: &Path) -> i32 {
+    let result = db::init_db()
+        .map_err(Into::into)
+        .and_then(|conn| db::export_json(&conn, path));
+    match result {
+        Ok(()) => {
+            println!("Exported to {}", path.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("Export failed: {}", e);
+            1
+        }
+    }
+}
+
+pub fn stats_report(conn: &Connection, now: NaiveDateTime) -> Result<StatsReport> {
+    let profile = db::get_profile(conn)?;
+    let settings = db::get_settings(conn)?;
+    let today = settings.day_of(now).format("%Y-%m-%d").to_string();
+    let (total_sessions, total_focus_secs) = db::get_total_stats(conn)?;
+    Ok(StatsReport {
+        today_sessions: db::get_today_session_count(conn, &today, settings.day_boundary_hour)?,
+        total_sessions,
+        total_focus_secs,
+        current_streak: profile.current_streak,
+        longest_streak: profile.longest_streak,
+        level: profile.level,
+        total_xp: profile.total_xp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Session, SessionType};
+
+    #[test]
+    fn test_stats_report() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        for started_at in ["2026-02-18T09:00:00", "2026-02-19T10:00:00"] {
+            let session = Session {
+                id: None,
+                started_at: started_at.to_string(),
+                completed_at: None,
+                duration_secs: 1500,
+                session_type: SessionType::Focus,
+                completed: true,
+                tag: None,
+                note: None,
+                intention: None,
+                xp_earned: 0,
+                interruptions: 0,
+                utc_offset_secs: None,
+            };
+            db::save_session(&conn, &session).unwrap();
+        }
+
+        let now =
+            NaiveDateTime::parse_from_str("2026-02-19T18:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let report = stats_report(&conn, now).unwrap();
+        assert_eq!(report.today_sessions, 1);
+        assert_eq!(report.total_sessions, 2);
+        assert_eq!(report.total_focus_secs, 3000);
+        assert_eq!(report.level, 1);
+    }
+}
@@ -1,20 +1,109 @@
-use chrono::NaiveDate;
-use rusqlite::{params, Connection, Result};
-use std::path::PathBuf;
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use serde::Serialize;
+use std::error::Error;
+use std::ffi::OsString;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
 
-use crate::models::{Session, UserProfile};
+use crate::achievements::Achievement;
+use crate::engine::SessionStart;
+use crate::models::{
+    self, AmbientSound, CycleMode, HeatmapPalette, Language, Session, SessionType, Settings,
+    SuspendAction, TimerConfig, TimerPreset, UserProfile, WeekStart, XpConfig,
+};
+use crate::timer::{CycleProgress, CycleTotals, Snapshot};
+use crate::xp;
+
+/// Overrides the data directory, e.g. for portable installs or separate profiles.
+pub const DATA_DIR_ENV: &str = "FERRIS_FOCUS_DATA_DIR";
 
 fn db_path() -> PathBuf {
-    let data_dir = dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("ferris-focus");
+    let data_dir = data_dir(std::env::var_os(DATA_DIR_ENV));
     std::fs::create_dir_all(&data_dir).ok();
     data_dir.join("ferris_focus.db")
 }
 
+fn data_dir(override_dir: Option<OsString>) -> PathBuf {
+    match override_dir {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ferris-focus"),
+    }
+}
+
 pub fn init_db() -> Result<Connection> {
     let conn = Connection::open(db_path())?;
+    init_schema(&conn)?;
+    Ok(conn)
+}
+
+/// Snapshots the database into `backups/` beside it, keeping the newest `keep` copies.
+pub fn backup(
+    conn: &Connection,
+    keep: u32,
+) -> std::result::Result<Option<PathBuf>, Box<dyn Error>> {
+    let Some(db_file) = conn.path().filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    let dir = Path::new(db_file)
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("backups");
+    backup_into(conn, &dir, keep)
+}
+
+/// Returns the new backup, or `None` if it would be identical to the latest one.
+fn backup_into(
+    conn: &Connection,
+    dir: &Path,
+    keep: u32,
+) -> std::result::Result<Option<PathBuf>, Box<dyn Error>> {
+    if keep == 0 {
+        return Ok(None);
+    }
+    std::fs::create_dir_all(dir)?;
+
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("ferris_focus-") && name.ends_with(".db"))
+        })
+        .collect();
+    // timestamped names sort chronologically
+    backups.sort();
+
+    let path = dir.join(format!(
+        "ferris_focus-{}.db",
+        Local::now().format("%Y%m%d-%H%M%S%3f")
+    ));
+    // VACUUM INTO writes a compact, consistent copy even while the db is open
+    conn.execute("VACUUM INTO ?1", params![path.to_string_lossy()])?;
+
+    let unchanged = match backups.last() {
+        Some(latest) => std::fs::read(latest)? == std::fs::read(&path)?,
+        None => false,
+    };
+    let created = if unchanged {
+        std::fs::remove_file(&path)?;
+        None
+    } else {
+        backups.push(path.clone());
+        Some(path)
+    };
+
+    let excess = backups.len().saturating_sub(keep as usize);
+    for old in &backups[..excess] {
+        std::fs::remove_file(old)?;
+    }
+    Ok(created)
+}
 
+pub fn init_schema(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS sessions (
@@ -35,32 +124,515 @@ pub fn init_db() -> Result<Connection> {
             last_session_date TEXT
         );
 
-        INSERT OR IGNORE INTO user_profile (id, total_xp, level, current_streak, longest_streak, last_session_date)
-        VALUES (1, 0, 1, 0, 0, NULL);
+        CREATE TABLE IF NOT EXISTS settings (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            focus_secs INTEGER NOT NULL,
+            short_break_secs INTEGER NOT NULL,
+            long_break_secs INTEGER NOT NULL,
+            sessions_before_long_break INTEGER NOT NULL
+        );
         ",
     )?;
+    insert_defaults(conn)?;
+    run_migrations(conn)
+}
 
-    Ok(conn)
+/// The profile and settings rows; columns added by migrations take their defaults.
+fn insert_defaults(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO user_profile (id, total_xp, level, current_streak, longest_streak, last_session_date)
+         VALUES (1, 0, 1, 0, 0, NULL)",
+        [],
+    )?;
+
+    let defaults = TimerConfig::default();
+    conn.execute(
+        "INSERT OR IGNORE INTO settings (id, focus_secs, short_break_secs, long_break_secs, sessions_before_long_break)
+         VALUES (1, ?1, ?2, ?3, ?4)",
+        params![
+            defaults.focus_secs,
+            defaults.short_break_secs,
+            defaults.long_break_secs,
+            defaults.sessions_before_long_break,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Deletes all sessions, progress and settings, leaving the database as a
+/// fresh install would have it.
+pub fn reset_all(conn: &Connection) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        "DELETE FROM sessions;
+         DELETE FROM achievements;
+         DELETE FROM user_profile;
+         DELETE FROM settings;
+         DELETE FROM active_timer;",
+    )?;
+    insert_defaults(&tx)?;
+    save_presets(&tx, &TimerPreset::defaults())?;
+    tx.commit()
+}
+
+type Migration = fn(&Connection) -> Result<()>;
+
+/// Schema changes after the tables above first shipped. Entry `i` upgrades a
+/// database from `user_version` i to i + 1; append new ones, never edit old ones.
+const MIGRATIONS: &[Migration] = &[
+    migrate_v1,
+    migrate_v2,
+    migrate_v3,
+    migrate_v4,
+    migrate_v5,
+    migrate_v6,
+    migrate_v7,
+    migrate_v8,
+    migrate_v9,
+    migrate_v10,
+    migrate_v11,
+    migrate_v12,
+    migrate_v13,
+    migrate_v14,
+    migrate_v15,
+    migrate_v16,
+    migrate_v17,
+    migrate_v18,
+    migrate_v19,
+    migrate_v20,
+    migrate_v21,
+    migrate_v22,
+    migrate_v23,
+    migrate_v24,
+    migrate_v25,
+    migrate_v26,
+    migrate_v27,
+    migrate_v28,
+    migrate_v29,
+    migrate_v30,
+    migrate_v31,
+    migrate_v32,
+    migrate_v33,
+    migrate_v34,
+    migrate_v35,
+    migrate_v36,
+    migrate_v37,
+    migrate_v38,
+];
+
+fn run_migrations(conn: &Connection) -> Result<()> {
+    let version: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (i, migration) in (1u32..).zip(MIGRATIONS).skip(version as usize) {
+        let tx = conn.unchecked_transaction()?;
+        migration(&tx)?;
+        tx.pragma_update(None, "user_version", i)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+/// Columns added before schema versioning existed. Unversioned databases may
+/// already have any subset of them, so each one is checked first.
+fn migrate_v1(conn: &Connection) -> Result<()> {
+    add_column_if_missing(
+        conn,
+        "user_profile",
+        "freezes_available",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(conn, "settings", "auto_start", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "auto_start_delay_secs",
+        "INTEGER NOT NULL DEFAULT 3",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "sound_enabled",
+        "BOOLEAN NOT NULL DEFAULT 1",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "auto_abandon_after_secs",
+        "INTEGER NOT NULL DEFAULT 900",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "minimize_to_tray",
+        "BOOLEAN NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "reset_cycle_daily",
+        "BOOLEAN NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "theme",
+        "TEXT NOT NULL DEFAULT 'Catppuccin Mocha'",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "light_from_hour",
+        "INTEGER NOT NULL DEFAULT 7",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "dark_from_hour",
+        "INTEGER NOT NULL DEFAULT 19",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "backups_to_keep",
+        "INTEGER NOT NULL DEFAULT 5",
+    )?;
+    Ok(())
+}
+
+/// Optional project tag on sessions
+fn migrate_v2(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE sessions ADD COLUMN tag TEXT")
+}
+
+/// Session notes and the setting that prompts for them
+fn migrate_v3(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE sessions ADD COLUMN note TEXT;
+         ALTER TABLE settings ADD COLUMN prompt_for_notes BOOLEAN NOT NULL DEFAULT 0;",
+    )
+}
+
+/// Ambient noise during focus
+fn migrate_v4(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN ambient_sound TEXT NOT NULL DEFAULT 'off';
+         ALTER TABLE settings ADD COLUMN ambient_volume_percent INTEGER NOT NULL DEFAULT 30;",
+    )
+}
+
+/// Quiet hours for notifications
+fn migrate_v5(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN quiet_hours_enabled BOOLEAN NOT NULL DEFAULT 0;
+         ALTER TABLE settings ADD COLUMN quiet_from_hour INTEGER NOT NULL DEFAULT 22;
+         ALTER TABLE settings ADD COLUMN quiet_until_hour INTEGER NOT NULL DEFAULT 7;",
+    )
+}
+
+fn migrate_v6(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN on_suspend TEXT NOT NULL DEFAULT 'finish';")
+}
+
+fn migrate_v7(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN always_on_top BOOLEAN NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v8(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN window_x INTEGER;
+         ALTER TABLE settings ADD COLUMN window_y INTEGER;
+         ALTER TABLE settings ADD COLUMN window_width INTEGER NOT NULL DEFAULT 320;
+         ALTER TABLE settings ADD COLUMN window_height INTEGER NOT NULL DEFAULT 540;",
+    )
+}
+
+fn migrate_v9(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN compact_mode BOOLEAN NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v10(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN streak_reminder_enabled BOOLEAN NOT NULL DEFAULT 1;
+         ALTER TABLE settings ADD COLUMN streak_reminder_hour INTEGER NOT NULL DEFAULT 20;
+         ALTER TABLE user_profile ADD COLUMN last_reminder_date TEXT;",
+    )
+}
+
+fn migrate_v11(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE user_profile ADD COLUMN last_weekly_summary_date TEXT;
+         ALTER TABLE user_profile ADD COLUMN weekly_summary_xp INTEGER NOT NULL DEFAULT 0;",
+    )
+}
+
+fn migrate_v12(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN focus_complete_message TEXT NOT NULL DEFAULT '';
+         ALTER TABLE settings ADD COLUMN break_over_message TEXT NOT NULL DEFAULT '';
+         ALTER TABLE settings ADD COLUMN level_up_message TEXT NOT NULL DEFAULT '';",
+    )
+}
+
+fn migrate_v13(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN hide_gamification BOOLEAN NOT NULL DEFAULT 0;
+         ALTER TABLE settings ADD COLUMN track_xp BOOLEAN NOT NULL DEFAULT 1;",
+    )
+}
+
+fn migrate_v14(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN use_24h_clock BOOLEAN NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v15(conn: &Connection) -> Result<()> {
+    let defaults = XpConfig::default();
+    conn.execute_batch(&format!(
+        "ALTER TABLE settings ADD COLUMN base_xp INTEGER NOT NULL DEFAULT {};
+         ALTER TABLE settings ADD COLUMN streak_bonus_per_day INTEGER NOT NULL DEFAULT {};
+         ALTER TABLE settings ADD COLUMN max_streak_bonus INTEGER NOT NULL DEFAULT {};",
+        defaults.base_xp, defaults.streak_bonus_per_day, defaults.max_streak_bonus
+    ))
+}
+
+fn migrate_v16(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE achievements (
+            id TEXT PRIMARY KEY,
+            unlocked_at TEXT NOT NULL
+        );",
+    )
+}
+
+fn migrate_v17(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN prompt_for_intention BOOLEAN NOT NULL DEFAULT 0;
+         ALTER TABLE sessions ADD COLUMN intention TEXT;",
+    )
+}
+
+/// XP wasn't kept per session before, so earlier focus sessions are
+/// credited the base reward as a best guess.
+fn migrate_v18(conn: &Connection) -> Result<()> {
+    conn.execute_batch(&format!(
+        "ALTER TABLE sessions ADD COLUMN xp_earned INTEGER NOT NULL DEFAULT 0;
+         UPDATE sessions
+         SET xp_earned = COALESCE((SELECT base_xp FROM settings WHERE id = 1), {})
+         WHERE session_type = 'focus' AND completed = 1;",
+        XpConfig::default().base_xp
+    ))
+}
+
+fn migrate_v19(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN week_starts_on TEXT NOT NULL DEFAULT 'monday';",
+    )
+}
+
+fn migrate_v20(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN extend_secs INTEGER NOT NULL DEFAULT 300;")
+}
+
+fn migrate_v21(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN active_preset TEXT;
+         CREATE TABLE timer_presets (
+             id INTEGER PRIMARY KEY,
+             name TEXT NOT NULL UNIQUE,
+             focus_secs INTEGER NOT NULL,
+             short_break_secs INTEGER NOT NULL,
+             long_break_secs INTEGER NOT NULL,
+             sessions_before_long_break INTEGER NOT NULL
+         );",
+    )?;
+    save_presets(conn, &TimerPreset::defaults())
+}
+
+fn migrate_v22(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE sessions ADD COLUMN interruptions INTEGER NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v23(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN reduce_motion BOOLEAN NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v24(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN heatmap_palette TEXT NOT NULL DEFAULT 'theme';",
+    )
+}
+
+fn migrate_v25(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN smooth_ring BOOLEAN NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v26(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN cycle_mode TEXT NOT NULL DEFAULT 'running';",
+    )
+}
+
+fn migrate_v27(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE active_timer (
+             id INTEGER PRIMARY KEY CHECK (id = 1),
+             session_type TEXT NOT NULL,
+             ends_at TEXT,
+             remaining_secs INTEGER NOT NULL,
+             focus_secs INTEGER NOT NULL,
+             short_break_secs INTEGER NOT NULL,
+             long_break_secs INTEGER NOT NULL,
+             sessions_before_long_break INTEGER NOT NULL,
+             extended_secs INTEGER NOT NULL,
+             interruptions INTEGER NOT NULL,
+             started_at TEXT NOT NULL,
+             tag TEXT,
+             intention TEXT
+         );",
+    )
+}
+
+fn migrate_v28(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN language TEXT NOT NULL DEFAULT 'en';")
+}
+
+fn migrate_v29(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN stage_changes_only BOOLEAN NOT NULL DEFAULT 0;",
+    )
+}
+
+fn migrate_v30(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN day_boundary_hour INTEGER NOT NULL DEFAULT 0;",
+    )
+}
+
+fn migrate_v31(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN daily_goal INTEGER NOT NULL DEFAULT 4;")
+}
+
+fn migrate_v32(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE settings ADD COLUMN status_file BOOLEAN NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v33(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN http_enabled BOOLEAN NOT NULL DEFAULT 0;
+         ALTER TABLE settings ADD COLUMN http_port INTEGER NOT NULL DEFAULT 7878;",
+    )
+}
+
+/// Sessions keep their local wall-clock times; rows from here on also note
+/// their UTC offset. Older rows stay as they are, with the offset unknown.
+fn migrate_v34(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE sessions ADD COLUMN utc_offset_secs INTEGER;")
+}
+
+fn migrate_v35(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN weekly_goal INTEGER NOT NULL DEFAULT 20;
+         ALTER TABLE user_profile ADD COLUMN last_weekly_goal_week TEXT;",
+    )
+}
+
+fn migrate_v36(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE user_profile ADD COLUMN cycle_focus_sessions INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE user_profile ADD COLUMN cycle_sessions INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE user_profile ADD COLUMN cycle_reset_date TEXT;",
+    )
+}
+
+fn migrate_v37(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE settings ADD COLUMN idle_pause BOOLEAN NOT NULL DEFAULT 0;
+         ALTER TABLE settings ADD COLUMN idle_threshold_secs INTEGER NOT NULL DEFAULT 300;",
+    )
+}
+
+fn migrate_v38(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE user_profile ADD COLUMN cycle_focus_secs INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE user_profile ADD COLUMN finished_cycle_sessions INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE user_profile ADD COLUMN finished_cycle_focus_secs INTEGER NOT NULL DEFAULT 0;",
+    )
+}
+
+/// Only needed by migrations that may meet a column an unversioned build already added.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let exists = conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1",
+            table
+        ))?
+        .exists([column])?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))?;
+    }
+    Ok(())
 }
 
-pub fn save_session(conn: &Connection, session: &Session) -> Result<()> {
+/// Returns the new row's id.
+pub fn save_session(conn: &Connection, session: &Session) -> Result<i64> {
     conn.execute(
-        "INSERT INTO sessions (started_at, completed_at, duration_secs, session_type, completed)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
+        "INSERT INTO sessions (started_at, completed_at, duration_secs, session_type, completed, tag, note, intention, xp_earned, interruptions, utc_offset_secs)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         params![
             session.started_at,
             session.completed_at,
             session.duration_secs,
             session.session_type.as_str(),
             session.completed,
+            session.tag,
+            session.note,
+            session.intention,
+            session.xp_earned,
+            session.interruptions,
+            session.utc_offset_secs,
         ],
     )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn set_session_note(conn: &Connection, id: i64, note: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET note = ?2 WHERE id = ?1",
+        params![id, note],
+    )?;
     Ok(())
 }
 
+/// Deletes session `id` if nothing was saved after it; returns whether it did.
+pub fn delete_last_session(conn: &Connection, id: i64) -> Result<bool> {
+    let deleted = conn.execute(
+        "DELETE FROM sessions WHERE id = ?1 AND id = (SELECT MAX(id) FROM sessions)",
+        params![id],
+    )?;
+    Ok(deleted > 0)
+}
+
+/// Most recent notes first, as (started_at, note)
+pub fn get_recent_notes(conn: &Connection, limit: u32) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT started_at, note FROM sessions
+         WHERE note IS NOT NULL AND note != ''
+         ORDER BY started_at DESC, id DESC
+         LIMIT ?1",
+    )?;
+    let rows = stmt.query_map([limit], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
 pub fn get_profile(conn: &Connection) -> Result<UserProfile> {
     conn.query_row(
-        "SELECT total_xp, level, current_streak, longest_streak, last_session_date FROM user_profile WHERE id = 1",
+        "SELECT total_xp, level, current_streak, longest_streak, last_session_date, freezes_available FROM user_profile WHERE id = 1",
         [],
         |row| {
             let last_date_str: Option<String> = row.get(4)?;
@@ -71,6 +643,7 @@ pub fn get_profile(conn: &Connection) -> Result<UserProfile> {
                 current_streak: row.get(2)?,
                 longest_streak: row.get(3)?,
                 last_session_date,
+                freezes_available: row.get(5)?,
             })
         },
     )
@@ -81,135 +654,1628 @@ pub fn update_profile(conn: &Connection, profile: &UserProfile) -> Result<()> {
         .last_session_date
         .map(|d| d.format("%Y-%m-%d").to_string());
     conn.execute(
-        "UPDATE user_profile SET total_xp = ?1, level = ?2, current_streak = ?3, longest_streak = ?4, last_session_date = ?5 WHERE id = 1",
+        "UPDATE user_profile SET total_xp = ?1, level = ?2, current_streak = ?3, longest_streak = ?4, last_session_date = ?5, freezes_available = ?6 WHERE id = 1",
         params![
             profile.total_xp,
             profile.level,
             profile.current_streak,
             profile.longest_streak,
             last_date_str,
+            profile.freezes_available,
         ],
     )?;
     Ok(())
 }
 
-pub fn get_today_session_count(conn: &Connection, today: &str) -> Result<u32> {
-    conn.query_row(
-        "SELECT COUNT(*) FROM sessions WHERE session_type = 'focus' AND completed = 1 AND started_at LIKE ?1",
-        params![format!("{}%", today)],
+/// Day the streak-at-risk reminder last went out
+pub fn get_last_reminder_date(conn: &Connection) -> Result<Option<NaiveDate>> {
+    let date: Option<String> = conn.query_row(
+        "SELECT last_reminder_date FROM user_profile WHERE id = 1",
+        [],
         |row| row.get(0),
-    )
+    )?;
+    Ok(date.and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()))
 }
 
-pub fn get_sessions_in_range(
-    conn: &Connection,
-    start: &str,
-    end: &str,
-) -> Result<Vec<(String, u32)>> {
-    let mut stmt = conn.prepare(
-        "SELECT substr(started_at, 1, 10) as day, COUNT(*) as cnt
-         FROM sessions
-         WHERE session_type = 'focus' AND completed = 1
-           AND substr(started_at, 1, 10) >= ?1
-           AND substr(started_at, 1, 10) <= ?2
-         GROUP BY day
-         ORDER BY day",
+pub fn set_last_reminder_date(conn: &Connection, date: NaiveDate) -> Result<()> {
+    conn.execute(
+        "UPDATE user_profile SET last_reminder_date = ?1 WHERE id = 1",
+        params![date.format("%Y-%m-%d").to_string()],
     )?;
+    Ok(())
+}
 
-    let rows = stmt.query_map(params![start, end], |row| {
-        Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+/// Unlocked achievements with when they were unlocked, oldest first
+pub fn get_achievements(conn: &Connection) -> Result<Vec<(Achievement, String)>> {
+    let mut stmt =
+        conn.prepare("SELECT id, unlocked_at FROM achievements ORDER BY unlocked_at, id")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
     })?;
-
-    let mut results = Vec::new();
+    let mut unlocked = Vec::new();
     for row in rows {
-        results.push(row?);
+        let (id, at) = row?;
+        // ids from a newer version are kept in the table but not shown
+        if let Ok(achievement) = id.parse() {
+            unlocked.push((achievement, at));
+        }
     }
-    Ok(results)
+    Ok(unlocked)
 }
 
-pub fn get_total_stats(conn: &Connection) -> Result<(u32, u32)> {
+/// Records an achievement; unlocking one twice keeps the first date.
+pub fn unlock_achievement(conn: &Connection, achievement: Achievement, at: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO achievements (id, unlocked_at) VALUES (?1, ?2)",
+        params![achievement.as_str(), at],
+    )?;
+    Ok(())
+}
+
+/// Week start the last weekly summary covered up to, and total XP at that point
+pub fn get_last_weekly_summary(conn: &Connection) -> Result<(Option<NaiveDate>, u32)> {
     conn.query_row(
-        "SELECT COUNT(*), COALESCE(SUM(duration_secs), 0) FROM sessions WHERE session_type = 'focus' AND completed = 1",
+        "SELECT last_weekly_summary_date, weekly_summary_xp FROM user_profile WHERE id = 1",
         [],
-        |row| Ok((row.get(0)?, row.get(1)?)),
+        |row| {
+            let date: Option<String> = row.get(0)?;
+            Ok((
+                date.and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
+                row.get(1)?,
+            ))
+        },
     )
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::SessionType;
-    use rusqlite::Connection;
+pub fn set_last_weekly_summary(
+    conn: &Connection,
+    week_start: NaiveDate,
+    total_xp: u32,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE user_profile SET last_weekly_summary_date = ?1, weekly_summary_xp = ?2 WHERE id = 1",
+        params![week_start.format("%Y-%m-%d").to_string(), total_xp],
+    )?;
+    Ok(())
+}
 
-    fn in_memory_db() -> Connection {
-        let conn = Connection::open_in_memory().unwrap();
-        conn.execute_batch(
-            "
-            CREATE TABLE sessions (
-                id INTEGER PRIMARY KEY,
-                started_at TEXT NOT NULL,
-                completed_at TEXT,
-                duration_secs INTEGER NOT NULL,
-                session_type TEXT NOT NULL,
-                completed BOOLEAN NOT NULL DEFAULT 0
-            );
-            CREATE TABLE user_profile (
-                id INTEGER PRIMARY KEY CHECK (id = 1),
-                total_xp INTEGER NOT NULL DEFAULT 0,
-                level INTEGER NOT NULL DEFAULT 1,
-                current_streak INTEGER NOT NULL DEFAULT 0,
-                longest_streak INTEGER NOT NULL DEFAULT 0,
-                last_session_date TEXT
-            );
-            INSERT INTO user_profile (id, total_xp, level, current_streak, longest_streak, last_session_date)
-            VALUES (1, 0, 1, 0, 0, NULL);
-            ",
-        )
-        .unwrap();
-        conn
-    }
+/// ISO week (`%G-W%V`) the weekly goal was last met in
+pub fn get_last_weekly_goal_week(conn: &Connection) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT last_weekly_goal_week FROM user_profile WHERE id = 1",
+        [],
+        |row| row.get(0),
+    )
+}
 
-    #[test]
-    fn test_init_and_save() {
-        let conn = in_memory_db();
-        let session = Session {
-            id: None,
-            started_at: "2026-02-19T10:00:00".to_string(),
-            completed_at: Some("2026-02-19T10:25:00".to_string()),
-            duration_secs: 1500,
-            session_type: SessionType::Focus,
-            completed: true,
-        };
+pub fn set_last_weekly_goal_week(conn: &Connection, week: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE user_profile SET last_weekly_goal_week = ?1 WHERE id = 1",
+        params![week],
+    )?;
+    Ok(())
+}
+
+pub fn get_cycle_progress(conn: &Connection) -> Result<CycleProgress> {
+    conn.query_row(
+        "SELECT cycle_focus_sessions, cycle_sessions, cycle_reset_date, cycle_focus_secs,
+                finished_cycle_sessions, finished_cycle_focus_secs
+         FROM user_profile WHERE id = 1",
+        [],
+        |row| {
+            let date: Option<String> = row.get(2)?;
+            let finished_sessions: u32 = row.get(4)?;
+            Ok(CycleProgress {
+                focus_sessions_completed: row.get(0)?,
+                cycle_sessions: row.get(1)?,
+                cycle_focus_secs: row.get(3)?,
+                // no sessions stands for no finished cycle
+                finished_cycle: match finished_sessions {
+                    0 => None,
+                    sessions => Some(CycleTotals {
+                        sessions,
+                        focus_secs: row.get(5)?,
+                    }),
+                },
+                reset_date: date.and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
+            })
+        },
+    )
+}
+
+pub fn save_cycle_progress(conn: &Connection, progress: &CycleProgress) -> Result<()> {
+    conn.execute(
+        "UPDATE user_profile SET cycle_focus_sessions = ?1, cycle_sessions = ?2,
+                cycle_reset_date = ?3, cycle_focus_secs = ?4,
+                finished_cycle_sessions = ?5, finished_cycle_focus_secs = ?6
+         WHERE id = 1",
+        params![
+            progress.focus_sessions_completed,
+            progress.cycle_sessions,
+            progress
+                .reset_date
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+            progress.cycle_focus_secs,
+            progress.finished_cycle.map_or(0, |cycle| cycle.sessions),
+            progress.finished_cycle.map_or(0, |cycle| cycle.focus_secs),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Rebuilds current/longest streak from focus history and stores them.
+/// Freezes aren't replayed, so a bridged gap counts as a break here.
+pub fn recompute_streaks(conn: &Connection, day_boundary_hour: u32) -> Result<(u32, u32)> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT date(started_at, ?1) as day FROM sessions
+         WHERE session_type = 'focus' AND completed = 1 AND day IS NOT NULL
+         ORDER BY day",
+    )?;
+    let dates: Vec<NaiveDate> = stmt
+        .query_map(params![day_shift(day_boundary_hour)], |row| {
+            row.get::<_, String>(0)
+        })?
+        .filter_map(|day| day.ok())
+        .filter_map(|day| NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok())
+        .collect();
+
+    let today = models::day_of(Local::now().naive_local(), day_boundary_hour);
+    let (current, longest) = xp::streaks_from_dates(&dates, today);
+    let last_date = dates.last().map(|d| d.format("%Y-%m-%d").to_string());
+    conn.execute(
+        "UPDATE user_profile SET current_streak = ?1, longest_streak = ?2, last_session_date = ?3 WHERE id = 1",
+        params![current, longest, last_date],
+    )?;
+    Ok((current, longest))
+}
+
+pub fn get_settings(conn: &Connection) -> Result<Settings> {
+    let presets = get_presets(conn)?;
+    conn.query_row(
+        "SELECT focus_secs, short_break_secs, long_break_secs, sessions_before_long_break,
+                auto_start, auto_start_delay_secs, sound_enabled, auto_abandon_after_secs,
+                minimize_to_tray, reset_cycle_daily, theme, light_from_hour, dark_from_hour,
+                backups_to_keep, prompt_for_notes, ambient_sound, ambient_volume_percent,
+                quiet_hours_enabled, quiet_from_hour, quiet_until_hour, on_suspend,
+                always_on_top, window_x, window_y, window_width, window_height,
+                compact_mode, streak_reminder_enabled, streak_reminder_hour,
+                focus_complete_message, break_over_message, level_up_message,
+                hide_gamification, track_xp,
+                use_24h_clock, base_xp, streak_bonus_per_day, max_streak_bonus,
+                prompt_for_intention, week_starts_on, extend_secs,
+                active_preset,
+                reduce_motion,
+                heatmap_palette,
+                smooth_ring,
+                cycle_mode,
+                language,
+                stage_changes_only,
+                day_boundary_hour,
+                daily_goal,
+                status_file,
+                http_enabled, http_port,
+                weekly_goal,
+                idle_pause, idle_threshold_secs
+         FROM settings WHERE id = 1",
+        [],
+        |row| {
+            Ok(Settings {
+                timer: TimerConfig {
+                    focus_secs: row.get(0)?,
+                    short_break_secs: row.get(1)?,
+                    long_break_secs: row.get(2)?,
+                    sessions_before_long_break: row.get(3)?,
+                },
+                presets,
+                xp: XpConfig {
+                    base_xp: row.get(35)?,
+                    streak_bonus_per_day: row.get(36)?,
+                    max_streak_bonus: row.get(37)?,
+                },
+                auto_start: row.get(4)?,
+                auto_start_delay_secs: row.get(5)?,
+                sound_enabled: row.get(6)?,
+                auto_abandon_after_secs: row.get(7)?,
+                minimize_to_tray: row.get(8)?,
+                reset_cycle_daily: row.get(9)?,
+                theme: row.get(10)?,
+                light_from_hour: row.get(11)?,
+                dark_from_hour: row.get(12)?,
+                backups_to_keep: row.get(13)?,
+                prompt_for_notes: row.get(14)?,
+                ambient_sound: row
+                    .get::<_, String>(15)?
+                    .parse()
+                    .unwrap_or(AmbientSound::Off),
+                ambient_volume_percent: row.get(16)?,
+                quiet_hours_enabled: row.get(17)?,
+                quiet_from_hour: row.get(18)?,
+                quiet_until_hour: row.get(19)?,
+                on_suspend: row
+                    .get::<_, String>(20)?
+                    .parse()
+                    .unwrap_or(SuspendAction::Finish),
+                always_on_top: row.get(21)?,
+                window_position: match (row.get(22)?, row.get(23)?) {
+                    (Some(x), Some(y)) => Some((x, y)),
+                    _ => None,
+                },
+                window_width: row.get(24)?,
+                window_height: row.get(25)?,
+                compact_mode: row.get(26)?,
+                streak_reminder_enabled: row.get(27)?,
+                streak_reminder_hour: row.get(28)?,
+                focus_complete_message: row.get(29)?,
+                break_over_message: row.get(30)?,
+                level_up_message: row.get(31)?,
+                hide_gamification: row.get(32)?,
+                track_xp: row.get(33)?,
+                use_24h_clock: row.get(34)?,
+                prompt_for_intention: row.get(38)?,
+                week_starts_on: row
+                    .get::<_, String>(39)?
+                    .parse()
+                    .unwrap_or(WeekStart::Monday),
+                extend_secs: row.get(40)?,
+                active_preset: row.get(41)?,
+                reduce_motion: row.get(42)?,
+                heatmap_palette: row
+                    .get::<_, String>(43)?
+                    .parse()
+                    .unwrap_or(HeatmapPalette::Theme),
+                smooth_ring: row.get(44)?,
+                cycle_mode: row
+                    .get::<_, String>(45)?
+                    .parse()
+                    .unwrap_or(CycleMode::Running),
+                language: row
+                    .get::<_, String>(46)?
+                    .parse()
+                    .unwrap_or(Language::English),
+                stage_changes_only: row.get(47)?,
+                day_boundary_hour: row.get(48)?,
+                daily_goal: row.get(49)?,
+                status_file: row.get(50)?,
+                http_enabled: row.get(51)?,
+                http_port: row.get(52)?,
+                weekly_goal: row.get(53)?,
+                idle_pause: row.get(54)?,
+                idle_threshold_secs: row.get(55)?,
+            })
+        },
+    )
+}
+
+pub fn save_settings(conn: &Connection, settings: &Settings) -> Result<()> {
+    let config = &settings.timer;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE settings SET focus_secs = ?1, short_break_secs = ?2, long_break_secs = ?3, sessions_before_long_break = ?4,
+                auto_start = ?5, auto_start_delay_secs = ?6, sound_enabled = ?7,
+                auto_abandon_after_secs = ?8, minimize_to_tray = ?9,
+                reset_cycle_daily = ?10, theme = ?11, light_from_hour = ?12,
+                dark_from_hour = ?13, backups_to_keep = ?14,
+                prompt_for_notes = ?15, ambient_sound = ?16, ambient_volume_percent = ?17,
+                quiet_hours_enabled = ?18, quiet_from_hour = ?19, quiet_until_hour = ?20,
+                on_suspend = ?21, always_on_top = ?22,
+                window_x = ?23, window_y = ?24, window_width = ?25, window_height = ?26,
+                compact_mode = ?27, streak_reminder_enabled = ?28, streak_reminder_hour = ?29,
+                focus_complete_message = ?30, break_over_message = ?31, level_up_message = ?32,
+                hide_gamification = ?33, track_xp = ?34,
+                use_24h_clock = ?35, base_xp = ?36, streak_bonus_per_day = ?37,
+                max_streak_bonus = ?38,
+                prompt_for_intention = ?39,
+                week_starts_on = ?40,
+                extend_secs = ?41,
+                active_preset = ?42,
+                reduce_motion = ?43,
+                heatmap_palette = ?44,
+                smooth_ring = ?45,
+                cycle_mode = ?46,
+                language = ?47,
+                stage_changes_only = ?48,
+                day_boundary_hour = ?49,
+                daily_goal = ?50,
+                status_file = ?51,
+                http_enabled = ?52, http_port = ?53,
+                weekly_goal = ?54,
+                idle_pause = ?55, idle_threshold_secs = ?56
+         WHERE id = 1",
+        params![
+            config.focus_secs,
+            config.short_break_secs,
+            config.long_break_secs,
+            config.sessions_before_long_break,
+            settings.auto_start,
+            settings.auto_start_delay_secs,
+            settings.sound_enabled,
+            settings.auto_abandon_after_secs,
+            settings.minimize_to_tray,
+            settings.reset_cycle_daily,
+            settings.theme,
+            settings.light_from_hour,
+            settings.dark_from_hour,
+            settings.backups_to_keep,
+            settings.prompt_for_notes,
+            settings.ambient_sound.as_str(),
+            settings.ambient_volume_percent,
+            settings.quiet_hours_enabled,
+            settings.quiet_from_hour,
+            settings.quiet_until_hour,
+            settings.on_suspend.as_str(),
+            settings.always_on_top,
+            settings.window_position.map(|(x, _)| x),
+            settings.window_position.map(|(_, y)| y),
+            settings.window_width,
+            settings.window_height,
+            settings.compact_mode,
+            settings.streak_reminder_enabled,
+            settings.streak_reminder_hour,
+            settings.focus_complete_message,
+            settings.break_over_message,
+            settings.level_up_message,
+            settings.hide_gamification,
+            settings.track_xp,
+            settings.use_24h_clock,
+            settings.xp.base_xp,
+            settings.xp.streak_bonus_per_day,
+            settings.xp.max_streak_bonus,
+            settings.prompt_for_intention,
+            settings.week_starts_on.as_str(),
+            settings.extend_secs,
+            settings.active_preset,
+            settings.reduce_motion,
+            settings.heatmap_palette.as_str(),
+            settings.smooth_ring,
+            settings.cycle_mode.as_str(),
+            settings.language.as_str(),
+            settings.stage_changes_only,
+            settings.day_boundary_hour,
+            settings.daily_goal,
+            settings.status_file,
+            settings.http_enabled,
+            settings.http_port,
+            settings.weekly_goal,
+            settings.idle_pause,
+            settings.idle_threshold_secs,
+        ],
+    )?;
+    save_presets(&tx, &settings.presets)?;
+    tx.commit()
+}
+
+pub fn get_presets(conn: &Connection) -> Result<Vec<TimerPreset>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, focus_secs, short_break_secs, long_break_secs, sessions_before_long_break
+         FROM timer_presets ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(TimerPreset {
+            name: row.get(0)?,
+            config: TimerConfig {
+                focus_secs: row.get(1)?,
+                short_break_secs: row.get(2)?,
+                long_break_secs: row.get(3)?,
+                sessions_before_long_break: row.get(4)?,
+            },
+        })
+    })?;
+    rows.collect()
+}
+
+/// Replaces the stored presets, keeping their order.
+fn save_presets(conn: &Connection, presets: &[TimerPreset]) -> Result<()> {
+    conn.execute("DELETE FROM timer_presets", [])?;
+    for preset in presets {
+        conn.execute(
+            "INSERT INTO timer_presets (name, focus_secs, short_break_secs, long_break_secs, sessions_before_long_break)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                preset.name,
+                preset.config.focus_secs,
+                preset.config.short_break_secs,
+                preset.config.long_break_secs,
+                preset.config.sessions_before_long_break,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Timestamps are stored as local wall-clock time in this format, and day and
+/// range queries compare them as such. Sessions also keep `utc_offset_secs`.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Saves the countdown under way, or clears it with `None`.
+pub fn save_active_timer(
+    conn: &Connection,
+    active: Option<(&Snapshot, &SessionStart)>,
+) -> Result<()> {
+    let Some((snapshot, start)) = active else {
+        conn.execute("DELETE FROM active_timer", [])?;
+        return Ok(());
+    };
+    conn.execute(
+        "INSERT OR REPLACE INTO active_timer (id, session_type, ends_at, remaining_secs,
+             focus_secs, short_break_secs, long_break_secs, sessions_before_long_break,
+             extended_secs, interruptions, started_at, tag, intention)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            snapshot.session_type.as_str(),
+            snapshot
+                .ends_at
+                .map(|at| at.format(TIMESTAMP_FORMAT).to_string()),
+            snapshot.remaining_secs,
+            snapshot.config.focus_secs,
+            snapshot.config.short_break_secs,
+            snapshot.config.long_break_secs,
+            snapshot.config.sessions_before_long_break,
+            snapshot.extended_secs,
+            snapshot.interruptions,
+            start.started_at,
+            start.tag,
+            start.intention,
+        ],
+    )?;
+    Ok(())
+}
+
+/// The countdown that was under way when the app last closed, if any.
+pub fn get_active_timer(conn: &Connection) -> Result<Option<(Snapshot, SessionStart)>> {
+    conn.query_row(
+        "SELECT session_type, ends_at, remaining_secs, focus_secs, short_break_secs,
+                long_break_secs, sessions_before_long_break, extended_secs, interruptions,
+                started_at, tag, intention
+         FROM active_timer WHERE id = 1",
+        [],
+        |row| {
+            let session_type: String = row.get(0)?;
+            let ends_at: Option<String> = row.get(1)?;
+            let snapshot = Snapshot {
+                session_type: session_type.parse().map_err(|e: String| {
+                    rusqlite::Error::FromSqlConversionFailure(0, Type::Text, e.into())
+                })?,
+                ends_at: ends_at
+                    .map(|at| NaiveDateTime::parse_from_str(&at, TIMESTAMP_FORMAT))
+                    .transpose()
+                    .map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(1, Type::Text, e.into())
+                    })?,
+                remaining_secs: row.get(2)?,
+                config: TimerConfig {
+                    focus_secs: row.get(3)?,
+                    short_break_secs: row.get(4)?,
+                    long_break_secs: row.get(5)?,
+                    sessions_before_long_break: row.get(6)?,
+                },
+                extended_secs: row.get(7)?,
+                interruptions: row.get(8)?,
+            };
+            let start = SessionStart {
+                started_at: row.get(9)?,
+                tag: row.get(10)?,
+                intention: row.get(11)?,
+            };
+            Ok((snapshot, start))
+        },
+    )
+    .optional()
+}
+
+/// SQLite date modifier moving a timestamp back by the day boundary, so
+/// `date(started_at, ..)` gives the day a session counts towards.
+fn day_shift(day_boundary_hour: u32) -> String {
+    format!("-{} hours", day_boundary_hour)
+}
+
+pub fn get_today_session_count(
+    conn: &Connection,
+    today: &str,
+    day_boundary_hour: u32,
+) -> Result<u32> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sessions
+         WHERE session_type = 'focus' AND completed = 1 AND date(started_at, ?2) = ?1",
+        params![today, day_shift(day_boundary_hour)],
+        |row| row.get(0),
+    )
+}
+
+pub fn get_abandoned_count(conn: &Connection, today: &str, day_boundary_hour: u32) -> Result<u32> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sessions
+         WHERE session_type = 'focus' AND completed = 0 AND date(started_at, ?2) = ?1",
+        params![today, day_shift(day_boundary_hour)],
+        |row| row.get(0),
+    )
+}
+
+pub fn get_sessions_in_range(
+    conn: &Connection,
+    start: &str,
+    end: &str,
+    day_boundary_hour: u32,
+) -> Result<Vec<(String, u32)>> {
+    let mut stmt = conn.prepare(
+        "SELECT date(started_at, ?3) as day, COUNT(*) as cnt
+         FROM sessions
+         WHERE session_type = 'focus' AND completed = 1
+           AND day >= ?1
+           AND day <= ?2
+         GROUP BY day
+         ORDER BY day",
+    )?;
+
+    let rows = stmt.query_map(params![start, end, day_shift(day_boundary_hour)], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row?);
+    }
+    Ok(results)
+}
+
+/// The day with the most completed focus sessions, if there is any history
+pub fn get_best_day(conn: &Connection, day_boundary_hour: u32) -> Result<Option<(String, u32)>> {
+    let mut stmt = conn.prepare(
+        "SELECT date(started_at, ?1) as day, COUNT(*) as cnt
+         FROM sessions
+         WHERE session_type = 'focus' AND completed = 1 AND day IS NOT NULL
+         GROUP BY day
+         ORDER BY cnt DESC, day DESC
+         LIMIT 1",
+    )?;
+    let mut rows = stmt.query_map(params![day_shift(day_boundary_hour)], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })?;
+    rows.next().transpose()
+}
+
+/// XP earned per day between two `%Y-%m-%d` days, inclusive; days without
+/// any are left out
+pub fn get_daily_xp(
+    conn: &Connection,
+    start: &str,
+    end: &str,
+    day_boundary_hour: u32,
+) -> Result<Vec<(String, u32)>> {
+    let mut stmt = conn.prepare(
+        "SELECT date(started_at, ?3) as day, SUM(xp_earned)
+         FROM sessions
+         WHERE session_type = 'focus' AND completed = 1
+           AND day >= ?1
+           AND day <= ?2
+         GROUP BY day
+         HAVING SUM(xp_earned) > 0
+         ORDER BY day",
+    )?;
+    let rows = stmt.query_map(params![start, end, day_shift(day_boundary_hour)], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })?;
+    rows.collect()
+}
+
+/// Whether each day from `start` to `end`, inclusive, had a completed focus
+/// session; days without any rows are filled in as inactive.
+pub fn get_streak_history(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    day_boundary_hour: u32,
+) -> Result<Vec<(NaiveDate, bool)>> {
+    let active = get_sessions_in_range(
+        conn,
+        &start.format("%Y-%m-%d").to_string(),
+        &end.format("%Y-%m-%d").to_string(),
+        day_boundary_hour,
+    )?;
+    Ok(start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .map(|date| {
+            let day = date.format("%Y-%m-%d").to_string();
+            (date, active.iter().any(|(d, _)| *d == day))
+        })
+        .collect())
+}
+
+/// Completed focus sessions in the 7 days starting at `week_start`
+pub fn get_weekly_totals(
+    conn: &Connection,
+    week_start: &str,
+    day_boundary_hour: u32,
+) -> Result<u32> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sessions
+         WHERE session_type = 'focus' AND completed = 1
+           AND date(started_at, ?2) >= ?1
+           AND date(started_at, ?2) <= date(?1, '+6 days')",
+        params![week_start, day_shift(day_boundary_hour)],
+        |row| row.get(0),
+    )
+}
+
+/// Completed focus seconds between two `%Y-%m-%d` days, inclusive
+pub fn get_focus_secs_in_range(
+    conn: &Connection,
+    start: &str,
+    end: &str,
+    day_boundary_hour: u32,
+) -> Result<u32> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(duration_secs), 0) FROM sessions
+         WHERE session_type = 'focus' AND completed = 1
+           AND date(started_at, ?3) >= ?1
+           AND date(started_at, ?3) <= ?2",
+        params![start, end, day_shift(day_boundary_hour)],
+        |row| row.get(0),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekSummary {
+    pub week_start: NaiveDate,
+    pub sessions: u32,
+    pub focus_secs: u32,
+    /// busiest day and its session count
+    pub best_day: Option<(String, u32)>,
+}
+
+/// Completed focus totals for the 7 days starting at `week_start`
+pub fn get_week_summary(
+    conn: &Connection,
+    week_start: NaiveDate,
+    day_boundary_hour: u32,
+) -> Result<WeekSummary> {
+    let start = week_start.format("%Y-%m-%d").to_string();
+    let end = (week_start + chrono::Duration::days(6))
+        .format("%Y-%m-%d")
+        .to_string();
+    let days = get_sessions_in_range(conn, &start, &end, day_boundary_hour)?;
+    let best_day = days
+        .iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .cloned();
+    Ok(WeekSummary {
+        week_start,
+        sessions: days.iter().map(|(_, count)| count).sum(),
+        focus_secs: get_focus_secs_in_range(conn, &start, &end, day_boundary_hour)?,
+        best_day,
+    })
+}
+
+pub fn get_total_stats(conn: &Connection) -> Result<(u32, u32)> {
+    conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(duration_secs), 0) FROM sessions WHERE session_type = 'focus' AND completed = 1",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+}
+
+/// Focus seconds across all focus sessions, abandoned ones included
+pub fn get_total_focus_time_including_partial(conn: &Connection) -> Result<u32> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(duration_secs), 0) FROM sessions WHERE session_type = 'focus'",
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Distractions marked across completed focus sessions
+pub fn get_total_interruptions(conn: &Connection) -> Result<u32> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(interruptions), 0) FROM sessions
+         WHERE session_type = 'focus' AND completed = 1",
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Focus seconds per tag, largest first; sessions without a tag count as "Untagged"
+pub fn get_stats_by_tag(conn: &Connection) -> Result<Vec<(String, u32)>> {
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(NULLIF(tag, ''), 'Untagged') as label, SUM(duration_secs) as secs
+         FROM sessions
+         WHERE session_type = 'focus' AND completed = 1
+         GROUP BY label
+         ORDER BY secs DESC, label",
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+/// Completed focus sessions by the hour of day they started.
+/// Rows whose timestamp has no readable hour are left out.
+pub fn get_hourly_distribution(conn: &Connection) -> Result<[u32; 24]> {
+    let mut stmt = conn.prepare(
+        "SELECT CAST(substr(started_at, 12, 2) AS INTEGER) as hour, COUNT(*)
+         FROM sessions
+         WHERE session_type = 'focus' AND completed = 1
+           AND substr(started_at, 12, 2) GLOB '[0-2][0-9]'
+         GROUP BY hour",
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, u32>(0)?, row.get(1)?)))?;
+
+    let mut hours = [0; 24];
+    for row in rows {
+        let (hour, count) = row?;
+        if let Some(slot) = hours.get_mut(hour as usize) {
+            *slot = count;
+        }
+    }
+    Ok(hours)
+}
+
+/// Seconds of completed focus by the weekday the session started, Monday
+/// first. Goes by the stored local timestamps with the day boundary applied,
+/// so a session after midnight but before the boundary counts on the day before.
+pub fn get_weekday_distribution(conn: &Connection, day_boundary_hour: u32) -> Result<[u32; 7]> {
+    let mut stmt = conn.prepare(
+        "SELECT CAST(strftime('%w', started_at, ?1) AS INTEGER) as weekday,
+                COALESCE(SUM(duration_secs), 0)
+         FROM sessions
+         WHERE session_type = 'focus' AND completed = 1
+           AND strftime('%w', started_at, ?1) IS NOT NULL
+         GROUP BY weekday",
+    )?;
+    let rows = stmt.query_map(params![day_shift(day_boundary_hour)], |row| {
+        Ok((row.get::<_, u32>(0)?, row.get(1)?))
+    })?;
+
+    let mut days = [0; 7];
+    for row in rows {
+        let (sunday_first, secs) = row?;
+        days[(sunday_first as usize + 6) % 7] = secs;
+    }
+    Ok(days)
+}
+
+/// Completed (short, long) breaks
+pub fn get_break_stats(conn: &Connection) -> Result<(u32, u32)> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(session_type = 'short_break'), 0),
+                COALESCE(SUM(session_type = 'long_break'), 0)
+         FROM sessions WHERE completed = 1",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+}
+
+pub const CSV_HEADER: &str = "id,started_at,completed_at,duration_secs,session_type,completed";
+
+/// Timestamps are written exactly as stored so an export can be re-imported losslessly.
+pub fn export_sessions_csv(
+    conn: &Connection,
+    path: &Path,
+) -> std::result::Result<(), Box<dyn Error>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, started_at, completed_at, duration_secs, session_type, completed
+         FROM sessions ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(format!(
+            "{},{},{},{},{},{}",
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            row.get::<_, u32>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, bool>(5)? as u8,
+        ))
+    })?;
+
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(file, "{}", CSV_HEADER)?;
+    for row in rows {
+        writeln!(file, "{}", row?)?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Writes the notes of sessions started on the day `date`, which begins at
+/// `day_boundary_hour`, as a Markdown list, one bullet per note with its
+/// start time.
+pub fn export_notes_markdown(
+    conn: &Connection,
+    date: NaiveDate,
+    day_boundary_hour: u32,
+    path: &Path,
+) -> std::result::Result<(), Box<dyn Error>> {
+    let mut stmt = conn.prepare(
+        "SELECT substr(started_at, 12, 5), note FROM sessions
+         WHERE date(started_at, ?2) = ?1 AND note IS NOT NULL AND note != ''
+         ORDER BY started_at, id",
+    )?;
+    let notes = stmt
+        .query_map(
+            params![
+                date.format("%Y-%m-%d").to_string(),
+                day_shift(day_boundary_hour)
+            ],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(file, "# Focus notes — {}", date.format("%A, %B %-d, %Y"))?;
+    writeln!(file)?;
+    if notes.is_empty() {
+        writeln!(file, "_No notes for this day._")?;
+    }
+    for (time, note) in notes {
+        // keep a multi-line note inside its bullet
+        writeln!(file, "- **{}** {}", time, note.trim().replace('\n', "\n  "))?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+pub fn get_all_sessions(conn: &Connection) -> Result<Vec<Session>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, started_at, completed_at, duration_secs, session_type, completed, tag, note, intention, xp_earned, interruptions,
+                utc_offset_secs
+         FROM sessions ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], session_from_row)?;
+    rows.collect()
+}
+
+/// Narrows the session history; `None` matches everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionFilter {
+    pub session_type: Option<SessionType>,
+    /// first and last `%Y-%m-%d` day, inclusive
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+/// One page of sessions matching `filter`, newest first. Days in the filter
+/// begin at `day_boundary_hour`.
+pub fn get_sessions_paginated(
+    conn: &Connection,
+    offset: u32,
+    limit: u32,
+    filter: &SessionFilter,
+    day_boundary_hour: u32,
+) -> Result<Vec<Session>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, started_at, completed_at, duration_secs, session_type, completed, tag, note, intention, xp_earned, interruptions,
+                utc_offset_secs
+         FROM sessions
+         WHERE (?1 IS NULL OR session_type = ?1)
+           AND (?2 IS NULL OR date(started_at, ?6) >= ?2)
+           AND (?3 IS NULL OR date(started_at, ?6) <= ?3)
+         ORDER BY started_at DESC, id DESC
+         LIMIT ?4 OFFSET ?5",
+    )?;
+    let rows = stmt.query_map(
+        params![
+            filter.session_type.map(|t| t.as_str()),
+            filter.start,
+            filter.end,
+            limit,
+            offset,
+            day_shift(day_boundary_hour),
+        ],
+        session_from_row,
+    )?;
+    rows.collect()
+}
+
+fn session_from_row(row: &rusqlite::Row) -> Result<Session> {
+    let session_type: String = row.get(4)?;
+    Ok(Session {
+        id: Some(row.get(0)?),
+        started_at: row.get(1)?,
+        completed_at: row.get(2)?,
+        duration_secs: row.get(3)?,
+        session_type: session_type.parse().map_err(|e: String| {
+            rusqlite::Error::FromSqlConversionFailure(4, Type::Text, e.into())
+        })?,
+        completed: row.get(5)?,
+        tag: row.get(6)?,
+        note: row.get(7)?,
+        intention: row.get(8)?,
+        xp_earned: row.get(9)?,
+        interruptions: row.get(10)?,
+        utc_offset_secs: row.get(11)?,
+    })
+}
+
+/// Everything needed to restore or analyze a profile elsewhere.
+#[derive(Debug, Serialize)]
+pub struct JsonExport {
+    pub profile: UserProfile,
+    pub sessions: Vec<Session>,
+}
+
+pub fn export_json(conn: &Connection, path: &Path) -> std::result::Result<(), Box<dyn Error>> {
+    let export = JsonExport {
+        profile: get_profile(conn)?,
+        sessions: get_all_sessions(conn)?,
+    };
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer_pretty(file, &export)?;
+    Ok(())
+}
+
+/// Writes just the configuration, for setting up another machine the same way.
+pub fn export_settings(
+    settings: &Settings,
+    path: &Path,
+) -> std::result::Result<(), Box<dyn Error>> {
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer_pretty(file, settings)?;
+    Ok(())
+}
+
+/// Reads a file written by `export_settings`, refusing one with values the
+/// settings screen wouldn't allow.
+pub fn import_settings(path: &Path) -> std::result::Result<Settings, Box<dyn Error>> {
+    let json = std::fs::read_to_string(path)?;
+    let settings: Settings =
+        serde_json::from_str(&json).map_err(|e| format!("not a settings file ({})", e))?;
+    settings.validate()?;
+    Ok(settings)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// Reads the format written by `export_sessions_csv`. Malformed rows are skipped
+/// and counted rather than failing the whole import; the `id` column is ignored.
+pub fn import_sessions_csv(
+    conn: &Connection,
+    path: &Path,
+    skip_duplicates: bool,
+) -> std::result::Result<ImportSummary, Box<dyn Error>> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let tx = conn.unchecked_transaction()?;
+    let mut summary = ImportSummary::default();
+
+    for (i, line) in file.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || (i == 0 && line.trim() == CSV_HEADER) {
+            continue;
+        }
+
+        let Some(session) = parse_csv_session(&line) else {
+            eprintln!("Skipping malformed CSV row {}: {}", i + 1, line);
+            summary.skipped += 1;
+            continue;
+        };
+
+        if skip_duplicates {
+            let exists = tx
+                .prepare_cached("SELECT 1 FROM sessions WHERE started_at = ?1")?
+                .exists([&session.started_at])?;
+            if exists {
+                summary.skipped += 1;
+                continue;
+            }
+        }
+
+        save_session(&tx, &session)?;
+        summary.imported += 1;
+    }
+
+    tx.commit()?;
+    Ok(summary)
+}
+
+fn parse_csv_session(line: &str) -> Option<Session> {
+    let fields: Vec<&str> = line.trim_end_matches('\r').split(',').collect();
+    let [_id, started_at, completed_at, duration_secs, session_type, completed] = fields[..] else {
+        return None;
+    };
+
+    if started_at.is_empty() {
+        return None;
+    }
+
+    Some(Session {
+        id: None,
+        started_at: started_at.to_string(),
+        completed_at: (!completed_at.is_empty()).then(|| completed_at.to_string()),
+        duration_secs: duration_secs.parse().ok()?,
+        session_type: session_type.parse::<SessionType>().ok()?,
+        completed: match completed {
+            "1" | "true" => true,
+            "0" | "false" => false,
+            _ => return None,
+        },
+        tag: None,
+        note: None,
+        intention: None,
+        xp_earned: 0,
+        interruptions: 0,
+        utc_offset_secs: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DEFAULT_THEME;
+    use rusqlite::Connection;
+
+    fn in_memory_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_data_dir_override() {
+        assert_eq!(
+            data_dir(Some(OsString::from("/tmp/ferris-profile"))),
+            PathBuf::from("/tmp/ferris-profile")
+        );
+        // an empty override is treated as unset
+        assert_eq!(data_dir(Some(OsString::new())), data_dir(None));
+        assert!(data_dir(None).ends_with("ferris-focus"));
+    }
+
+    #[test]
+    fn test_backup_rotation() {
+        let dir =
+            std::env::temp_dir().join(format!("ferris_focus_backup_test_{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        let conn = in_memory_db();
+        let count = || std::fs::read_dir(&dir).unwrap().count();
+
+        assert!(backup_into(&conn, &dir, 2).unwrap().is_some());
+        // nothing changed, so no new copy
+        assert!(backup_into(&conn, &dir, 2).unwrap().is_none());
+        assert_eq!(count(), 1);
+
+        for started_at in ["2026-02-19T10:00:00", "2026-02-19T11:00:00"] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+            assert!(backup_into(&conn, &dir, 2).unwrap().is_some());
+        }
+        assert_eq!(count(), 2);
+
+        // the newest copy holds both sessions
+        let latest = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .max()
+            .unwrap();
+        let restored = Connection::open(&latest).unwrap();
+        assert_eq!(get_total_stats(&restored).unwrap().0, 2);
+
+        assert!(backup_into(&conn, &dir, 0).unwrap().is_none());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    fn user_version(conn: &Connection) -> usize {
+        conn.pragma_query_value(None, "user_version", |row| row.get::<_, u32>(0))
+            .unwrap() as usize
+    }
+
+    #[test]
+    fn test_migrations_are_idempotent() {
+        let conn = in_memory_db();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+
+        init_schema(&conn).unwrap();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+
+        // unversioned databases may already have some of the v1 columns
+        migrate_v1(&conn).unwrap();
+        assert_eq!(get_settings(&conn).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn test_init_and_save() {
+        let conn = in_memory_db();
+        let session = Session {
+            id: None,
+            started_at: "2026-02-19T10:00:00".to_string(),
+            completed_at: Some("2026-02-19T10:25:00".to_string()),
+            duration_secs: 1500,
+            session_type: SessionType::Focus,
+            completed: true,
+            tag: None,
+            note: None,
+            intention: None,
+            xp_earned: 0,
+            interruptions: 0,
+            utc_offset_secs: None,
+        };
         save_session(&conn, &session).unwrap();
 
-        let count = get_today_session_count(&conn, "2026-02-19").unwrap();
-        assert_eq!(count, 1);
+        let count = get_today_session_count(&conn, "2026-02-19", 0).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_day_boundary() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-03-04T22:00:00",
+            "2026-03-05T01:00:00",
+            "2026-03-05T09:00:00",
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+
+        assert_eq!(get_today_session_count(&conn, "2026-03-05", 0).unwrap(), 2);
+        // with days starting at 4 AM the 1 AM session belongs to the 4th
+        assert_eq!(get_today_session_count(&conn, "2026-03-04", 4).unwrap(), 2);
+        assert_eq!(get_today_session_count(&conn, "2026-03-05", 4).unwrap(), 1);
+        assert_eq!(
+            get_sessions_in_range(&conn, "2026-03-04", "2026-03-05", 4).unwrap(),
+            vec![("2026-03-04".to_string(), 2), ("2026-03-05".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_profile_roundtrip() {
+        let conn = in_memory_db();
+        let mut profile = get_profile(&conn).unwrap();
+        assert_eq!(profile.total_xp, 0);
+
+        profile.total_xp = 500;
+        profile.level = 2;
+        profile.current_streak = 3;
+        profile.last_session_date = NaiveDate::from_ymd_opt(2026, 2, 19);
+        profile.freezes_available = 2;
+        update_profile(&conn, &profile).unwrap();
+
+        let loaded = get_profile(&conn).unwrap();
+        assert_eq!(loaded.total_xp, 500);
+        assert_eq!(loaded.level, 2);
+        assert_eq!(loaded.current_streak, 3);
+        assert_eq!(loaded.freezes_available, 2);
+        assert_eq!(
+            loaded.last_session_date,
+            NaiveDate::from_ymd_opt(2026, 2, 19)
+        );
+    }
+
+    #[test]
+    fn test_total_stats() {
+        let conn = in_memory_db();
+        let session = Session {
+            id: None,
+            started_at: "2026-02-19T10:00:00".to_string(),
+            completed_at: Some("2026-02-19T10:25:00".to_string()),
+            duration_secs: 1500,
+            session_type: SessionType::Focus,
+            completed: true,
+            tag: None,
+            note: None,
+            intention: None,
+            xp_earned: 0,
+            interruptions: 0,
+            utc_offset_secs: None,
+        };
+        save_session(&conn, &session).unwrap();
+        save_session(&conn, &session).unwrap();
+
+        let (count, total_secs) = get_total_stats(&conn).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(total_secs, 3000);
+    }
+
+    #[test]
+    fn test_total_interruptions() {
+        let conn = in_memory_db();
+        for (interruptions, completed) in [(2, true), (3, true), (5, false)] {
+            let session = Session {
+                interruptions,
+                completed,
+                ..focus_at("2026-02-19T10:00:00")
+            };
+            save_session(&conn, &session).unwrap();
+        }
+        assert_eq!(get_total_interruptions(&conn).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_total_focus_time_including_partial() {
+        let conn = in_memory_db();
+        save_session(&conn, &focus_at("2026-02-19T10:00:00")).unwrap();
+        let abandoned = Session {
+            duration_secs: 600,
+            completed: false,
+            ..focus_at("2026-02-19T11:00:00")
+        };
+        save_session(&conn, &abandoned).unwrap();
+
+        assert_eq!(get_total_stats(&conn).unwrap(), (1, 1500));
+        assert_eq!(get_total_focus_time_including_partial(&conn).unwrap(), 2100);
     }
 
     #[test]
-    fn test_profile_roundtrip() {
+    fn test_stats_by_tag() {
         let conn = in_memory_db();
-        let mut profile = get_profile(&conn).unwrap();
-        assert_eq!(profile.total_xp, 0);
+        let tagged = |tag: Option<&str>, secs| Session {
+            duration_secs: secs,
+            tag: tag.map(String::from),
+            ..focus_at("2026-02-19T10:00:00")
+        };
+        for session in [
+            tagged(Some("ferris"), 1500),
+            tagged(Some("ferris"), 1500),
+            tagged(Some("thesis"), 600),
+            tagged(None, 900),
+            tagged(Some(""), 300),
+        ] {
+            save_session(&conn, &session).unwrap();
+        }
 
-        profile.total_xp = 500;
-        profile.level = 2;
-        profile.current_streak = 3;
-        profile.last_session_date = NaiveDate::from_ymd_opt(2026, 2, 19);
+        assert_eq!(
+            get_stats_by_tag(&conn).unwrap(),
+            vec![
+                ("ferris".to_string(), 3000),
+                ("Untagged".to_string(), 1200),
+                ("thesis".to_string(), 600),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hourly_distribution() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-02-18T09:00:00",
+            "2026-02-19T09:45:10",
+            "2026-02-19T14:05:00",
+            "2026-02-19T23:59:59",
+            // unreadable or out of range hours are skipped
+            "2026-02-19",
+            "2026-02-19T25:00:00",
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+        let abandoned = Session {
+            completed: false,
+            ..focus_at("2026-02-19T09:10:00")
+        };
+        save_session(&conn, &abandoned).unwrap();
+
+        let hours = get_hourly_distribution(&conn).unwrap();
+        assert_eq!(hours[9], 2);
+        assert_eq!(hours[14], 1);
+        assert_eq!(hours[23], 1);
+        assert_eq!(hours.iter().sum::<u32>(), 4);
+    }
+
+    #[test]
+    fn test_weekday_distribution() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-03-02T09:00:00", // Monday
+            "2026-03-09T23:30:00", // Monday
+            "2026-03-04T10:00:00", // Wednesday
+            "2026-03-08T00:15:00", // Sunday
+            "not a date",
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+        let abandoned = Session {
+            completed: false,
+            ..focus_at("2026-03-05T09:00:00")
+        };
+        save_session(&conn, &abandoned).unwrap();
+
+        assert_eq!(
+            get_weekday_distribution(&conn, 0).unwrap(),
+            [3000, 0, 1500, 0, 0, 0, 1500]
+        );
+    }
+
+    #[test]
+    fn test_week_summary() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-03-01T09:00:00", // Sunday before
+            "2026-03-02T09:00:00",
+            "2026-03-04T09:00:00",
+            "2026-03-04T11:00:00",
+            "2026-03-08T22:00:00",
+            "2026-03-09T08:00:00", // next Monday
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let summary = get_week_summary(&conn, monday, 0).unwrap();
+        assert_eq!(summary.sessions, 4);
+        assert_eq!(summary.focus_secs, 4 * 1500);
+        assert_eq!(summary.best_day, Some(("2026-03-04".to_string(), 2)));
+
+        let empty = get_week_summary(&conn, monday - chrono::Duration::days(14), 0).unwrap();
+        assert_eq!(
+            (empty.sessions, empty.focus_secs, empty.best_day),
+            (0, 0, None)
+        );
+
+        assert_eq!(get_last_weekly_summary(&conn).unwrap(), (None, 0));
+        set_last_weekly_summary(&conn, monday, 1200).unwrap();
+        assert_eq!(
+            get_last_weekly_summary(&conn).unwrap(),
+            (Some(monday), 1200)
+        );
+    }
+
+    #[test]
+    fn test_reset_all() {
+        let conn = in_memory_db();
+        save_session(&conn, &focus_at("2026-02-19T10:00:00")).unwrap();
+        unlock_achievement(&conn, Achievement::FirstSession, "2026-02-19T10:25:00").unwrap();
+        let profile = UserProfile {
+            total_xp: 900,
+            level: 2,
+            current_streak: 3,
+            longest_streak: 3,
+            last_session_date: NaiveDate::from_ymd_opt(2026, 2, 19),
+            freezes_available: 1,
+        };
         update_profile(&conn, &profile).unwrap();
+        let settings = Settings {
+            auto_start: true,
+            theme: "Dracula".to_string(),
+            presets: Vec::new(),
+            ..Settings::default()
+        };
+        save_settings(&conn, &settings).unwrap();
 
-        let loaded = get_profile(&conn).unwrap();
-        assert_eq!(loaded.total_xp, 500);
-        assert_eq!(loaded.level, 2);
-        assert_eq!(loaded.current_streak, 3);
+        reset_all(&conn).unwrap();
+        assert!(get_all_sessions(&conn).unwrap().is_empty());
+        assert!(get_achievements(&conn).unwrap().is_empty());
+        assert_eq!(get_profile(&conn).unwrap(), UserProfile::default());
+        assert_eq!(get_settings(&conn).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn test_achievements() {
+        let conn = in_memory_db();
+        assert!(get_achievements(&conn).unwrap().is_empty());
+
+        unlock_achievement(&conn, Achievement::NightOwl, "2026-02-19T01:30:00").unwrap();
+        unlock_achievement(&conn, Achievement::FirstSession, "2026-02-18T09:25:00").unwrap();
+        unlock_achievement(&conn, Achievement::NightOwl, "2026-02-20T02:00:00").unwrap();
         assert_eq!(
-            loaded.last_session_date,
-            NaiveDate::from_ymd_opt(2026, 2, 19)
+            get_achievements(&conn).unwrap(),
+            vec![
+                (Achievement::FirstSession, "2026-02-18T09:25:00".to_string()),
+                (Achievement::NightOwl, "2026-02-19T01:30:00".to_string()),
+            ]
         );
     }
 
     #[test]
-    fn test_total_stats() {
+    fn test_delete_last_session() {
+        let conn = in_memory_db();
+        let first = save_session(&conn, &focus_at("2026-02-18T09:00:00")).unwrap();
+        let second = save_session(&conn, &focus_at("2026-02-18T10:00:00")).unwrap();
+
+        // only the most recent session can go
+        assert!(!delete_last_session(&conn, first).unwrap());
+        assert!(delete_last_session(&conn, second).unwrap());
+        assert!(!delete_last_session(&conn, second).unwrap());
+        assert_eq!(get_total_stats(&conn).unwrap(), (1, 1500));
+    }
+
+    #[test]
+    fn test_recent_notes() {
+        let conn = in_memory_db();
+        let first = save_session(&conn, &focus_at("2026-02-18T09:00:00")).unwrap();
+        save_session(&conn, &focus_at("2026-02-19T09:00:00")).unwrap();
+        let third = save_session(&conn, &focus_at("2026-02-20T09:00:00")).unwrap();
+
+        set_session_note(&conn, first, "outlined chapter 2").unwrap();
+        set_session_note(&conn, third, "fixed the parser").unwrap();
+
+        assert_eq!(
+            get_recent_notes(&conn, 10).unwrap(),
+            vec![
+                (
+                    "2026-02-20T09:00:00".to_string(),
+                    "fixed the parser".to_string()
+                ),
+                (
+                    "2026-02-18T09:00:00".to_string(),
+                    "outlined chapter 2".to_string()
+                ),
+            ]
+        );
+        assert_eq!(get_recent_notes(&conn, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_recompute_streaks() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-02-01T09:00:00",
+            "2026-02-01T14:00:00",
+            "2026-02-02T09:00:00",
+            "2026-02-03T09:00:00",
+            "2026-02-05T09:00:00",
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+        // breaks and abandoned sessions don't keep a streak alive
+        let short = Session {
+            session_type: SessionType::ShortBreak,
+            ..focus_at("2026-02-04T09:00:00")
+        };
+        save_session(&conn, &short).unwrap();
+
+        let mut profile = get_profile(&conn).unwrap();
+        profile.current_streak = 40;
+        profile.longest_streak = 40;
+        update_profile(&conn, &profile).unwrap();
+
+        assert_eq!(recompute_streaks(&conn, 0).unwrap(), (0, 3));
+        let profile = get_profile(&conn).unwrap();
+        assert_eq!(profile.longest_streak, 3);
+        assert_eq!(profile.current_streak, 0);
+        assert_eq!(
+            profile.last_session_date,
+            NaiveDate::from_ymd_opt(2026, 2, 5)
+        );
+    }
+
+    #[test]
+    fn test_break_stats() {
+        let conn = in_memory_db();
+        assert_eq!(get_break_stats(&conn).unwrap(), (0, 0));
+
+        let focus = focus_at("2026-02-19T10:00:00");
+        let short = Session {
+            session_type: SessionType::ShortBreak,
+            duration_secs: 300,
+            ..focus.clone()
+        };
+        let long = Session {
+            session_type: SessionType::LongBreak,
+            duration_secs: 900,
+            ..focus.clone()
+        };
+        let skipped = Session {
+            completed: false,
+            ..short.clone()
+        };
+        for s in [&focus, &short, &short, &long, &skipped] {
+            save_session(&conn, s).unwrap();
+        }
+
+        assert_eq!(get_break_stats(&conn).unwrap(), (2, 1));
+        // breaks must not leak into focus stats
+        assert_eq!(get_total_stats(&conn).unwrap(), (1, 1500));
+        assert_eq!(get_today_session_count(&conn, "2026-02-19", 0).unwrap(), 1);
+        assert_eq!(
+            get_best_day(&conn, 0).unwrap(),
+            Some(("2026-02-19".to_string(), 1))
+        );
+    }
+
+    #[test]
+    fn test_settings_default_on_fresh_db() {
+        let conn = in_memory_db();
+        assert_eq!(get_settings(&conn).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn test_settings_roundtrip() {
+        let conn = in_memory_db();
+        let settings = Settings {
+            timer: TimerConfig {
+                focus_secs: 52 * 60,
+                short_break_secs: 17 * 60,
+                long_break_secs: 30 * 60,
+                sessions_before_long_break: 3,
+            },
+            // reordered, with one renamed and one dropped
+            presets: vec![
+                TimerPreset::defaults()[2].clone(),
+                TimerPreset {
+                    name: "Classic".to_string(),
+                    ..TimerPreset::defaults()[0].clone()
+                },
+            ],
+            xp: XpConfig {
+                base_xp: 50,
+                streak_bonus_per_day: 5,
+                max_streak_bonus: 25,
+            },
+            auto_start: true,
+            auto_start_delay_secs: 5,
+            sound_enabled: false,
+            auto_abandon_after_secs: 0,
+            minimize_to_tray: true,
+            reset_cycle_daily: true,
+            theme: "Tokyo Night".to_string(),
+            light_from_hour: 6,
+            dark_from_hour: 21,
+            backups_to_keep: 2,
+            prompt_for_notes: true,
+            ambient_sound: AmbientSound::BrownNoise,
+            ambient_volume_percent: 55,
+            quiet_hours_enabled: true,
+            quiet_from_hour: 23,
+            quiet_until_hour: 6,
+            on_suspend: SuspendAction::Abandon,
+            always_on_top: true,
+            window_position: Some((-1200, 80)),
+            window_width: 360,
+            window_height: 600,
+            compact_mode: true,
+            streak_reminder_enabled: false,
+            streak_reminder_hour: 18,
+            focus_complete_message: "Nice, +{xp} XP".to_string(),
+            break_over_message: String::new(),
+            level_up_message: "Level {level}: {stage}".to_string(),
+            hide_gamification: true,
+            track_xp: false,
+            use_24h_clock: true,
+            prompt_for_intention: true,
+            week_starts_on: WeekStart::Sunday,
+            extend_secs: 600,
+            active_preset: Some("Deep Work".to_string()),
+            reduce_motion: true,
+            heatmap_palette: HeatmapPalette::Viridis,
+            smooth_ring: true,
+            cycle_mode: CycleMode::FullCycle,
+            language: Language::Spanish,
+            stage_changes_only: true,
+            day_boundary_hour: 4,
+            daily_goal: 6,
+            status_file: true,
+            http_enabled: true,
+            http_port: 8080,
+            weekly_goal: 10,
+            idle_pause: true,
+            idle_threshold_secs: 600,
+        };
+        save_settings(&conn, &settings).unwrap();
+        assert_eq!(get_settings(&conn).unwrap(), settings);
+
+        // re-running the schema must not clobber saved values
+        init_schema(&conn).unwrap();
+        assert_eq!(get_settings(&conn).unwrap(), settings);
+    }
+
+    #[test]
+    fn test_settings_upgrade_adds_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "
+            CREATE TABLE settings (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                focus_secs INTEGER NOT NULL,
+                short_break_secs INTEGER NOT NULL,
+                long_break_secs INTEGER NOT NULL,
+                sessions_before_long_break INTEGER NOT NULL
+            );
+            INSERT INTO settings VALUES (1, 3000, 600, 1800, 4);
+            ",
+        )
+        .unwrap();
+        init_schema(&conn).unwrap();
+
+        let settings = get_settings(&conn).unwrap();
+        assert_eq!(settings.timer.focus_secs, 3000);
+        assert!(!settings.auto_start);
+        assert_eq!(settings.auto_start_delay_secs, 3);
+        assert!(settings.sound_enabled);
+        assert!(!settings.minimize_to_tray);
+        assert_eq!(settings.theme, DEFAULT_THEME);
+    }
+
+    #[test]
+    fn test_export_sessions_csv() {
         let conn = in_memory_db();
         let session = Session {
             id: None,
@@ -218,12 +2284,489 @@ mod tests {
             duration_secs: 1500,
             session_type: SessionType::Focus,
             completed: true,
+            tag: None,
+            note: None,
+            intention: None,
+            xp_earned: 0,
+            interruptions: 0,
+            utc_offset_secs: None,
         };
         save_session(&conn, &session).unwrap();
-        save_session(&conn, &session).unwrap();
 
-        let (count, total_secs) = get_total_stats(&conn).unwrap();
-        assert_eq!(count, 2);
-        assert_eq!(total_secs, 3000);
+        let path = std::env::temp_dir().join("ferris_focus_export_test.csv");
+        export_sessions_csv(&conn, &path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "1,2026-02-19T10:00:00,2026-02-19T10:25:00,1500,focus,1"
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_import_sessions_csv() {
+        let conn = in_memory_db();
+        let path = std::env::temp_dir().join("ferris_focus_import_test.csv");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n\
+                 1,2026-02-19T10:00:00,2026-02-19T10:25:00,1500,focus,1\n\
+                 2,2026-02-19T10:25:00,2026-02-19T10:30:00,300,short_break,1\n\
+                 3,2026-02-19T11:00:00,,1500,nap,1\n\
+                 not,a,row\n",
+                CSV_HEADER
+            ),
+        )
+        .unwrap();
+
+        let summary = import_sessions_csv(&conn, &path, true).unwrap();
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.skipped, 2);
+        assert_eq!(get_today_session_count(&conn, "2026-02-19", 0).unwrap(), 1);
+
+        // importing the same file again only finds duplicates
+        let again = import_sessions_csv(&conn, &path, true).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(again.imported, 0);
+        assert_eq!(again.skipped, 4);
+    }
+
+    #[test]
+    fn test_csv_export_import_roundtrip() {
+        let source = in_memory_db();
+        let session = Session {
+            id: None,
+            started_at: "2026-02-19T10:00:00".to_string(),
+            completed_at: None,
+            duration_secs: 420,
+            session_type: SessionType::LongBreak,
+            completed: false,
+            tag: None,
+            note: None,
+            intention: None,
+            xp_earned: 0,
+            interruptions: 0,
+            utc_offset_secs: None,
+        };
+        save_session(&source, &session).unwrap();
+
+        let path = std::env::temp_dir().join("ferris_focus_roundtrip_test.csv");
+        export_sessions_csv(&source, &path).unwrap();
+        let target = in_memory_db();
+        import_sessions_csv(&target, &path, false).unwrap();
+
+        let reexport = std::env::temp_dir().join("ferris_focus_roundtrip_test_2.csv");
+        export_sessions_csv(&target, &reexport).unwrap();
+        let original = std::fs::read_to_string(&path).unwrap();
+        let roundtripped = std::fs::read_to_string(&reexport).unwrap();
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&reexport).ok();
+        assert_eq!(original, roundtripped);
+    }
+
+    #[test]
+    fn test_get_all_sessions() {
+        let conn = in_memory_db();
+        let focus = Session {
+            intention: Some("write the parser".to_string()),
+            ..focus_at("2026-02-19T10:00:00")
+        };
+        let short = Session {
+            session_type: SessionType::ShortBreak,
+            completed: false,
+            ..focus_at("2026-02-19T10:25:00")
+        };
+        save_session(&conn, &focus).unwrap();
+        save_session(&conn, &short).unwrap();
+
+        let sessions = get_all_sessions(&conn).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].id, Some(1));
+        assert_eq!(sessions[0].session_type, SessionType::Focus);
+        assert_eq!(sessions[0].intention.as_deref(), Some("write the parser"));
+        assert_eq!(sessions[1].session_type, SessionType::ShortBreak);
+        assert_eq!(sessions[1].intention, None);
+        assert!(!sessions[1].completed);
+    }
+
+    #[test]
+    fn test_export_json() {
+        let conn = in_memory_db();
+        save_session(&conn, &focus_at("2026-02-19T10:00:00")).unwrap();
+
+        let path = std::env::temp_dir().join("ferris_focus_export_test.json");
+        export_json(&conn, &path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(json["profile"]["level"], 1);
+        assert_eq!(json["sessions"][0]["started_at"], "2026-02-19T10:00:00");
+        assert_eq!(json["sessions"][0]["session_type"], "Focus");
+    }
+
+    #[test]
+    fn test_settings_export_import() {
+        let settings = Settings {
+            timer: TimerConfig {
+                focus_secs: 50 * 60,
+                ..TimerConfig::default()
+            },
+            theme: "Nord".to_string(),
+            daily_goal: 6,
+            auto_start: true,
+            ..Settings::default()
+        };
+        let path = std::env::temp_dir().join("ferris_focus_settings_test.json");
+        export_settings(&settings, &path).unwrap();
+        assert_eq!(import_settings(&path).unwrap(), settings);
+
+        // fields a file leaves out keep their defaults
+        std::fs::write(&path, r#"{"daily_goal": 2}"#).unwrap();
+        assert_eq!(import_settings(&path).unwrap().daily_goal, 2);
+
+        let timer = r#"{"focus_secs": 0, "short_break_secs": 300, "long_break_secs": 900,
+            "sessions_before_long_break": 4}"#;
+        std::fs::write(&path, format!(r#"{{"timer": {}}}"#, timer)).unwrap();
+        let zero = import_settings(&path).unwrap_err().to_string();
+        std::fs::write(&path, "not json").unwrap();
+        let malformed = import_settings(&path).unwrap_err().to_string();
+        std::fs::remove_file(&path).ok();
+        assert!(zero.contains("focus duration"), "{}", zero);
+        assert!(
+            malformed.starts_with("not a settings file"),
+            "{}",
+            malformed
+        );
+    }
+
+    #[test]
+    fn test_export_notes_markdown() {
+        let conn = in_memory_db();
+        let noted = |started_at: &str, note: &str| {
+            let id = save_session(&conn, &focus_at(started_at)).unwrap();
+            set_session_note(&conn, id, note).unwrap();
+        };
+        noted("2026-02-19T14:30:00", "Wrote the parser\nand its tests");
+        noted("2026-02-19T09:05:00", "Reviewed PRs");
+        noted("2026-02-20T09:00:00", "Another day");
+        // still the 19th with the day starting at 4 AM
+        noted("2026-02-20T01:15:00", "Late fix");
+        save_session(&conn, &focus_at("2026-02-19T11:00:00")).unwrap();
+
+        let path = std::env::temp_dir().join("ferris_focus_notes_test.md");
+        let date = NaiveDate::from_ymd_opt(2026, 2, 19).unwrap();
+        export_notes_markdown(&conn, date, 4, &path).unwrap();
+        let markdown = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            markdown,
+            "# Focus notes — Thursday, February 19, 2026\n\n\
+             - **09:05** Reviewed PRs\n\
+             - **14:30** Wrote the parser\n  and its tests\n\
+             - **01:15** Late fix\n"
+        );
+
+        let empty_day = NaiveDate::from_ymd_opt(2026, 2, 21).unwrap();
+        export_notes_markdown(&conn, empty_day, 4, &path).unwrap();
+        let markdown = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(markdown.ends_with("_No notes for this day._\n"));
+    }
+
+    #[test]
+    fn test_abandoned_count() {
+        let conn = in_memory_db();
+        let abandoned = Session {
+            id: None,
+            started_at: "2026-02-19T10:00:00".to_string(),
+            completed_at: None,
+            duration_secs: 600,
+            session_type: SessionType::Focus,
+            completed: false,
+            tag: None,
+            note: None,
+            intention: None,
+            xp_earned: 0,
+            interruptions: 0,
+            utc_offset_secs: None,
+        };
+        save_session(&conn, &abandoned).unwrap();
+
+        assert_eq!(get_abandoned_count(&conn, "2026-02-19", 0).unwrap(), 1);
+        assert_eq!(get_today_session_count(&conn, "2026-02-19", 0).unwrap(), 0);
+        assert_eq!(get_total_stats(&conn).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_daily_xp() {
+        let conn = in_memory_db();
+        for (started_at, xp_earned) in [
+            ("2026-02-18T09:00:00", 55),
+            ("2026-02-18T11:00:00", 60),
+            ("2026-02-19T09:00:00", 65),
+            // outside the range
+            ("2026-02-21T09:00:00", 70),
+        ] {
+            let session = Session {
+                xp_earned,
+                ..focus_at(started_at)
+            };
+            save_session(&conn, &session).unwrap();
+        }
+        let abandoned = Session {
+            completed: false,
+            xp_earned: 50,
+            ..focus_at("2026-02-19T10:00:00")
+        };
+        save_session(&conn, &abandoned).unwrap();
+
+        assert_eq!(
+            get_daily_xp(&conn, "2026-02-17", "2026-02-20", 0).unwrap(),
+            vec![
+                ("2026-02-18".to_string(), 115),
+                ("2026-02-19".to_string(), 65),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sessions_paginated() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-02-17T09:00:00",
+            "2026-02-18T09:00:00",
+            "2026-02-19T09:00:00",
+            "2026-02-20T09:00:00",
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+        let short_break = Session {
+            session_type: SessionType::ShortBreak,
+            ..focus_at("2026-02-19T09:30:00")
+        };
+        save_session(&conn, &short_break).unwrap();
+
+        let started = |sessions: Vec<Session>| -> Vec<String> {
+            sessions.into_iter().map(|s| s.started_at).collect()
+        };
+        let all = SessionFilter::default();
+        assert_eq!(
+            started(get_sessions_paginated(&conn, 0, 2, &all, 0).unwrap()),
+            vec!["2026-02-20T09:00:00", "2026-02-19T09:30:00"]
+        );
+        assert_eq!(
+            started(get_sessions_paginated(&conn, 4, 2, &all, 0).unwrap()),
+            vec!["2026-02-17T09:00:00"]
+        );
+
+        let focus_midweek = SessionFilter {
+            session_type: Some(SessionType::Focus),
+            start: Some("2026-02-18".to_string()),
+            end: Some("2026-02-19".to_string()),
+        };
+        assert_eq!(
+            started(get_sessions_paginated(&conn, 0, 10, &focus_midweek, 0).unwrap()),
+            vec!["2026-02-19T09:00:00", "2026-02-18T09:00:00"]
+        );
+
+        // a session just past midnight belongs to the day before the boundary
+        save_session(&conn, &focus_at("2026-02-21T02:00:00")).unwrap();
+        let the_20th = SessionFilter {
+            start: Some("2026-02-20".to_string()),
+            end: Some("2026-02-20".to_string()),
+            ..SessionFilter::default()
+        };
+        assert_eq!(
+            started(get_sessions_paginated(&conn, 0, 10, &the_20th, 4).unwrap()),
+            vec!["2026-02-21T02:00:00", "2026-02-20T09:00:00"]
+        );
+    }
+
+    #[test]
+    fn test_active_timer_roundtrip() {
+        let conn = in_memory_db();
+        assert_eq!(get_active_timer(&conn).unwrap(), None);
+
+        let ends_at = NaiveDate::from_ymd_opt(2026, 3, 9)
+            .unwrap()
+            .and_hms_opt(9, 25, 0)
+            .unwrap();
+        let running = Snapshot {
+            session_type: SessionType::Focus,
+            ends_at: Some(ends_at),
+            remaining_secs: 900,
+            config: TimerConfig::default(),
+            extended_secs: 300,
+            interruptions: 2,
+        };
+        let start = SessionStart {
+            started_at: "2026-03-09T09:00:00".to_string(),
+            tag: Some("rust".to_string()),
+            intention: Some("Finish the parser".to_string()),
+        };
+        save_active_timer(&conn, Some((&running, &start))).unwrap();
+        assert_eq!(
+            get_active_timer(&conn).unwrap(),
+            Some((running, start.clone()))
+        );
+
+        let paused = Snapshot {
+            ends_at: None,
+            ..running
+        };
+        save_active_timer(&conn, Some((&paused, &start))).unwrap();
+        assert_eq!(get_active_timer(&conn).unwrap(), Some((paused, start)));
+
+        save_active_timer(&conn, None).unwrap();
+        assert_eq!(get_active_timer(&conn).unwrap(), None);
+    }
+
+    #[test]
+    fn test_streak_history() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-02-17T09:00:00",
+            "2026-02-17T11:00:00",
+            "2026-02-19T09:00:00",
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+        let abandoned = Session {
+            completed: false,
+            ..focus_at("2026-02-18T10:00:00")
+        };
+        save_session(&conn, &abandoned).unwrap();
+
+        let date = |d| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        assert_eq!(
+            get_streak_history(&conn, date(16), date(20), 0).unwrap(),
+            vec![
+                (date(16), false),
+                (date(17), true),
+                (date(18), false),
+                (date(19), true),
+                (date(20), false),
+            ]
+        );
+    }
+
+    fn focus_at(started_at: &str) -> Session {
+        Session {
+            id: None,
+            started_at: started_at.to_string(),
+            completed_at: None,
+            duration_secs: 1500,
+            session_type: SessionType::Focus,
+            completed: true,
+            tag: None,
+            note: None,
+            intention: None,
+            xp_earned: 0,
+            interruptions: 0,
+            utc_offset_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_cycle_progress_roundtrip() {
+        let conn = in_memory_db();
+        assert_eq!(get_cycle_progress(&conn).unwrap(), CycleProgress::default());
+        let progress = CycleProgress {
+            focus_sessions_completed: 7,
+            cycle_sessions: 3,
+            cycle_focus_secs: 75 * 60,
+            finished_cycle: Some(CycleTotals {
+                sessions: 4,
+                focus_secs: 100 * 60,
+            }),
+            reset_date: NaiveDate::from_ymd_opt(2026, 3, 4),
+        };
+        save_cycle_progress(&conn, &progress).unwrap();
+        assert_eq!(get_cycle_progress(&conn).unwrap(), progress);
+    }
+
+    #[test]
+    fn test_dst_boundary_days() {
+        // in Central Europe clocks skip 02:00-03:00 on 2026-03-29 and repeat
+        // it on 2026-10-25; sessions keep the wall-clock time they started at
+        let conn = in_memory_db();
+        let sessions = [
+            ("2026-03-29T01:30:00", 3600),
+            ("2026-03-29T03:30:00", 7200),
+            ("2026-10-25T02:30:00", 7200),
+            ("2026-10-25T02:30:00", 3600),
+            ("2026-10-25T23:30:00", 3600),
+        ];
+        for (started_at, offset) in sessions {
+            let session = Session {
+                utc_offset_secs: Some(offset),
+                ..focus_at(started_at)
+            };
+            save_session(&conn, &session).unwrap();
+        }
+        save_session(&conn, &focus_at("2026-10-26T00:30:00")).unwrap();
+
+        // the short day still holds both of its sessions
+        assert_eq!(get_today_session_count(&conn, "2026-03-29", 0).unwrap(), 2);
+        // the repeated hour is two sessions, and the long day ends at midnight
+        assert_eq!(get_today_session_count(&conn, "2026-10-25", 0).unwrap(), 3);
+        assert_eq!(
+            get_focus_secs_in_range(&conn, "2026-10-25", "2026-10-25", 0).unwrap(),
+            3 * 1500
+        );
+        let offsets: Vec<_> = get_all_sessions(&conn)
+            .unwrap()
+            .iter()
+            .map(|s| s.utc_offset_secs)
+            .collect();
+        assert_eq!(
+            offsets,
+            [
+                Some(3600),
+                Some(7200),
+                Some(7200),
+                Some(3600),
+                Some(3600),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_best_day() {
+        let conn = in_memory_db();
+        assert_eq!(get_best_day(&conn, 0).unwrap(), None);
+
+        for started_at in [
+            "2026-02-18T09:00:00",
+            "2026-02-19T09:00:00",
+            "2026-02-19T10:00:00",
+            "2026-02-20T09:00:00",
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+        assert_eq!(
+            get_best_day(&conn, 0).unwrap(),
+            Some(("2026-02-19".to_string(), 2))
+        );
+    }
+
+    #[test]
+    fn test_weekly_totals() {
+        let conn = in_memory_db();
+        for started_at in [
+            "2026-02-15T09:00:00", // previous Sunday
+            "2026-02-16T09:00:00", // Monday
+            "2026-02-22T23:00:00", // Sunday
+            "2026-02-23T09:00:00", // next Monday
+        ] {
+            save_session(&conn, &focus_at(started_at)).unwrap();
+        }
+        assert_eq!(get_weekly_totals(&conn, "2026-02-16", 0).unwrap(), 2);
     }
 }
@@ -0,0 +1,810 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use rusqlite::{Connection, Result};
+
+use crate::achievements::{self, Achievement};
+use crate::db;
+use crate::models::{self, Session, SessionType, Settings, UserProfile, WeekStart};
+use crate::timer::Timer;
+use crate::xp;
+
+/// Recent notes listed in Stats
+pub const RECENT_NOTES_SHOWN: u32 = 20;
+/// Weeks covered by the monthly heatmap
+pub const MONTHLY_HEATMAP_WEEKS: i64 = 5;
+/// Days covered by the trend line in the timer view
+pub const TREND_DAYS: i64 = 30;
+/// Days covered by the XP chart in Stats
+pub const XP_CHART_DAYS: i64 = 14;
+/// Days covered by the active-days strip in Stats
+pub const STREAK_HISTORY_DAYS: i64 = 91;
+
+/// Something worth telling the user about, beyond the session itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// one per level gained, so a big jump still shows every stage
+    LeveledUp(u32),
+    Unlocked(Achievement),
+    /// this week's focus sessions reached the weekly goal of this many
+    WeeklyGoalMet(u32),
+}
+
+/// What was captured when a session started.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionStart {
+    pub started_at: String,
+    pub tag: Option<String>,
+    /// what the user said they'd focus on
+    pub intention: Option<String>,
+}
+
+/// A session that has been recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub session_type: SessionType,
+    pub duration_secs: u32,
+    /// XP awarded; `None` for breaks and with XP tracking turned off
+    pub xp: Option<u32>,
+    pub events: Vec<Event>,
+    /// row id of the saved session, `None` without a database
+    pub id: Option<i64>,
+    pub interruptions: u32,
+}
+
+/// Session counts and breakdowns shown in Stats.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub today_sessions: u32,
+    pub today_abandoned: u32,
+    pub total_sessions: u32,
+    pub total_focus_secs: u32,
+    /// focus seconds including abandoned sessions
+    pub total_focus_secs_with_partial: u32,
+    /// completed (short, long) breaks
+    pub break_counts: (u32, u32),
+    /// sessions per day so far this week
+    pub weekly_data: Vec<(String, u32)>,
+    pub week_sessions: u32,
+    pub best_day: Option<(String, u32)>,
+    /// focus seconds per tag
+    pub tag_stats: Vec<(String, u32)>,
+    pub recent_notes: Vec<(String, String)>,
+    /// completed focus sessions per starting hour
+    pub hourly_data: [u32; 24],
+    /// completed focus seconds per weekday, Monday first
+    pub weekday_data: [u32; 7],
+    /// sessions per day since `monthly_heatmap_start`
+    pub monthly_data: Vec<(String, u32)>,
+    /// with when each was unlocked
+    pub achievements: Vec<(Achievement, String)>,
+    /// XP earned per day over the last `XP_CHART_DAYS`, oldest first
+    pub daily_xp: Vec<u32>,
+    /// distractions marked across all completed focus sessions
+    pub total_interruptions: u32,
+    /// whether each of the last `STREAK_HISTORY_DAYS` had a focus session, oldest first
+    pub streak_history: Vec<(NaiveDate, bool)>,
+}
+
+impl Stats {
+    pub fn load(conn: &Connection, today: NaiveDate, settings: &Settings) -> Result<Self> {
+        let day = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+        let today_str = day(today);
+        let week_start = settings.week_starts_on;
+        let boundary = settings.day_boundary_hour;
+        let (total_sessions, total_focus_secs) = db::get_total_stats(conn)?;
+        Ok(Stats {
+            today_sessions: db::get_today_session_count(conn, &today_str, boundary)?,
+            today_abandoned: db::get_abandoned_count(conn, &today_str, boundary)?,
+            total_sessions,
+            total_focus_secs,
+            total_focus_secs_with_partial: db::get_total_focus_time_including_partial(conn)?,
+            break_counts: db::get_break_stats(conn)?,
+            weekly_data: db::get_sessions_in_range(
+                conn,
+                &day(current_week_start(today, week_start)),
+                &today_str,
+                boundary,
+            )?,
+            week_sessions: db::get_weekly_totals(
+                conn,
+                &day(current_week_start(today, week_start)),
+                boundary,
+            )?,
+            best_day: db::get_best_day(conn, boundary)?,
+            tag_stats: db::get_stats_by_tag(conn)?,
+            recent_notes: db::get_recent_notes(conn, RECENT_NOTES_SHOWN)?,
+            hourly_data: db::get_hourly_distribution(conn)?,
+            weekday_data: db::get_weekday_distribution(conn, boundary)?,
+            monthly_data: db::get_sessions_in_range(
+                conn,
+                &day(monthly_heatmap_start(today, week_start)),
+                &today_str,
+                boundary,
+            )?,
+            achievements: db::get_achievements(conn)?,
+            daily_xp: daily_xp(conn, today, XP_CHART_DAYS, boundary)?,
+            total_interruptions: db::get_total_interruptions(conn)?,
+            streak_history: db::get_streak_history(
+                conn,
+                today - Duration::days(STREAK_HISTORY_DAYS - 1),
+                today,
+                boundary,
+            )?,
+        })
+    }
+
+    /// Counts a just-recorded session without going back to the database.
+    pub fn record(&mut self, completion: &Completion) {
+        match completion.session_type {
+            SessionType::Focus => {
+                self.today_sessions += 1;
+                self.week_sessions += 1;
+                self.total_sessions += 1;
+                self.total_focus_secs += completion.duration_secs;
+                self.total_focus_secs_with_partial += completion.duration_secs;
+                self.total_interruptions += completion.interruptions;
+                if let Some((_, active)) = self.streak_history.last_mut() {
+                    *active = true;
+                }
+            }
+            SessionType::ShortBreak => self.break_counts.0 += 1,
+            SessionType::LongBreak => self.break_counts.1 += 1,
+        }
+        if let (Some(xp), Some(today)) = (completion.xp, self.daily_xp.last_mut()) {
+            *today += xp;
+        }
+    }
+
+    /// Counts the time spent in an abandoned focus session.
+    pub fn record_abandoned(&mut self, elapsed_secs: u32) {
+        self.today_abandoned += 1;
+        self.total_focus_secs_with_partial += elapsed_secs;
+    }
+}
+
+/// Completed focus sessions per day for the `days` days up to `today`,
+/// oldest first, with days off as zeros.
+pub fn daily_counts(
+    conn: &Connection,
+    today: NaiveDate,
+    days: i64,
+    day_boundary_hour: u32,
+) -> Result<Vec<u32>> {
+    per_day(today, days, |start, end| {
+        db::get_sessions_in_range(conn, start, end, day_boundary_hour)
+    })
+}
+
+/// XP earned per day for the `days` days up to `today`, oldest first.
+pub fn daily_xp(
+    conn: &Connection,
+    today: NaiveDate,
+    days: i64,
+    day_boundary_hour: u32,
+) -> Result<Vec<u32>> {
+    per_day(today, days, |start, end| {
+        db::get_daily_xp(conn, start, end, day_boundary_hour)
+    })
+}
+
+fn per_day(
+    today: NaiveDate,
+    days: i64,
+    query: impl FnOnce(&str, &str) -> Result<Vec<(String, u32)>>,
+) -> Result<Vec<u32>> {
+    let start = today - Duration::days(days - 1);
+    let data = query(
+        &start.format("%Y-%m-%d").to_string(),
+        &today.format("%Y-%m-%d").to_string(),
+    )?;
+    Ok((0..days)
+        .map(|offset| {
+            let date = (start + Duration::days(offset))
+                .format("%Y-%m-%d")
+                .to_string();
+            data.iter()
+                .find(|(d, _)| *d == date)
+                .map_or(0, |(_, count)| *count)
+        })
+        .collect())
+}
+
+/// First day of the week containing `today`
+pub fn current_week_start(today: NaiveDate, week_start: WeekStart) -> NaiveDate {
+    today - Duration::days(week_start.days_into_week(today) as i64)
+}
+
+/// Whether this week's focus sessions have reached the weekly goal for the
+/// first time this week; the week is noted so it's only reported once.
+fn reach_weekly_goal(conn: &Connection, settings: &Settings, today: NaiveDate) -> Result<bool> {
+    let goal = settings.weekly_goal;
+    if goal == 0 {
+        return Ok(false);
+    }
+    let week_start = current_week_start(today, settings.week_starts_on);
+    // weeks starting on Sunday go by the ISO week their Sunday falls in
+    let week = week_start.format("%G-W%V").to_string();
+    if db::get_last_weekly_goal_week(conn)?.as_deref() == Some(week.as_str()) {
+        return Ok(false);
+    }
+    let day = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+    let done: u32 = db::get_sessions_in_range(
+        conn,
+        &day(week_start),
+        &day(today),
+        settings.day_boundary_hour,
+    )?
+    .iter()
+    .map(|(_, count)| count)
+    .sum();
+    if done < goal {
+        return Ok(false);
+    }
+    db::set_last_weekly_goal_week(conn, &week)?;
+    Ok(true)
+}
+
+/// First day of the first column, so the grid ends with the current week
+pub fn monthly_heatmap_start(today: NaiveDate, week_start: WeekStart) -> NaiveDate {
+    current_week_start(today, week_start) - Duration::days((MONTHLY_HEATMAP_WEEKS - 1) * 7)
+}
+
+/// Advances the streak and adds the XP `xp_for_streak` gives for the new
+/// streak length; returns the XP, or `None` with XP tracking turned off.
+pub fn reward_focus(
+    profile: &mut UserProfile,
+    settings: &Settings,
+    today: NaiveDate,
+    xp_for_streak: impl FnOnce(u32) -> u32,
+) -> Option<u32> {
+    if !settings.track_xp {
+        return None;
+    }
+    let old_level = profile.level;
+
+    let (new_streak, used_freeze) = xp::update_streak(
+        profile.last_session_date,
+        today,
+        profile.current_streak,
+        profile.freezes_available,
+    );
+    if used_freeze {
+        profile.freezes_available -= 1;
+    }
+    profile.current_streak = new_streak;
+    if new_streak > profile.longest_streak {
+        profile.longest_streak = new_streak;
+    }
+    profile.last_session_date = Some(today);
+
+    let xp = xp_for_streak(profile.current_streak);
+    profile.total_xp += xp;
+    profile.level = xp::calculate_level(profile.total_xp);
+    profile.freezes_available += xp::freezes_earned(old_level, profile.level);
+    Some(xp)
+}
+
+/// Records the session the timer just finished, rewarding it if it was focus.
+/// The countdown stands still while paused, so its length is time spent
+/// running; pauses only show in the gap between start and completion.
+pub fn complete_session(
+    conn: Option<&Connection>,
+    profile: &mut UserProfile,
+    settings: &Settings,
+    timer: &Timer,
+    start: SessionStart,
+    now: NaiveDateTime,
+) -> Result<Completion> {
+    let session_type = timer.current_session_type().unwrap_or(SessionType::Focus);
+    let session = finished_session(
+        session_type,
+        timer.total_duration_secs(),
+        timer.interruptions,
+        start,
+        now,
+    );
+    record(
+        conn,
+        profile,
+        settings,
+        session,
+        settings.day_of(now),
+        |streak| xp::calculate_xp(&settings.xp, streak),
+    )
+}
+
+/// Records a stopped stopwatch as a focus session, with XP for the time spent.
+pub fn complete_stopwatch(
+    conn: Option<&Connection>,
+    profile: &mut UserProfile,
+    settings: &Settings,
+    elapsed_secs: u32,
+    interruptions: u32,
+    start: SessionStart,
+    now: NaiveDateTime,
+) -> Result<Completion> {
+    let session = finished_session(SessionType::Focus, elapsed_secs, interruptions, start, now);
+    record(
+        conn,
+        profile,
+        settings,
+        session,
+        settings.day_of(now),
+        |streak| xp::stopwatch_xp(&settings.xp, elapsed_secs, streak),
+    )
+}
+
+fn finished_session(
+    session_type: SessionType,
+    duration_secs: u32,
+    interruptions: u32,
+    start: SessionStart,
+    now: NaiveDateTime,
+) -> Session {
+    Session {
+        id: None,
+        utc_offset_secs: local_offset_secs(&start.started_at),
+        started_at: start.started_at,
+        completed_at: Some(now.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        duration_secs,
+        session_type,
+        completed: true,
+        tag: start.tag,
+        note: None,
+        intention: start.intention,
+        xp_earned: 0,
+        interruptions,
+    }
+}
+
+/// The system timezone's offset at `started_at`, as the session is saved.
+fn local_offset_secs(started_at: &str) -> Option<i32> {
+    let at = NaiveDateTime::parse_from_str(started_at, "%Y-%m-%dT%H:%M:%S").ok()?;
+    models::utc_offset_secs(&Local, at)
+}
+
+fn record(
+    conn: Option<&Connection>,
+    profile: &mut UserProfile,
+    settings: &Settings,
+    mut session: Session,
+    today: NaiveDate,
+    xp_for_streak: impl FnOnce(u32) -> u32,
+) -> Result<Completion> {
+    let old_level = profile.level;
+    let xp = match session.session_type {
+        SessionType::Focus => reward_focus(profile, settings, today, xp_for_streak),
+        _ => None,
+    };
+    session.xp_earned = xp.unwrap_or(0);
+    let mut events: Vec<Event> = (old_level + 1..=profile.level)
+        .map(Event::LeveledUp)
+        .collect();
+
+    let id = match conn {
+        Some(conn) => {
+            let id = db::save_session(conn, &session)?;
+            db::update_profile(conn, profile)?;
+            if session.session_type == SessionType::Focus {
+                let at = session.completed_at.as_deref().unwrap_or_default();
+                events.extend(unlock_achievements(conn, profile, settings, today, at)?);
+                if reach_weekly_goal(conn, settings, today)? {
+                    events.push(Event::WeeklyGoalMet(settings.weekly_goal));
+                }
+            }
+            Some(id)
+        }
+        None => None,
+    };
+    Ok(Completion {
+        session_type: session.session_type,
+        duration_secs: session.duration_secs,
+        xp,
+        events,
+        id,
+        interruptions: session.interruptions,
+    })
+}
+
+/// Unlocks achievements the latest session earned, dated `at`.
+fn unlock_achievements(
+    conn: &Connection,
+    profile: &UserProfile,
+    settings: &Settings,
+    today: NaiveDate,
+    at: &str,
+) -> Result<Vec<Event>> {
+    let stats = Stats::load(conn, today, settings)?;
+    let unlocked: Vec<Achievement> = stats.achievements.iter().map(|(a, _)| *a).collect();
+    let mut events = Vec::new();
+    for achievement in achievements::evaluate(profile, &stats, &unlocked) {
+        db::unlock_achievement(conn, achievement, at)?;
+        events.push(Event::Unlocked(achievement));
+    }
+    Ok(events)
+}
+
+/// Records a focus session skipped partway as abandoned, with the time
+/// actually spent; returns those seconds, `None` if none were spent.
+pub fn abandon_focus(
+    conn: Option<&Connection>,
+    timer: &Timer,
+    start: SessionStart,
+) -> Result<Option<u32>> {
+    if timer.current_session_type() != Some(SessionType::Focus) {
+        return Ok(None);
+    }
+    let elapsed = timer.elapsed_secs();
+    if elapsed == 0 {
+        return Ok(None);
+    }
+
+    if let Some(conn) = conn {
+        let session = Session {
+            id: None,
+            utc_offset_secs: local_offset_secs(&start.started_at),
+            started_at: start.started_at,
+            completed_at: None,
+            duration_secs: elapsed,
+            session_type: SessionType::Focus,
+            completed: false,
+            tag: start.tag,
+            note: None,
+            intention: start.intention,
+            xp_earned: 0,
+            interruptions: timer.interruptions,
+        };
+        db::save_session(conn, &session)?;
+    }
+    Ok(Some(elapsed))
+}
+
+/// What a skipped session was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Skipped {
+    /// focus, with the seconds recorded as abandoned; `None` if none were spent
+    Focus(Option<u32>),
+    /// a break, which the Skip button follows with the next focus session
+    Break,
+}
+
+/// Gives up the session under way: a focus session is recorded as abandoned
+/// first, then the timer goes idle either way.
+pub fn skip_session(
+    conn: Option<&Connection>,
+    timer: &mut Timer,
+    start: SessionStart,
+) -> Result<Skipped> {
+    let on_break = matches!(
+        timer.current_session_type(),
+        Some(SessionType::ShortBreak | SessionType::LongBreak)
+    );
+    let abandoned = abandon_focus(conn, timer, start);
+    timer.reset();
+    if on_break {
+        return Ok(Skipped::Break);
+    }
+    abandoned.map(Skipped::Focus)
+}
+
+/// Takes back the latest session, worth `xp`: the row goes, the XP is
+/// subtracted and streaks are rebuilt from what's left of the history.
+/// `false` if `id` is no longer the latest session.
+pub fn undo_session(conn: &Connection, id: i64, xp: u32, day_boundary_hour: u32) -> Result<bool> {
+    if !db::delete_last_session(conn, id)? {
+        return Ok(false);
+    }
+    db::recompute_streaks(conn, day_boundary_hour)?;
+    let mut profile = db::get_profile(conn)?;
+    let old_level = profile.level;
+    profile.total_xp = profile.total_xp.saturating_sub(xp);
+    profile.level = xp::calculate_level(profile.total_xp);
+    profile.freezes_available = profile
+        .freezes_available
+        .saturating_sub(xp::freezes_earned(profile.level, old_level));
+    db::update_profile(conn, &profile)?;
+    Ok(true)
+}
+
+/// Last week's summary and the XP gained since the previous one, if
+/// this is the first look since the week ended. The very first look only
+/// starts tracking.
+pub fn pending_week_summary(
+    conn: &Connection,
+    profile: &UserProfile,
+    today: NaiveDate,
+    settings: &Settings,
+) -> Option<(db::WeekSummary, u32)> {
+    let this_week = current_week_start(today, settings.week_starts_on);
+    let (last, xp_then) = db::get_last_weekly_summary(conn).ok()?;
+    if last.is_some_and(|date| date >= this_week) {
+        return None;
+    }
+    let _ = db::set_last_weekly_summary(conn, this_week, profile.total_xp);
+
+    last?;
+    let summary = db::get_week_summary(
+        conn,
+        this_week - Duration::days(7),
+        settings.day_boundary_hour,
+    )
+    .ok()?;
+    (summary.sessions > 0).then(|| (summary, profile.total_xp.saturating_sub(xp_then)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimerConfig, XpConfig, FOCUS_DURATION_SECS};
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_reward_focus_advances_streak() {
+        let mut profile = UserProfile::default();
+        let settings = Settings::default();
+
+        let first = reward_focus(&mut profile, &settings, day(1), |streak| {
+            xp::calculate_xp(&settings.xp, streak)
+        })
+        .unwrap();
+        let second = reward_focus(&mut profile, &settings, day(2), |streak| {
+            xp::calculate_xp(&settings.xp, streak)
+        })
+        .unwrap();
+        assert_eq!(profile.current_streak, 2);
+        assert_eq!(profile.last_session_date, Some(day(2)));
+        assert_eq!(profile.total_xp, first + second);
+    }
+
+    #[test]
+    fn test_reward_focus_respects_track_xp() {
+        let mut profile = UserProfile::default();
+        let settings = Settings {
+            track_xp: false,
+            ..Settings::default()
+        };
+        assert_eq!(
+            reward_focus(&mut profile, &settings, day(1), |streak| xp::calculate_xp(
+                &settings.xp,
+                streak
+            )),
+            None
+        );
+        assert_eq!(profile.total_xp, 0);
+        assert_eq!(profile.current_streak, 0);
+    }
+
+    #[test]
+    fn test_level_ups_are_reported_one_by_one() {
+        let mut profile = UserProfile::default();
+        let big_run = (xp::xp_for_level(3) / xp::calculate_xp(&XpConfig::default(), 1) + 1)
+            * FOCUS_DURATION_SECS;
+        let completion = complete_stopwatch(
+            None,
+            &mut profile,
+            &Settings::default(),
+            big_run,
+            0,
+            SessionStart::default(),
+            day(1).and_hms_opt(9, 0, 0).unwrap(),
+        )
+        .unwrap();
+        assert!(profile.level >= 3);
+        assert_eq!(
+            completion.events,
+            (2..=profile.level)
+                .map(Event::LeveledUp)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_day_boundary_keeps_late_sessions_on_the_same_day() {
+        let mut profile = UserProfile::default();
+        let settings = Settings {
+            day_boundary_hour: 4,
+            ..Settings::default()
+        };
+        for now in [
+            day(4).and_hms_opt(23, 0, 0).unwrap(),
+            day(5).and_hms_opt(1, 0, 0).unwrap(),
+        ] {
+            complete_stopwatch(
+                None,
+                &mut profile,
+                &settings,
+                FOCUS_DURATION_SECS,
+                0,
+                SessionStart::default(),
+                now,
+            )
+            .unwrap();
+        }
+        assert_eq!(profile.current_streak, 1);
+        assert_eq!(profile.last_session_date, Some(day(4)));
+    }
+
+    #[test]
+    fn test_weekly_goal_met_once_per_week() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        let mut profile = UserProfile::default();
+        let settings = Settings {
+            weekly_goal: 2,
+            week_starts_on: WeekStart::Sunday,
+            ..Settings::default()
+        };
+        let mut focus_on = |d| {
+            let completion = complete_stopwatch(
+                Some(&conn),
+                &mut profile,
+                &settings,
+                FOCUS_DURATION_SECS,
+                0,
+                SessionStart {
+                    started_at: format!("2026-03-{:02}T09:00:00", d),
+                    ..SessionStart::default()
+                },
+                day(d).and_hms_opt(9, 25, 0).unwrap(),
+            )
+            .unwrap();
+            completion.events.contains(&Event::WeeklyGoalMet(2))
+        };
+        // Saturday and Sunday fall in different weeks when weeks start on Sunday
+        assert!(!focus_on(7));
+        assert!(!focus_on(8));
+        assert!(focus_on(9));
+        assert!(!focus_on(10));
+        assert_eq!(
+            db::get_last_weekly_goal_week(&conn).unwrap().as_deref(),
+            Some("2026-W10")
+        );
+    }
+
+    #[test]
+    fn test_paused_time_is_not_recorded() {
+        let mut profile = UserProfile::default();
+        let settings = Settings::default();
+        let config = TimerConfig::default();
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &config);
+        let started = timer.session_end_instant.unwrap()
+            - std::time::Duration::from_secs(config.focus_secs as u64);
+
+        // ten minutes in, a ten-minute pause
+        let paused = started + std::time::Duration::from_secs(600);
+        timer.pause_at(paused);
+        timer.resume_at(paused + std::time::Duration::from_secs(600));
+        let end = timer.session_end_instant.unwrap();
+        assert_eq!(end - started, std::time::Duration::from_secs(35 * 60));
+        assert!(!timer.tick_at(end - std::time::Duration::from_secs(1)));
+        assert!(timer.tick_at(end));
+
+        let completion = complete_session(
+            None,
+            &mut profile,
+            &settings,
+            &timer,
+            SessionStart {
+                started_at: "2026-03-04T09:00:00".to_string(),
+                ..SessionStart::default()
+            },
+            day(4).and_hms_opt(9, 35, 0).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(completion.duration_secs, config.focus_secs);
+    }
+
+    #[test]
+    fn test_abandon_focus_only_records_focus() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        let config = TimerConfig::default();
+        let start = || SessionStart {
+            started_at: "2026-03-04T09:00:00".to_string(),
+            ..SessionStart::default()
+        };
+        let mut timer = Timer::new();
+
+        // a skipped break isn't a session given up
+        timer.start(SessionType::ShortBreak, &config);
+        let end = timer.session_end_instant.unwrap();
+        timer.tick_at(end - std::time::Duration::from_secs(60));
+        assert_eq!(abandon_focus(Some(&conn), &timer, start()).unwrap(), None);
+        assert!(db::get_all_sessions(&conn).unwrap().is_empty());
+
+        timer.start(SessionType::Focus, &config);
+        let end = timer.session_end_instant.unwrap();
+        timer.tick_at(end - std::time::Duration::from_secs(config.focus_secs as u64 - 600));
+        assert_eq!(
+            abandon_focus(Some(&conn), &timer, start()).unwrap(),
+            Some(600)
+        );
+        let sessions = db::get_all_sessions(&conn).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert!(!sessions[0].completed);
+        assert_eq!(sessions[0].duration_secs, 600);
+        assert_eq!(sessions[0].session_type, SessionType::Focus);
+    }
+
+    #[test]
+    fn test_daily_counts() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        for started_at in [
+            "2026-03-01T09:00:00",
+            "2026-03-03T09:00:00",
+            "2026-03-03T11:00:00",
+            // before the range
+            "2026-02-20T09:00:00",
+        ] {
+            let session = finished_session(
+                SessionType::Focus,
+                1500,
+                0,
+                SessionStart {
+                    started_at: started_at.to_string(),
+                    ..SessionStart::default()
+                },
+                day(3).and_hms_opt(12, 0, 0).unwrap(),
+            );
+            db::save_session(&conn, &session).unwrap();
+        }
+        assert_eq!(
+            daily_counts(&conn, day(4), 5, 0).unwrap(),
+            vec![0, 1, 0, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_week_starts() {
+        // a Wednesday
+        let today = day(4);
+        assert_eq!(current_week_start(today, WeekStart::Monday), day(2));
+        assert_eq!(current_week_start(today, WeekStart::Sunday), day(1));
+        assert_eq!(
+            monthly_heatmap_start(today, WeekStart::Monday),
+            NaiveDate::from_ymd_opt(2026, 2, 2).unwrap()
+        );
+        assert_eq!(
+            monthly_heatmap_start(today, WeekStart::Sunday),
+            NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()
+        );
+        // the week start itself is day zero
+        assert_eq!(current_week_start(day(1), WeekStart::Sunday), day(1));
+    }
+
+    #[test]
+    fn test_stats_record() {
+        let mut stats = Stats {
+            daily_xp: vec![10, 0],
+            ..Stats::default()
+        };
+        let completion = Completion {
+            session_type: SessionType::Focus,
+            duration_secs: 1500,
+            xp: Some(55),
+            events: Vec::new(),
+            id: None,
+            interruptions: 2,
+        };
+        stats.record(&completion);
+        stats.record(&Completion {
+            session_type: SessionType::LongBreak,
+            xp: None,
+            ..completion
+        });
+        assert_eq!(stats.today_sessions, 1);
+        assert_eq!(stats.total_focus_secs, 1500);
+        assert_eq!(stats.break_counts, (0, 1));
+        assert_eq!(stats.daily_xp, vec![10, 55]);
+        assert_eq!(stats.total_interruptions, 2);
+
+        stats.record_abandoned(300);
+        assert_eq!(stats.today_abandoned, 1);
+        assert_eq!(stats.total_focus_secs, 1500);
+        assert_eq!(stats.total_focus_secs_with_partial, 1800);
+    }
+}
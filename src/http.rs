@@ -0,0 +1,127 @@
+use chrono::Local;
+use rusqlite::Connection;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::{cli, db};
+
+/// How often the idle server checks whether it should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Serves `GET /stats` as JSON on localhost for personal dashboards.
+/// Dropping it stops the server and waits for its thread.
+pub struct Server {
+    pub port: u16,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    /// Binds to `127.0.0.1:port` and answers from its own database connection.
+    pub fn start(port: u16) -> io::Result<Server> {
+        let conn = db::init_db().map_err(io::Error::other)?;
+        Self::start_with(port, conn)
+    }
+
+    pub fn start_with(port: u16, conn: Connection) -> io::Result<Server> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let thread = std::thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(e) = respond(stream, &conn) {
+                            eprintln!("Failed to answer stats request: {}", e);
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(POLL_INTERVAL)
+                    }
+                    Err(e) => eprintln!("Failed to accept stats request: {}", e),
+                }
+            }
+        });
+        Ok(Server {
+            port,
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn respond(stream: TcpStream, conn: &Connection) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let (status, body) = match request_line.split_whitespace().take(2).collect::<Vec<_>>()[..] {
+        ["GET", "/stats"] => match cli::stats_report(conn, Local::now().naive_local()) {
+            Ok(report) => ("200 OK", serde_json::to_string(&report)?),
+            Err(e) => ("500 Internal Server Error", error_json(&e.to_string())),
+        },
+        ["GET", _] => ("404 Not Found", error_json("not found")),
+        _ => (
+            "405 Method Not Allowed",
+            error_json("only GET is supported"),
+        ),
+    };
+    write!(
+        &stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+fn error_json(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(port: u16, path: &str) -> String {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serves_stats() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        let server = Server::start_with(0, conn).unwrap();
+
+        let response = get(server.port, "/stats");
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["total_sessions"], 0);
+        assert_eq!(json["level"], 1);
+
+        assert!(get(server.port, "/").starts_with("HTTP/1.1 404"));
+        drop(server);
+    }
+}
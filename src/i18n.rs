@@ -0,0 +1,720 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::models::Language;
+
+/// Index into `Language::ALL` of the language `tr` looks up.
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    let index = Language::ALL.iter().position(|&l| l == language);
+    CURRENT.store(index.unwrap_or(0) as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL
+        .get(CURRENT.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or(Language::English)
+}
+
+/// The string for `key` in the current language.
+pub fn tr(key: &'static str) -> &'static str {
+    lookup(language(), key)
+}
+
+/// `tr` with its `{name}` placeholders filled from `vars`.
+pub fn trf(key: &'static str, vars: &[(&str, String)]) -> String {
+    fill(tr(key), vars)
+}
+
+/// Replaces each `{name}` in `text` with its value; unknown placeholders are left as typed.
+pub fn fill(text: &str, vars: &[(&str, String)]) -> String {
+    vars.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Falls back to English for keys a language hasn't translated, then to the key itself.
+fn lookup(language: Language, key: &'static str) -> &'static str {
+    let find = |table: &[(&str, &'static str)]| {
+        table.iter().find(|(k, _)| *k == key).map(|(_, text)| *text)
+    };
+    find(table(language)).or_else(|| find(EN)).unwrap_or(key)
+}
+
+fn table(language: Language) -> &'static [(&'static str, &'static str)] {
+    match language {
+        Language::English => EN,
+        Language::Spanish => ES,
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("nav.timer", "⏱ Timer"),
+    ("nav.stats", "📊 Stats"),
+    ("nav.settings", "⚙️ Settings"),
+    ("common.cancel", "Cancel"),
+    ("common.close", "Close"),
+    ("common.continue", "Continue"),
+    ("common.next", "Next"),
+    ("common.retry", "Retry"),
+    ("common.save", "Save"),
+    ("common.start", "Start"),
+    ("common.delete", "Delete"),
+    ("common.level", "Level {level}"),
+    ("common.minutes", "{n} min"),
+    ("common.days_ago", "{n} days ago"),
+    ("common.today", "today"),
+    ("session.focus", "FOCUS"),
+    ("session.short_break", "SHORT BREAK"),
+    ("session.long_break", "LONG BREAK"),
+    ("level_up.title", "🎉 Level Up! 🎉"),
+    ("week.title", "📅 Your Week"),
+    ("week.of", "Week of {date}"),
+    ("week.sessions", "{n} focus sessions"),
+    ("week.focused", "{h}h {m}m focused"),
+    ("week.best_day", "Best day: {day} ({n} sessions)"),
+    ("week.best_day_none", "Best day: —"),
+    ("week.dismiss", "Nice!"),
+    ("cycle.title", "🔁 Cycle complete!"),
+    ("cycle.sessions", "{n} focus sessions"),
+    ("cycle.focused", "You focused ~{m} minutes."),
+    ("cycle.dismiss", "Onward"),
+    ("close.question", "A session is running. Close anyway?"),
+    (
+        "reset.ask",
+        "Delete all sessions, XP, achievements and settings?",
+    ),
+    ("reset.confirm", "This can't be undone. Really reset?"),
+    ("reset.everything", "Reset everything"),
+    ("db.not_saved", "⚠ Progress isn't being saved"),
+    ("timer.starting_focus", "Starting focus in {n}…"),
+    ("timer.starting_break", "Starting break in {n}…"),
+    ("timer.stopwatch", "STOPWATCH"),
+    ("timer.ready", "READY"),
+    ("timer.ends_at", "Ends at {time}"),
+    ("timer.preset", "Preset"),
+    ("timer.intention", "What are you focusing on?"),
+    ("timer.note", "What did you get done?"),
+    ("timer.tag", "Tag (optional)"),
+    ("timer.level_short", "Lv. {level}"),
+    ("timer.streak", "🔥 Streak: {n} days"),
+    ("timer.long_break_next", "long break next"),
+    ("timer.until_long_break", "≈{n} min until long break"),
+    ("timer.level_xp", "{earned} / {span} XP to Level {level}"),
+    (
+        "timer.late_hint",
+        "🌙 It's late — your streak is safe, consider resting.",
+    ),
+    ("controls.start_focus", "▶  Start Focus"),
+    ("controls.short_break", "☕  Short Break"),
+    ("controls.long_break", "🎉  Long Break"),
+    ("controls.stop", "⏹  Stop"),
+    ("controls.pause", "⏸  Pause"),
+    ("controls.resume", "▶  Resume"),
+    ("controls.skip", "⏭  Skip"),
+    ("stats.title", "📊 Stats & Progress"),
+    ("stats.today", "Today: {n} focus sessions"),
+    ("stats.daily_goal", "🎯 Daily goal: {n}/{goal}"),
+    ("stats.weekly_goal", "🗓 Weekly goal: {n}/{goal}"),
+    ("stats.abandoned", "Abandoned today: {n}"),
+    ("stats.week", "This week: {n} sessions"),
+    ("stats.best_day", "Best day ever: {day} ({n} sessions)"),
+    ("stats.best_day_none", "Best day ever: —"),
+    (
+        "stats.all_time",
+        "All time: {n} sessions • {h}h {m}m focused",
+    ),
+    ("stats.partial", "Total focus (incl. partial): {h}h {m}m"),
+    (
+        "stats.interruptions",
+        "💭 Avg interruptions per session: {n}",
+    ),
+    (
+        "stats.breaks",
+        "☕ Breaks taken: {n} ({short} short • {long} long)",
+    ),
+    (
+        "stats.streak",
+        "🔥 Current streak: {n} days  •  Best: {best} days",
+    ),
+    (
+        "stats.freezes",
+        "🧊 Streak freezes: {n} (one every {levels} levels)",
+    ),
+    ("stats.total_xp", "⭐ Total XP: {n}"),
+    ("stats.this_week", "This Week"),
+    ("stats.last_weeks", "Last 5 Weeks"),
+    ("stats.range_week", "Week"),
+    ("stats.range_month", "Month"),
+    ("stats.achievements", "Achievements"),
+    ("stats.xp_per_day", "XP per Day"),
+    ("stats.active_days", "Active Days"),
+    ("stats.active", "{n} active"),
+    ("stats.by_hour", "Focus by Hour"),
+    ("stats.by_weekday", "Focus by Weekday"),
+    ("stats.by_tag", "Focus by Tag"),
+    ("stats.no_focus", "No focus sessions yet"),
+    ("stats.recent_notes", "Recent Notes"),
+    (
+        "stats.no_notes",
+        "Notes you write after focus sessions show up here",
+    ),
+    ("stats.export_notes", "📝  Export today's notes"),
+    ("stats.all_sessions", "🗂  All sessions"),
+    ("stats.undo", "↶  Undo last session"),
+    ("stats.export_csv", "⬇  Export CSV"),
+    ("stats.import_csv", "⬆  Import CSV"),
+    ("stats.share", "📸  Share"),
+    ("stats.copy", "📋  Copy"),
+    ("history.title", "🗂 History"),
+    ("history.empty", "No sessions to show"),
+    ("history.load_more", "Load more"),
+    ("history.abandoned", "{kind} (abandoned)"),
+    ("history.no_details", "No note or intention"),
+    ("history.range_all", "All time"),
+    ("history.range_today", "Today"),
+    ("history.range_week", "Last 7 days"),
+    ("history.range_month", "Last 30 days"),
+    ("history.all_types", "All types"),
+    ("preset.custom", "Custom"),
+    ("preset.save_as", "Save durations as…"),
+    ("settings.title", "⚙️ Settings"),
+    ("settings.timer", "Timer"),
+    ("settings.focus", "Focus"),
+    ("settings.short_break", "Short break"),
+    ("settings.long_break", "Long break"),
+    ("settings.sessions_per_cycle", "Sessions per cycle"),
+    ("settings.daily_goal", "Daily goal (sessions)"),
+    ("settings.weekly_goal", "Weekly goal (sessions)"),
+    (
+        "settings.next_session",
+        "Changes apply from the next session.",
+    ),
+    ("settings.flow", "Flow"),
+    ("settings.auto_start", "Auto-start next session"),
+    ("settings.reset_cycle_daily", "Restart cycle each day"),
+    ("settings.prompt_note", "Ask for a note after focus"),
+    (
+        "settings.prompt_intention",
+        "Ask for an intention before focus",
+    ),
+    ("settings.auto_start_delay", "Auto-start delay"),
+    ("settings.auto_abandon", "Abandon paused after"),
+    ("settings.idle_pause", "Pause focus when I'm away"),
+    ("settings.idle_after", "Away after"),
+    (
+        "settings.idle_unsupported",
+        "Idle time isn't available on this desktop",
+    ),
+    ("settings.never", "Never"),
+    ("settings.extend_by", "Extend by"),
+    ("settings.on_suspend", "Time ran out while asleep"),
+    ("settings.cycle", "Cycle: {done}/{total} focus sessions"),
+    ("settings.reset_cycle", "Reset cycle"),
+    ("settings.appearance", "Appearance"),
+    ("settings.language", "Language"),
+    ("settings.theme", "Theme"),
+    ("settings.theme_auto", "Auto (time of day)"),
+    ("settings.light_from", "Light from"),
+    ("settings.dark_from", "Dark from"),
+    ("settings.24h_clock", "24-hour clock"),
+    ("settings.reduce_motion", "Reduce motion"),
+    ("settings.smooth_ring", "Smooth progress ring"),
+    ("settings.week_start", "Week starts on"),
+    ("settings.day_boundary", "New day starts at"),
+    ("settings.heatmap_palette", "Heatmap colors"),
+    ("settings.focus_only", "Focus-only mode (hide Ferris & XP)"),
+    ("settings.track_xp", "Keep tracking XP and streaks"),
+    ("settings.rewards", "Rewards"),
+    ("settings.base_xp", "XP per session"),
+    ("settings.streak_bonus", "Bonus per streak day"),
+    ("settings.max_streak_bonus", "Max streak bonus"),
+    (
+        "settings.stage_changes_only",
+        "Only celebrate new Ferris stages",
+    ),
+    ("settings.alerts", "Alerts"),
+    ("settings.sound", "Completion sound"),
+    ("settings.quiet_hours", "Quiet hours"),
+    ("settings.quiet_from", "Quiet from"),
+    ("settings.quiet_until", "Quiet until"),
+    (
+        "settings.streak_reminder",
+        "Remind me when my streak is at risk",
+    ),
+    ("settings.remind_from", "Remind from"),
+    ("settings.messages", "Notification messages"),
+    (
+        "settings.messages_hint",
+        "Use {xp}, {level}, {stage} and {streak}; leave empty for the default.",
+    ),
+    ("settings.ambience", "Focus ambience"),
+    ("settings.volume", "Volume"),
+    ("settings.window", "Window"),
+    ("settings.minimize_to_tray", "Minimize to tray"),
+    ("settings.status_file", "Write status file for status bars"),
+    ("settings.http", "Serve stats on localhost"),
+    ("settings.http_port", "Port"),
+    ("settings.data", "Data"),
+    ("settings.backups", "Startup backups kept"),
+    ("settings.off", "Off"),
+    ("settings.export_json", "⬇  Export JSON backup"),
+    ("settings.export_settings", "⬇  Export settings"),
+    ("settings.import_settings", "⬆  Import settings"),
+    ("settings.recalculate", "↻  Recalculate stats"),
+    ("settings.reset_all", "🗑  Reset all data"),
+    ("status.exported", "Exported to {path}"),
+    ("status.export_failed", "Export failed: {error}"),
+    ("status.settings_imported", "Settings imported"),
+    ("status.imported", "Imported {n} sessions ({skipped} skipped)"),
+    ("status.import_failed", "Import failed: {error}"),
+    ("status.copied", "Copied stats to the clipboard"),
+    (
+        "status.copied_instead",
+        "Couldn't render an image; copied a summary instead",
+    ),
+    (
+        "status.recalculated",
+        "Streaks recalculated: {current} current, {best} best",
+    ),
+    ("status.recalculate_failed", "Recalculate failed: {error}"),
+    ("status.no_database", "database unavailable"),
+    ("status.undo_too_late", "Too late to undo that session"),
+    ("status.undone", "Last session undone"),
+    ("status.nothing_to_undo", "Nothing to undo"),
+    ("status.undo_failed", "Undo failed: {error}"),
+    ("status.reset_failed", "Reset failed: {error}"),
+    ("status.deleted", "All data deleted"),
+    (
+        "share.summary",
+        "{emoji} Level {level} {stage} • 🔥 {streak}-day streak • {h}h focused • {n} sessions • {date}",
+    ),
+    ("ambience.off", "Off"),
+    ("ambience.white", "White noise"),
+    ("ambience.brown", "Brown noise"),
+    ("suspend.finish", "Count it as done"),
+    ("suspend.abandon", "Abandon it"),
+    ("week_start.monday", "Monday"),
+    ("week_start.sunday", "Sunday"),
+    ("cycle_mode.running", "Every Nth session"),
+    ("cycle_mode.full_cycle", "After a full cycle"),
+    ("palette.theme", "Theme"),
+    ("palette.green", "Greens"),
+    ("palette.viridis", "Viridis"),
+    ("stage.egg", "Egg"),
+    ("stage.hatchling", "Hatchling"),
+    ("stage.junior", "Junior Crab"),
+    ("stage.senior", "Senior Crab"),
+    ("stage.king", "King Crab"),
+    ("stage.emperor", "Emperor Crab"),
+    ("stage.legend", "Legendary Crab"),
+    ("stage.mythic", "Mythic Crab"),
+    ("achievement.first_session", "First session"),
+    ("achievement.first_session_desc", "Complete a focus session"),
+    ("achievement.week_streak", "7-day streak"),
+    ("achievement.week_streak_desc", "Focus 7 days in a row"),
+    ("achievement.hundred_sessions", "100 sessions"),
+    ("achievement.hundred_sessions_desc", "Complete 100 focus sessions"),
+    ("achievement.ten_hours", "10 hours focused"),
+    ("achievement.ten_hours_desc", "Focus for 10 hours in total"),
+    ("achievement.night_owl", "Night owl"),
+    ("achievement.night_owl_desc", "Start a focus session after midnight"),
+    ("notify.streak_title", "🔥 Keep your streak"),
+    (
+        "notify.streak_body",
+        "Your {streak}-day streak is at risk — do one focus session!",
+    ),
+    ("notify.level_up_title", "🎉 Level Up! Level {level}"),
+    (
+        "notify.level_up_body",
+        "Your Ferris has hatched into a {stage}! {emoji}",
+    ),
+    ("notify.achievement_title", "{emoji} Achievement unlocked"),
+    ("notify.weekly_goal_title", "🗓 Weekly goal reached!"),
+    (
+        "notify.weekly_goal_body",
+        "{goal} focus sessions this week. Nice work!",
+    ),
+    ("notify.idle_title", "⏸ Focus paused"),
+    (
+        "notify.idle_body",
+        "No input for {minutes} min. It picks up again when you're back.",
+    ),
+    ("notify.focus_title", "🦀 Focus Complete!"),
+    ("notify.focus_body", "Great work! Time for a break."),
+    (
+        "notify.focus_body_xp",
+        "Great work! Time for a break. +{xp} XP!",
+    ),
+    ("notify.break_title", "☕ Break Over!"),
+    ("notify.break_body", "Ready to focus again?"),
+    ("notify.long_break_title", "🎉 Long Break Over!"),
+    (
+        "notify.long_break_body",
+        "You've earned it! Ready to start a new cycle?",
+    ),
+    ("notify.start_break", "Start Break"),
+    ("notify.start_focus", "Start Focus"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("nav.timer", "⏱ Temporizador"),
+    ("nav.stats", "📊 Estadísticas"),
+    ("nav.settings", "⚙️ Ajustes"),
+    ("common.cancel", "Cancelar"),
+    ("common.close", "Cerrar"),
+    ("common.continue", "Continuar"),
+    ("common.next", "Siguiente"),
+    ("common.retry", "Reintentar"),
+    ("common.save", "Guardar"),
+    ("common.start", "Empezar"),
+    ("common.delete", "Borrar"),
+    ("common.level", "Nivel {level}"),
+    ("common.days_ago", "hace {n} días"),
+    ("common.today", "hoy"),
+    ("session.focus", "ENFOQUE"),
+    ("session.short_break", "PAUSA CORTA"),
+    ("session.long_break", "PAUSA LARGA"),
+    ("level_up.title", "🎉 ¡Subes de nivel! 🎉"),
+    ("week.title", "📅 Tu semana"),
+    ("week.of", "Semana del {date}"),
+    ("week.sessions", "{n} sesiones de enfoque"),
+    ("week.focused", "{h} h {m} min de enfoque"),
+    ("week.best_day", "Mejor día: {day} ({n} sesiones)"),
+    ("week.best_day_none", "Mejor día: —"),
+    ("week.dismiss", "¡Genial!"),
+    ("cycle.title", "🔁 ¡Ciclo completado!"),
+    ("cycle.sessions", "{n} sesiones de enfoque"),
+    ("cycle.focused", "Te has enfocado ~{m} minutos."),
+    ("cycle.dismiss", "Adelante"),
+    (
+        "close.question",
+        "Hay una sesión en curso. ¿Cerrar de todos modos?",
+    ),
+    (
+        "reset.ask",
+        "¿Borrar todas las sesiones, la XP, los logros y los ajustes?",
+    ),
+    (
+        "reset.confirm",
+        "No se puede deshacer. ¿Seguro que quieres borrarlo todo?",
+    ),
+    ("reset.everything", "Borrar todo"),
+    ("db.not_saved", "⚠ No se está guardando el progreso"),
+    ("timer.starting_focus", "Enfoque en {n}…"),
+    ("timer.starting_break", "Pausa en {n}…"),
+    ("timer.stopwatch", "CRONÓMETRO"),
+    ("timer.ready", "LISTO"),
+    ("timer.ends_at", "Termina a las {time}"),
+    ("timer.preset", "Preajuste"),
+    ("timer.intention", "¿En qué te vas a concentrar?"),
+    ("timer.note", "¿Qué has conseguido?"),
+    ("timer.tag", "Etiqueta (opcional)"),
+    ("timer.level_short", "Nv. {level}"),
+    ("timer.streak", "🔥 Racha: {n} días"),
+    ("timer.long_break_next", "ahora toca pausa larga"),
+    ("timer.until_long_break", "≈{n} min hasta la pausa larga"),
+    (
+        "timer.level_xp",
+        "{earned} / {span} XP para el nivel {level}",
+    ),
+    (
+        "timer.late_hint",
+        "🌙 Es tarde: tu racha está a salvo, quizá toca descansar.",
+    ),
+    ("controls.start_focus", "▶  Empezar enfoque"),
+    ("controls.short_break", "☕  Pausa corta"),
+    ("controls.long_break", "🎉  Pausa larga"),
+    ("controls.stop", "⏹  Parar"),
+    ("controls.pause", "⏸  Pausar"),
+    ("controls.resume", "▶  Reanudar"),
+    ("controls.skip", "⏭  Saltar"),
+    ("stats.title", "📊 Estadísticas y progreso"),
+    ("stats.today", "Hoy: {n} sesiones de enfoque"),
+    ("stats.daily_goal", "🎯 Objetivo diario: {n}/{goal}"),
+    ("stats.weekly_goal", "🗓 Objetivo semanal: {n}/{goal}"),
+    ("stats.abandoned", "Abandonadas hoy: {n}"),
+    ("stats.week", "Esta semana: {n} sesiones"),
+    ("stats.best_day", "Mejor día: {day} ({n} sesiones)"),
+    ("stats.best_day_none", "Mejor día: —"),
+    (
+        "stats.all_time",
+        "En total: {n} sesiones • {h} h {m} min de enfoque",
+    ),
+    (
+        "stats.partial",
+        "Enfoque total (con parciales): {h} h {m} min",
+    ),
+    ("stats.interruptions", "💭 Interrupciones por sesión: {n}"),
+    (
+        "stats.breaks",
+        "☕ Pausas: {n} ({short} cortas • {long} largas)",
+    ),
+    (
+        "stats.streak",
+        "🔥 Racha actual: {n} días  •  Mejor: {best} días",
+    ),
+    (
+        "stats.freezes",
+        "🧊 Congelaciones de racha: {n} (una cada {levels} niveles)",
+    ),
+    ("stats.total_xp", "⭐ XP total: {n}"),
+    ("stats.this_week", "Esta semana"),
+    ("stats.last_weeks", "Últimas 5 semanas"),
+    ("stats.range_week", "Semana"),
+    ("stats.range_month", "Mes"),
+    ("stats.achievements", "Logros"),
+    ("stats.xp_per_day", "XP por día"),
+    ("stats.active_days", "Días activos"),
+    ("stats.active", "{n} activos"),
+    ("stats.by_hour", "Enfoque por hora"),
+    ("stats.by_weekday", "Enfoque por día de la semana"),
+    ("stats.by_tag", "Enfoque por etiqueta"),
+    ("stats.no_focus", "Aún no hay sesiones de enfoque"),
+    ("stats.recent_notes", "Notas recientes"),
+    (
+        "stats.no_notes",
+        "Aquí aparecen las notas que escribas tras cada sesión",
+    ),
+    ("stats.export_notes", "📝  Exportar las notas de hoy"),
+    ("stats.all_sessions", "🗂  Todas las sesiones"),
+    ("stats.undo", "↶  Deshacer la última sesión"),
+    ("stats.export_csv", "⬇  Exportar CSV"),
+    ("stats.import_csv", "⬆  Importar CSV"),
+    ("stats.share", "📸  Compartir"),
+    ("stats.copy", "📋  Copiar"),
+    ("history.title", "🗂 Historial"),
+    ("history.empty", "No hay sesiones que mostrar"),
+    ("history.load_more", "Cargar más"),
+    ("history.abandoned", "{kind} (abandonada)"),
+    ("history.no_details", "Sin nota ni intención"),
+    ("history.range_all", "Todo el tiempo"),
+    ("history.range_today", "Hoy"),
+    ("history.range_week", "Últimos 7 días"),
+    ("history.range_month", "Últimos 30 días"),
+    ("history.all_types", "Todos los tipos"),
+    ("preset.custom", "Personalizado"),
+    ("preset.save_as", "Guardar duraciones como…"),
+    ("settings.title", "⚙️ Ajustes"),
+    ("settings.timer", "Temporizador"),
+    ("settings.focus", "Enfoque"),
+    ("settings.short_break", "Pausa corta"),
+    ("settings.long_break", "Pausa larga"),
+    ("settings.sessions_per_cycle", "Sesiones por ciclo"),
+    ("settings.daily_goal", "Objetivo diario (sesiones)"),
+    ("settings.weekly_goal", "Objetivo semanal (sesiones)"),
+    (
+        "settings.next_session",
+        "Los cambios se aplican desde la próxima sesión.",
+    ),
+    ("settings.flow", "Flujo"),
+    (
+        "settings.auto_start",
+        "Empezar la siguiente sesión automáticamente",
+    ),
+    ("settings.reset_cycle_daily", "Reiniciar el ciclo cada día"),
+    ("settings.prompt_note", "Pedir una nota tras el enfoque"),
+    (
+        "settings.prompt_intention",
+        "Pedir una intención antes del enfoque",
+    ),
+    ("settings.auto_start_delay", "Espera antes de empezar"),
+    ("settings.auto_abandon", "Abandonar en pausa tras"),
+    ("settings.idle_pause", "Pausar el enfoque si me ausento"),
+    ("settings.idle_after", "Ausente tras"),
+    (
+        "settings.idle_unsupported",
+        "Este escritorio no informa del tiempo inactivo",
+    ),
+    ("settings.never", "Nunca"),
+    ("settings.extend_by", "Alargar"),
+    ("settings.on_suspend", "Si el tiempo acaba en suspensión"),
+    (
+        "settings.cycle",
+        "Ciclo: {done}/{total} sesiones de enfoque",
+    ),
+    ("settings.reset_cycle", "Reiniciar ciclo"),
+    ("settings.appearance", "Apariencia"),
+    ("settings.language", "Idioma"),
+    ("settings.theme", "Tema"),
+    ("settings.theme_auto", "Automático (según la hora)"),
+    ("settings.light_from", "Claro desde"),
+    ("settings.dark_from", "Oscuro desde"),
+    ("settings.24h_clock", "Reloj de 24 horas"),
+    ("settings.reduce_motion", "Reducir animaciones"),
+    ("settings.smooth_ring", "Anillo de progreso continuo"),
+    ("settings.week_start", "La semana empieza el"),
+    ("settings.day_boundary", "El día empieza a las"),
+    ("settings.heatmap_palette", "Colores del mapa de calor"),
+    (
+        "settings.focus_only",
+        "Solo enfoque (ocultar a Ferris y la XP)",
+    ),
+    ("settings.track_xp", "Seguir contando XP y rachas"),
+    ("settings.rewards", "Recompensas"),
+    ("settings.base_xp", "XP por sesión"),
+    ("settings.streak_bonus", "Bonus por día de racha"),
+    ("settings.max_streak_bonus", "Bonus máximo de racha"),
+    (
+        "settings.stage_changes_only",
+        "Celebrar solo las nuevas etapas de Ferris",
+    ),
+    ("settings.alerts", "Avisos"),
+    ("settings.sound", "Sonido al terminar"),
+    ("settings.quiet_hours", "Horas de silencio"),
+    ("settings.quiet_from", "Silencio desde"),
+    ("settings.quiet_until", "Silencio hasta"),
+    (
+        "settings.streak_reminder",
+        "Avisarme si mi racha está en peligro",
+    ),
+    ("settings.remind_from", "Avisar desde"),
+    ("settings.messages", "Mensajes de las notificaciones"),
+    (
+        "settings.messages_hint",
+        "Usa {xp}, {level}, {stage} y {streak}; déjalo vacío para el mensaje por defecto.",
+    ),
+    ("settings.ambience", "Sonido ambiente"),
+    ("settings.volume", "Volumen"),
+    ("settings.window", "Ventana"),
+    ("settings.minimize_to_tray", "Minimizar a la bandeja"),
+    (
+        "settings.status_file",
+        "Escribir archivo de estado para barras de estado",
+    ),
+    ("settings.http", "Servir estadísticas en localhost"),
+    ("settings.http_port", "Puerto"),
+    ("settings.data", "Datos"),
+    ("settings.backups", "Copias al arrancar"),
+    ("settings.off", "No"),
+    ("settings.export_json", "⬇  Exportar copia JSON"),
+    ("settings.export_settings", "⬇  Exportar ajustes"),
+    ("settings.import_settings", "⬆  Importar ajustes"),
+    ("settings.recalculate", "↻  Recalcular estadísticas"),
+    ("settings.reset_all", "🗑  Borrar todos los datos"),
+    ("status.exported", "Exportado a {path}"),
+    ("status.export_failed", "Error al exportar: {error}"),
+    ("status.settings_imported", "Ajustes importados"),
+    ("status.imported", "{n} sesiones importadas ({skipped} omitidas)"),
+    ("status.import_failed", "Error al importar: {error}"),
+    ("status.copied", "Estadísticas copiadas al portapapeles"),
+    ("status.copied_instead", "No se pudo crear la imagen; se copió un resumen"),
+    ("status.recalculated", "Rachas recalculadas: {current} actual, {best} mejor"),
+    ("status.recalculate_failed", "Error al recalcular: {error}"),
+    ("status.no_database", "base de datos no disponible"),
+    ("status.undo_too_late", "Ya es tarde para deshacer esa sesión"),
+    ("status.undone", "Última sesión deshecha"),
+    ("status.nothing_to_undo", "Nada que deshacer"),
+    ("status.undo_failed", "Error al deshacer: {error}"),
+    ("status.reset_failed", "Error al restablecer: {error}"),
+    ("status.deleted", "Todos los datos borrados"),
+    (
+        "share.summary",
+        "{emoji} Nivel {level} {stage} • 🔥 Racha de {streak} días • {h}h de enfoque • {n} sesiones • {date}",
+    ),
+    ("ambience.off", "Desactivado"),
+    ("ambience.white", "Ruido blanco"),
+    ("ambience.brown", "Ruido marrón"),
+    ("suspend.finish", "Contarla como hecha"),
+    ("suspend.abandon", "Abandonarla"),
+    ("week_start.monday", "Lunes"),
+    ("week_start.sunday", "Domingo"),
+    ("cycle_mode.running", "Cada N sesiones"),
+    ("cycle_mode.full_cycle", "Tras un ciclo completo"),
+    ("palette.theme", "Tema"),
+    ("palette.green", "Verdes"),
+    ("stage.egg", "Huevo"),
+    ("stage.hatchling", "Cría"),
+    ("stage.junior", "Cangrejo joven"),
+    ("stage.senior", "Cangrejo veterano"),
+    ("stage.king", "Cangrejo rey"),
+    ("stage.emperor", "Cangrejo emperador"),
+    ("stage.legend", "Cangrejo legendario"),
+    ("stage.mythic", "Cangrejo mítico"),
+    ("achievement.first_session", "Primera sesión"),
+    ("achievement.first_session_desc", "Completa una sesión de enfoque"),
+    ("achievement.week_streak", "Racha de 7 días"),
+    ("achievement.week_streak_desc", "Concéntrate 7 días seguidos"),
+    ("achievement.hundred_sessions", "100 sesiones"),
+    ("achievement.hundred_sessions_desc", "Completa 100 sesiones de enfoque"),
+    ("achievement.ten_hours", "10 horas de enfoque"),
+    ("achievement.ten_hours_desc", "Concéntrate 10 horas en total"),
+    ("achievement.night_owl", "Búho nocturno"),
+    ("achievement.night_owl_desc", "Empieza una sesión de enfoque después de medianoche"),
+    ("notify.streak_title", "🔥 Mantén tu racha"),
+    (
+        "notify.streak_body",
+        "Tu racha de {streak} días está en peligro: ¡haz una sesión de enfoque!",
+    ),
+    ("notify.level_up_title", "🎉 ¡Subes de nivel! Nivel {level}"),
+    (
+        "notify.level_up_body",
+        "¡Tu Ferris se ha convertido en {stage}! {emoji}",
+    ),
+    ("notify.achievement_title", "{emoji} Logro desbloqueado"),
+    ("notify.weekly_goal_title", "🗓 ¡Objetivo semanal cumplido!"),
+    (
+        "notify.weekly_goal_body",
+        "{goal} sesiones de enfoque esta semana. ¡Buen trabajo!",
+    ),
+    ("notify.idle_title", "⏸ Enfoque en pausa"),
+    (
+        "notify.idle_body",
+        "Sin actividad durante {minutes} min. Seguirá cuando vuelvas.",
+    ),
+    ("notify.focus_title", "🦀 ¡Enfoque completado!"),
+    ("notify.focus_body", "¡Buen trabajo! Toca descansar."),
+    (
+        "notify.focus_body_xp",
+        "¡Buen trabajo! Toca descansar. ¡+{xp} XP!",
+    ),
+    ("notify.break_title", "☕ ¡Fin de la pausa!"),
+    ("notify.break_body", "¿Listo para volver a concentrarte?"),
+    ("notify.long_break_title", "🎉 ¡Fin de la pausa larga!"),
+    (
+        "notify.long_break_body",
+        "¡Te lo has ganado! ¿Empezamos un nuevo ciclo?",
+    ),
+    ("notify.start_break", "Empezar pausa"),
+    ("notify.start_focus", "Empezar enfoque"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback() {
+        assert_eq!(lookup(Language::Spanish, "common.save"), "Guardar");
+        assert_eq!(lookup(Language::English, "common.save"), "Save");
+        // same in both, so only English has it
+        assert_eq!(lookup(Language::Spanish, "common.minutes"), "{n} min");
+        assert_eq!(lookup(Language::Spanish, "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_tables_match() {
+        for (key, _) in ES {
+            assert!(
+                EN.iter().any(|(k, _)| k == key),
+                "'{}' isn't in English",
+                key
+            );
+        }
+        for (i, (key, _)) in EN.iter().enumerate() {
+            assert!(!EN[..i].iter().any(|(k, _)| k == key), "'{}' twice", key);
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            fill(
+                "{n} of {total}, {other}",
+                &[("n", "2".into()), ("total", "4".into())]
+            ),
+            "2 of 4, {other}"
+        );
+    }
+}
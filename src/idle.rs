@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+/// How often idle time is checked while it matters.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// What an idle check asks of the timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleAction {
+    Pause,
+    Resume,
+    Nothing,
+}
+
+/// `idle_paused` is whether an earlier check paused the session; only those
+/// pauses are lifted again when input comes back.
+pub fn react(
+    idle_secs: u64,
+    threshold_secs: u32,
+    focus_running: bool,
+    idle_paused: bool,
+) -> IdleAction {
+    if focus_running && idle_secs >= threshold_secs as u64 {
+        IdleAction::Pause
+    } else if idle_paused && idle_secs < POLL_INTERVAL.as_secs() {
+        IdleAction::Resume
+    } else {
+        IdleAction::Nothing
+    }
+}
+
+/// Seconds since the last keyboard or mouse input, asked of the desktop
+/// over D-Bus. `None` when neither GNOME's nor the freedesktop screensaver
+/// interface answers, e.g. on most wlroots compositors.
+#[cfg(target_os = "linux")]
+pub async fn idle_secs() -> Option<u64> {
+    let conn = zbus::Connection::session().await.ok()?;
+    let gnome = conn
+        .call_method(
+            Some("org.gnome.Mutter.IdleMonitor"),
+            "/org/gnome/Mutter/IdleMonitor/Core",
+            Some("org.gnome.Mutter.IdleMonitor"),
+            "GetIdletime",
+            &(),
+        )
+        .await
+        .ok()
+        .and_then(|reply| reply.body().deserialize::<u64>().ok());
+    if let Some(ms) = gnome {
+        return Some(ms / 1000);
+    }
+    let reply = conn
+        .call_method(
+            Some("org.freedesktop.ScreenSaver"),
+            "/org/freedesktop/ScreenSaver",
+            Some("org.freedesktop.ScreenSaver"),
+            "GetSessionIdleTime",
+            &(),
+        )
+        .await
+        .ok()?;
+    let ms: u32 = reply.body().deserialize().ok()?;
+    Some(ms as u64 / 1000)
+}
+
+#[cfg(windows)]
+pub async fn idle_secs() -> Option<u64> {
+    use windows_sys::Win32::System::SystemInformation::GetTickCount;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    // SAFETY: `info` is a LASTINPUTINFO with its size filled in, as the call expects
+    if unsafe { GetLastInputInfo(&mut info) } == 0 {
+        return None;
+    }
+    // SAFETY: no arguments, no preconditions
+    let now = unsafe { GetTickCount() };
+    Some(now.wrapping_sub(info.dwTime) as u64 / 1000)
+}
+
+/// Reads `HIDIdleTime` (nanoseconds) off the HID system in the I/O registry.
+#[cfg(target_os = "macos")]
+pub async fn idle_secs() -> Option<u64> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .ok()?;
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let nanos: u64 = listing
+        .lines()
+        .find_map(|line| line.split_once("\"HIDIdleTime\" = "))
+        .and_then(|(_, nanos)| nanos.trim().parse().ok())?;
+    Some(nanos / 1_000_000_000)
+}
+
+/// No way to tell here, so idle pausing turns itself off.
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub async fn idle_secs() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_react() {
+        // only a running focus session is paused
+        assert_eq!(react(300, 300, true, false), IdleAction::Pause);
+        assert_eq!(react(299, 300, true, false), IdleAction::Nothing);
+        assert_eq!(react(900, 300, false, false), IdleAction::Nothing);
+
+        // and only a pause of its own is lifted, once there's input again
+        assert_eq!(react(2, 300, false, true), IdleAction::Resume);
+        assert_eq!(react(40, 300, false, true), IdleAction::Nothing);
+        assert_eq!(react(2, 300, false, false), IdleAction::Nothing);
+    }
+}
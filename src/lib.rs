@@ -0,0 +1,16 @@
+pub mod achievements;
+pub mod audio;
+pub mod cli;
+pub mod db;
+pub mod engine;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod i18n;
+pub mod idle;
+pub mod models;
+pub mod notifications;
+pub mod share;
+pub mod status;
+pub mod timer;
+pub mod tray;
+pub mod xp;
@@ -1,134 +1,637 @@
 #![windows_subsystem = "windows"]
-mod db;
-mod models;
-mod notifications;
-mod timer;
-mod xp;
 
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, Timelike};
 use iced::alignment;
 use iced::mouse;
 use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke};
 use iced::widget::mouse_area;
-use iced::widget::{button, column, container, row, rule, space, text};
-use iced::{time, window, Center, Color, Element, Fill, Padding, Subscription, Task, Theme};
+use iced::widget::{
+    button, column, container, pick_list, row, rule, scrollable, slider, space, text, text_input,
+    toggler,
+};
+use iced::{color, time, window, Center, Color, Element, Fill, Padding, Subscription, Task, Theme};
 use rusqlite::Connection;
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 
-use models::{Session, SessionType, UserProfile, FOCUS_DURATION_SECS, SESSIONS_BEFORE_LONG_BREAK};
-use timer::{Timer, TimerState};
+use ferris_focus::achievements::Achievement;
+use ferris_focus::engine::{self, Completion, Event, SessionStart, Stats};
+#[cfg(feature = "http")]
+use ferris_focus::http;
+use ferris_focus::i18n::{self, tr, trf};
+use ferris_focus::{audio, cli, db, idle, models, notifications, share, status, timer, tray, xp};
+use models::{
+    AmbientSound, CycleMode, HeatmapPalette, Language, Session, SessionType, Settings,
+    SuspendAction, UserProfile, WeekStart, AUTO_THEME, COMPACT_WINDOW_SIZE, WINDOW_HEIGHT,
+    WINDOW_WIDTH,
+};
+use notifications::ActionEvent;
+use status::Status;
+use timer::{CycleProgress, CycleTotals, Snapshot, Timer, TimerState};
+use tray::TrayEvent;
 
 fn main() -> iced::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     let window_settings = window::Settings {
-        size: iced::Size::new(320.0, 540.0),
+        size: iced::Size::new(WINDOW_WIDTH as f32, WINDOW_HEIGHT as f32),
         decorations: false,
         ..Default::default()
     };
 
-    iced::application(App::default, update, view)
+    let result = iced::application(App::default, update, view)
         .title("Ferris Focus")
-        .theme(Theme::CatppuccinMocha)
+        .theme(theme)
         .subscription(subscription)
         .window(window_settings)
         .centered()
-        .run()
+        .run();
+    status::clear();
+    result
+}
+
+/// How long after completing a session it can still be undone
+const UNDO_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Redraw interval for the focus animation
+const ANIMATION_FRAME: Duration = Duration::from_millis(100);
+/// Seconds for one bob of Ferris and pulse of the ring
+const ANIMATION_PERIOD_SECS: f32 = 3.0;
+/// Redraw interval for the smooth progress ring, about 30fps
+const SMOOTH_FRAME: Duration = Duration::from_millis(33);
+/// How long the ring takes to fade into a session of another type
+const SWITCH_DURATION: Duration = Duration::from_millis(600);
+/// Sessions fetched at a time in the History view
+const HISTORY_PAGE: u32 = 50;
+
+/// What's needed to take back the session that just completed.
+#[derive(Debug, Clone, Copy)]
+struct CompletedSession {
+    id: i64,
+    xp: u32,
+    /// whether it advanced the long-break cycle
+    in_cycle: bool,
+    duration_secs: u32,
+    at: Instant,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum View {
     Timer,
     Stats,
+    /// every session, reached from Stats
+    History,
+    Settings,
+}
+
+/// How far through the two confirmations a factory reset is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResetStep {
+    Ask,
+    Confirm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeatmapRange {
+    Week,
+    Month,
+}
+
+/// How far back the History view goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HistoryRange {
+    AllTime,
+    Today,
+    Week,
+    Month,
+}
+
+impl HistoryRange {
+    const ALL: [HistoryRange; 4] = [
+        HistoryRange::AllTime,
+        HistoryRange::Today,
+        HistoryRange::Week,
+        HistoryRange::Month,
+    ];
+
+    /// Days counted back from today, including it; `None` for no limit
+    fn days(self) -> Option<i64> {
+        match self {
+            HistoryRange::AllTime => None,
+            HistoryRange::Today => Some(1),
+            HistoryRange::Week => Some(7),
+            HistoryRange::Month => Some(30),
+        }
+    }
+}
+
+impl std::fmt::Display for HistoryRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(tr(match self {
+            HistoryRange::AllTime => "history.range_all",
+            HistoryRange::Today => "history.range_today",
+            HistoryRange::Week => "history.range_week",
+            HistoryRange::Month => "history.range_month",
+        }))
+    }
+}
+
+/// Session type shown in History; `None` shows them all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TypeFilter(Option<SessionType>);
+
+impl TypeFilter {
+    const ALL: [TypeFilter; 4] = [
+        TypeFilter(None),
+        TypeFilter(Some(SessionType::Focus)),
+        TypeFilter(Some(SessionType::ShortBreak)),
+        TypeFilter(Some(SessionType::LongBreak)),
+    ];
+}
+
+impl std::fmt::Display for TypeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(tr(match self.0 {
+            None => "history.all_types",
+            Some(SessionType::Focus) => "settings.focus",
+            Some(SessionType::ShortBreak) => "settings.short_break",
+            Some(SessionType::LongBreak) => "settings.long_break",
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigField {
+    FocusMinutes,
+    ShortBreakMinutes,
+    LongBreakMinutes,
+    SessionsBeforeLongBreak,
+    AutoStartDelaySecs,
+    AutoAbandonMinutes,
+    IdleMinutes,
+    ExtendMinutes,
+    LightFromHour,
+    DarkFromHour,
+    BackupsToKeep,
+    QuietFromHour,
+    QuietUntilHour,
+    StreakReminderHour,
+    DayBoundaryHour,
+    DailyGoal,
+    WeeklyGoal,
+    BaseXp,
+    StreakBonusPerDay,
+    MaxStreakBonus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MessageTemplate {
+    FocusComplete,
+    BreakOver,
+    LevelUp,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ThemeChoice {
+    /// light by day, dark by night
+    Auto,
+    Fixed(Theme),
+}
+
+impl std::fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeChoice::Auto => f.write_str(tr("settings.theme_auto")),
+            ThemeChoice::Fixed(theme) => theme.fmt(f),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     Tick,
+    PauseTick,
+    AnimationTick,
     Start,
+    StartStopwatch,
+    /// straight from finished focus into the next one
+    SkipBreak,
+    StopStopwatch,
+    SetTag(String),
+    EditIntention(String),
+    CancelIntention,
+    DismissLateHint,
+    TogglePromptForIntention(bool),
+    EditNote(String),
+    SaveNote,
+    DiscardNote,
+    TogglePromptForNotes(bool),
+    Toggle24hClock(bool),
+    ToggleReduceMotion(bool),
+    ToggleStageChangesOnly(bool),
+    ToggleSmoothRing(bool),
     PauseResume,
+    Extend,
+    MarkDistraction,
     Skip,
+    ResetCycle,
     SwitchView(View),
+    SetHeatmapRange(HeatmapRange),
+    SetHistoryRange(HistoryRange),
+    SetHistoryType(TypeFilter),
+    LoadMoreHistory,
+    /// show or hide a session's note and intention
+    ToggleHistoryEntry(i64),
+    ExportCsv,
+    ExportPathChosen(Option<PathBuf>),
+    ExportDone(Result<PathBuf, String>),
+    Share,
+    CopyStats,
+    ShareCaptured(window::Screenshot),
+    SharePathChosen(Option<PathBuf>),
+    ImportCsv,
+    ExportJson,
+    ExportNotes,
+    ExportNotesPathChosen(Option<PathBuf>),
+    RecalculateStats,
+    UndoLastSession,
+    ExportJsonPathChosen(Option<PathBuf>),
+    ExportSettings,
+    ExportSettingsPathChosen(Option<PathBuf>),
+    ImportSettings,
+    ImportSettingsPathChosen(Option<PathBuf>),
+    ImportPathChosen(Option<PathBuf>),
+    AdjustConfig(ConfigField, i32),
+    ToggleAutoStart(bool),
+    ToggleResetCycleDaily(bool),
+    SetCycleMode(CycleMode),
+    ToggleSound(bool),
+    ToggleQuietHours(bool),
+    ToggleStreakReminder(bool),
+    ToggleHideGamification(bool),
+    ToggleTrackXp(bool),
+    EditMessageTemplate(MessageTemplate, String),
+    SetAmbientSound(AmbientSound),
+    SetSuspendAction(SuspendAction),
+    SetWeekStart(WeekStart),
+    SetHeatmapPalette(HeatmapPalette),
+    SetLanguage(Language),
+    SelectPreset(String),
+    EditPresetName(String),
+    SavePreset,
+    DeletePreset,
+    SetAmbientVolume(u32),
+    SetTheme(ThemeChoice),
+    ClockTick,
+    ToggleMinimizeToTray(bool),
+    ToggleStatusFile(bool),
+    ToggleIdlePause(bool),
+    CheckIdle,
+    IdleChecked(Option<u64>),
+    #[cfg(feature = "http")]
+    ToggleHttp(bool),
+    #[cfg(feature = "http")]
+    EditHttpPort(String),
+    #[cfg(feature = "http")]
+    ApplyHttpPort,
+    Tray(tray::TrayEvent),
+    NotificationAction(notifications::ActionEvent),
     DismissLevelUp,
+    DismissWeekSummary,
+    DismissCycleSummary,
+    RetryDb,
+    ToggleOnTop,
+    ToggleCompact,
+    WindowMoved(iced::Point),
+    WindowResized(iced::Size),
+    WindowFocused,
+    RestorePosition(Option<iced::Size>),
+    SaveWindowGeometry,
     Minimize,
     Close,
+    ConfirmClose,
+    CancelClose,
+    RequestReset,
+    ConfirmReset,
+    CancelReset,
     WindowReady(window::Id),
     DragStart,
 }
 
 struct App {
     timer: Timer,
+    settings: Settings,
     profile: UserProfile,
     current_view: View,
     db: Option<Connection>,
-    session_start_time: Option<String>,
-    today_sessions: u32,
-    total_sessions: u32,
-    total_focus_secs: u32,
-    weekly_data: Vec<(String, u32)>,
-    level_up: Option<u32>,
+    /// why the database couldn't be opened; progress is kept in memory only
+    db_error: Option<String>,
+    /// what was captured when the current session started
+    session: Option<SessionStart>,
+    /// tag typed in the Timer view, applied to the next focus session
+    active_tag: String,
+    /// intention being typed before a focus session starts
+    intention_draft: Option<String>,
+    /// focus started during quiet hours; cleared when dismissed or the next session starts
+    late_hint: bool,
+    /// name typed in Settings for saving the current durations as a preset
+    preset_name: String,
+    /// note being written for a just-finished focus session: (session id, text)
+    pending_note: Option<(i64, String)>,
+    last_completed: Option<CompletedSession>,
+    stats: Stats,
+    /// focus sessions per day for the timer view's trend line, oldest first
+    trend: Vec<u32>,
+    heatmap_range: HeatmapRange,
+    /// loaded pages of the History view, newest first
+    history: Vec<Session>,
+    /// whether another page is waiting past the last one loaded
+    history_more: bool,
+    history_range: HistoryRange,
+    history_type: TypeFilter,
+    /// session whose details are expanded
+    history_open: Option<i64>,
+    /// outcome of the last export/import, shown in Stats
+    data_status: Option<String>,
+    /// PNG of the stats screen waiting for a place to be saved
+    share_image: Option<Vec<u8>>,
+    /// levels still to celebrate, one modal each, oldest first
+    level_ups: VecDeque<u32>,
+    /// last week's totals and XP gained since the previous summary, shown once per week
+    week_summary: Option<(db::WeekSummary, u32)>,
+    /// the cycle a long break just rounded off, shown once it's over
+    cycle_summary: Option<CycleTotals>,
+    /// asking before a close would drop the running session
+    confirm_close: bool,
+    reset_step: Option<ResetStep>,
+    /// seconds left before the next session auto-starts
+    auto_start_in: Option<u32>,
+    /// when the last tick of a running session arrived, by system and monotonic clock
+    last_tick: Option<(SystemTime, Instant)>,
+    /// 0.0..1.0 through the focus animation's cycle
+    animation_phase: f32,
     window_id: Option<window::Id>,
+    /// window moved or resized since the placement was last saved
+    window_geometry_dirty: bool,
+    tray_ready: bool,
+    /// whether the auto theme is currently on its light side
+    daytime: bool,
+    notification_actions: Option<notifications::ActionSender>,
+    /// day the streak reminder last fired, so it goes out at most once a day
+    last_reminder_date: Option<NaiveDate>,
+    ambient: audio::Ambient,
+    /// countdown as last written to the database, to pick up after a restart
+    saved_timer: Option<Snapshot>,
+    /// long-break cycle as last written to the database
+    saved_cycle: CycleProgress,
+    /// session type the ring last showed, to notice when it changes
+    shown_session_type: Option<SessionType>,
+    /// when the ring switched session types, while the switch plays
+    switched_at: Option<Instant>,
+    /// status file contents as last written, so unchanged ticks skip the write
+    written_status: Option<Status>,
+    /// paused by the idle check, so input coming back resumes it
+    idle_paused: bool,
+    /// the desktop can't report idle time, so stop asking
+    idle_unsupported: bool,
+    /// stats endpoint, running while it's enabled in settings
+    #[cfg(feature = "http")]
+    http_server: Option<http::Server>,
+    /// port as typed, applied on enter
+    #[cfg(feature = "http")]
+    http_port_draft: String,
+    #[cfg(feature = "http")]
+    http_error: Option<String>,
 }
 
 impl Default for App {
     fn default() -> Self {
-        let db = db::init_db().ok();
-        let profile = db
+        let (db, db_error) = match db::init_db() {
+            Ok(conn) => (Some(conn), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let mut profile: UserProfile = db
             .as_ref()
             .and_then(|c| db::get_profile(c).ok())
             .unwrap_or_default();
-        let today = Local::now().format("%Y-%m-%d").to_string();
-        let today_sessions = db
+        // keep the stored level in step with the current XP curve
+        let level = xp::calculate_level(profile.total_xp);
+        if level != profile.level {
+            profile.level = level;
+            if let Some(conn) = &db {
+                let _ = db::update_profile(conn, &profile);
+            }
+        }
+        let settings = db
+            .as_ref()
+            .and_then(|c| db::get_settings(c).ok())
+            .unwrap_or_default();
+        let backup = db.as_ref().map(|c| db::backup(c, settings.backups_to_keep));
+        if let Some(Err(e)) = backup {
+            eprintln!("Failed to back up database: {}", e);
+        }
+        let daytime = settings.is_daytime(Local::now().hour());
+        let today = settings.day_of(Local::now().naive_local());
+        let stats = db
+            .as_ref()
+            .and_then(|c| Stats::load(c, today, &settings).ok())
+            .unwrap_or_default();
+        let trend = db
+            .as_ref()
+            .and_then(|c| {
+                engine::daily_counts(c, today, engine::TREND_DAYS, settings.day_boundary_hour).ok()
+            })
+            .unwrap_or_default();
+        let week_summary = db
             .as_ref()
-            .and_then(|c| db::get_today_session_count(c, &today).ok())
-            .unwrap_or(0);
-        let (total_sessions, total_focus_secs) = db
+            .and_then(|c| engine::pending_week_summary(c, &profile, today, &settings));
+
+        let last_reminder_date = db
             .as_ref()
-            .and_then(|c| db::get_total_stats(c).ok())
-            .unwrap_or((0, 0));
+            .and_then(|c| db::get_last_reminder_date(c).ok())
+            .flatten();
 
-        let week_start = (Local::now() - chrono::Duration::days(6))
-            .format("%Y-%m-%d")
-            .to_string();
-        let weekly_data = db
+        let saved_cycle = db
             .as_ref()
-            .and_then(|c| db::get_sessions_in_range(c, &week_start, &today).ok())
+            .and_then(|c| db::get_cycle_progress(c).ok())
             .unwrap_or_default();
 
-        App {
-            timer: Timer::new(),
+        let mut timer = Timer::new();
+        timer.restore_cycle(saved_cycle);
+        timer.last_cycle_reset_date.get_or_insert(today);
+        timer.cycle_mode = settings.cycle_mode;
+        i18n::set_language(settings.language);
+
+        let mut app = App {
+            timer,
+            settings,
             profile,
             current_view: View::Timer,
             db,
-            session_start_time: None,
-            today_sessions,
-            total_sessions,
-            total_focus_secs,
-            weekly_data,
-            level_up: None,
+            db_error,
+            session: None,
+            active_tag: String::new(),
+            intention_draft: None,
+            late_hint: false,
+            preset_name: String::new(),
+            pending_note: None,
+            last_completed: None,
+            stats,
+            trend,
+            heatmap_range: HeatmapRange::Week,
+            history: Vec::new(),
+            history_more: false,
+            history_range: HistoryRange::AllTime,
+            history_type: TypeFilter(None),
+            history_open: None,
+            data_status: None,
+            share_image: None,
+            level_ups: VecDeque::new(),
+            week_summary,
+            cycle_summary: None,
+            confirm_close: false,
+            reset_step: None,
+            auto_start_in: None,
+            last_tick: None,
+            animation_phase: 0.0,
             window_id: None,
-        }
+            window_geometry_dirty: false,
+            tray_ready: false,
+            daytime,
+            notification_actions: None,
+            last_reminder_date,
+            ambient: audio::Ambient::default(),
+            saved_timer: None,
+            saved_cycle,
+            shown_session_type: None,
+            switched_at: None,
+            written_status: None,
+            idle_paused: false,
+            idle_unsupported: false,
+            #[cfg(feature = "http")]
+            http_server: None,
+            #[cfg(feature = "http")]
+            http_port_draft: String::new(),
+            #[cfg(feature = "http")]
+            http_error: None,
+        };
+        restore_timer(&mut app);
+        reset_cycle_if_new_day(&mut app);
+        #[cfg(feature = "http")]
+        sync_http_server(&mut app);
+        app
     }
 }
 
 fn update(app: &mut App, message: Message) -> Task<Message> {
+    let task = handle_message(app, message);
+    sync_ambient(app);
+    sync_saved_timer(app);
+    sync_saved_cycle(app);
+    sync_status_file(app);
+    sync_switch(app);
+    task
+}
+
+fn handle_message(app: &mut App, message: Message) -> Task<Message> {
     match message {
         Message::Tick => {
-            let finished = app.timer.tick();
-            if finished {
-                on_session_complete(app);
+            reset_cycle_if_new_day(app);
+            if reconcile_suspend(app) {
+                return Task::none();
+            }
+            if !finish_if_due(app) {
+                app.auto_start_in = app.auto_start_in.map(|secs| secs.saturating_sub(1));
+            }
+            if app.auto_start_in == Some(0) {
+                start_next(app);
+            }
+            Task::none()
+        }
+        Message::AnimationTick => {
+            app.animation_phase = (app.animation_phase
+                + animation_frame(app).as_secs_f32() / ANIMATION_PERIOD_SECS)
+                .fract();
+            if !is_switching(app) {
+                app.switched_at = None;
+            }
+            Task::none()
+        }
+        Message::PauseTick => {
+            let paused_for = app.timer.tick_paused();
+            let limit = app.settings.auto_abandon_after_secs;
+            if limit > 0 && paused_for >= limit {
+                on_skip(app);
             }
             Task::none()
         }
         Message::Start => {
-            let session_type = if app.timer.is_finished() {
-                app.timer.next_session_type()
-            } else {
-                SessionType::Focus
-            };
-            app.timer.start(session_type);
-            app.session_start_time = Some(Local::now().format("%Y-%m-%dT%H:%M:%S").to_string());
+            on_start(app);
+            Task::none()
+        }
+        Message::SkipBreak => {
+            if app.timer.skip_break(&app.settings.timer) {
+                app.auto_start_in = None;
+                on_session_start(app);
+            }
+            Task::none()
+        }
+        Message::StartStopwatch => {
+            app.auto_start_in = None;
+            app.timer.start_stopwatch(&app.settings.timer);
+            on_session_start(app);
+            Task::none()
+        }
+        Message::StopStopwatch => {
+            on_stopwatch_stop(app);
+            Task::none()
+        }
+        Message::SetTag(tag) => {
+            app.active_tag = tag;
+            Task::none()
+        }
+        Message::EditIntention(intention) => {
+            if let Some(draft) = &mut app.intention_draft {
+                *draft = intention;
+            }
+            Task::none()
+        }
+        Message::DismissLateHint => {
+            app.late_hint = false;
+            Task::none()
+        }
+        Message::CancelIntention => {
+            app.intention_draft = None;
+            Task::none()
+        }
+        Message::EditNote(note) => {
+            if let Some((_, text)) = &mut app.pending_note {
+                *text = note;
+            }
+            Task::none()
+        }
+        Message::SaveNote => {
+            if let (Some((id, note)), Some(conn)) = (app.pending_note.take(), &app.db) {
+                let note = note.trim();
+                if !note.is_empty() {
+                    let _ = db::set_session_note(conn, id, note);
+                }
+            }
+            Task::none()
+        }
+        Message::DiscardNote => {
+            app.pending_note = None;
             Task::none()
         }
         Message::PauseResume => {
+            // ticks stop while paused, which isn't a gap to catch up on
+            app.last_tick = None;
+            app.idle_paused = false;
             if app.timer.is_running() {
                 app.timer.pause();
             } else if app.timer.is_paused() {
@@ -136,475 +639,3530 @@ fn update(app: &mut App, message: Message) -> Task<Message> {
             }
             Task::none()
         }
+        Message::MarkDistraction => {
+            app.timer.mark_interruption();
+            Task::none()
+        }
+        Message::Extend => {
+            on_extend(app);
+            Task::none()
+        }
         Message::Skip => {
             if app.timer.is_running() || app.timer.is_paused() {
-                app.timer.reset();
-                app.session_start_time = None;
+                on_skip_to_focus(app);
             }
             Task::none()
         }
+        Message::ResetCycle => {
+            app.timer.reset_cycle();
+            Task::none()
+        }
         Message::SwitchView(v) => {
             app.current_view = v;
-            if v == View::Stats {
+            match v {
+                View::Stats => refresh_stats(app),
+                View::Timer => refresh_trend(app),
+                View::History => load_history(app, true),
+                View::Settings => {}
+            }
+            Task::none()
+        }
+        Message::SetHistoryRange(range) => {
+            app.history_range = range;
+            load_history(app, true);
+            Task::none()
+        }
+        Message::SetHistoryType(filter) => {
+            app.history_type = filter;
+            load_history(app, true);
+            Task::none()
+        }
+        Message::LoadMoreHistory => {
+            load_history(app, false);
+            Task::none()
+        }
+        Message::ToggleHistoryEntry(id) => {
+            app.history_open = (app.history_open != Some(id)).then_some(id);
+            Task::none()
+        }
+        Message::SetHeatmapRange(range) => {
+            app.heatmap_range = range;
+            Task::none()
+        }
+        Message::ExportCsv => Task::perform(
+            async {
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("ferris-focus-sessions.csv")
+                    .add_filter("CSV", &["csv"])
+                    .save_file()
+                    .await
+                    .map(|handle| handle.path().to_path_buf())
+            },
+            Message::ExportPathChosen,
+        ),
+        Message::ExportPathChosen(path) => {
+            let Some(path) = path else {
+                return Task::none();
+            };
+            let result = match &app.db {
+                Some(conn) => db::export_sessions_csv(conn, &path)
+                    .map(|_| path)
+                    .map_err(|e| e.to_string()),
+                None => Err(tr("status.no_database").to_string()),
+            };
+            Task::done(Message::ExportDone(result))
+        }
+        Message::ExportDone(result) => {
+            app.data_status = Some(match result {
+                Ok(path) => trf("status.exported", &[("path", path.display().to_string())]),
+                Err(e) => trf("status.export_failed", &[("error", e)]),
+            });
+            Task::none()
+        }
+        Message::ExportJson => Task::perform(
+            async {
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("ferris-focus.json")
+                    .add_filter("JSON", &["json"])
+                    .save_file()
+                    .await
+                    .map(|handle| handle.path().to_path_buf())
+            },
+            Message::ExportJsonPathChosen,
+        ),
+        Message::ExportJsonPathChosen(path) => {
+            let Some(path) = path else {
+                return Task::none();
+            };
+            let result = match &app.db {
+                Some(conn) => db::export_json(conn, &path)
+                    .map(|_| path)
+                    .map_err(|e| e.to_string()),
+                None => Err(tr("status.no_database").to_string()),
+            };
+            Task::done(Message::ExportDone(result))
+        }
+        Message::ExportSettings => Task::perform(
+            async {
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("ferris-focus-settings.json")
+                    .add_filter("JSON", &["json"])
+                    .save_file()
+                    .await
+                    .map(|handle| handle.path().to_path_buf())
+            },
+            Message::ExportSettingsPathChosen,
+        ),
+        Message::ExportSettingsPathChosen(path) => {
+            let Some(path) = path else {
+                return Task::none();
+            };
+            let result = db::export_settings(&app.settings, &path)
+                .map(|_| path)
+                .map_err(|e| e.to_string());
+            Task::done(Message::ExportDone(result))
+        }
+        Message::ImportSettings => Task::perform(
+            async {
+                rfd::AsyncFileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .pick_file()
+                    .await
+                    .map(|handle| handle.path().to_path_buf())
+            },
+            Message::ImportSettingsPathChosen,
+        ),
+        Message::ImportSettingsPathChosen(path) => {
+            let Some(path) = path else {
+                return Task::none();
+            };
+            match db::import_settings(&path) {
+                Ok(settings) => {
+                    app.data_status = Some(tr("status.settings_imported").to_string());
+                    apply_imported_settings(app, settings)
+                }
+                Err(e) => {
+                    app.data_status =
+                        Some(trf("status.import_failed", &[("error", e.to_string())]));
+                    Task::none()
+                }
+            }
+        }
+        Message::ExportNotes => {
+            let file_name = format!("ferris-focus-notes-{}.md", Local::now().format("%Y-%m-%d"));
+            Task::perform(
+                async move {
+                    rfd::AsyncFileDialog::new()
+                        .set_file_name(file_name)
+                        .add_filter("Markdown", &["md"])
+                        .save_file()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                },
+                Message::ExportNotesPathChosen,
+            )
+        }
+        Message::ExportNotesPathChosen(path) => {
+            let Some(path) = path else {
+                return Task::none();
+            };
+            let result = match &app.db {
+                Some(conn) => db::export_notes_markdown(
+                    conn,
+                    today(app),
+                    app.settings.day_boundary_hour,
+                    &path,
+                )
+                .map(|_| path)
+                .map_err(|e| e.to_string()),
+                None => Err(tr("status.no_database").to_string()),
+            };
+            Task::done(Message::ExportDone(result))
+        }
+        Message::Share => match app.window_id {
+            Some(id) => window::screenshot(id).map(Message::ShareCaptured),
+            None => share_as_text(app),
+        },
+        Message::CopyStats => {
+            app.data_status = Some(tr("status.copied").to_string());
+            copy_summary(app)
+        }
+        Message::ShareCaptured(screenshot) => {
+            match share::encode_png(
+                screenshot.size.width,
+                screenshot.size.height,
+                &screenshot.rgba,
+            ) {
+                Ok(png) => app.share_image = Some(png),
+                Err(e) => {
+                    eprintln!("Failed to encode stats image: {}", e);
+                    return share_as_text(app);
+                }
+            }
+            let file_name = format!("ferris-focus-{}.png", Local::now().format("%Y-%m-%d"));
+            Task::perform(
+                async move {
+                    rfd::AsyncFileDialog::new()
+                        .set_file_name(file_name)
+                        .add_filter("PNG image", &["png"])
+                        .save_file()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                },
+                Message::SharePathChosen,
+            )
+        }
+        Message::SharePathChosen(path) => {
+            let (Some(path), Some(png)) = (path, app.share_image.take()) else {
+                return Task::none();
+            };
+            let result = std::fs::write(&path, png)
+                .map(|_| path)
+                .map_err(|e| e.to_string());
+            Task::done(Message::ExportDone(result))
+        }
+        Message::RecalculateStats => {
+            let Some(conn) = &app.db else {
+                app.data_status = Some(trf(
+                    "status.recalculate_failed",
+                    &[("error", tr("status.no_database").to_string())],
+                ));
+                return Task::none();
+            };
+            app.data_status = Some(
+                match db::recompute_streaks(conn, app.settings.day_boundary_hour) {
+                    Ok((current, longest)) => trf(
+                        "status.recalculated",
+                        &[
+                            ("current", current.to_string()),
+                            ("best", longest.to_string()),
+                        ],
+                    ),
+                    Err(e) => trf("status.recalculate_failed", &[("error", e.to_string())]),
+                },
+            );
+            refresh_stats(app);
+            Task::none()
+        }
+        Message::UndoLastSession => {
+            undo_last_session(app);
+            Task::none()
+        }
+        Message::ImportCsv => Task::perform(
+            async {
+                rfd::AsyncFileDialog::new()
+                    .add_filter("CSV", &["csv"])
+                    .pick_file()
+                    .await
+                    .map(|handle| handle.path().to_path_buf())
+            },
+            Message::ImportPathChosen,
+        ),
+        Message::ImportPathChosen(path) => {
+            let Some(path) = path else {
+                return Task::none();
+            };
+            let Some(conn) = &app.db else {
+                app.data_status = Some(trf(
+                    "status.import_failed",
+                    &[("error", tr("status.no_database").to_string())],
+                ));
+                return Task::none();
+            };
+            app.data_status = Some(match db::import_sessions_csv(conn, &path, true) {
+                Ok(summary) => trf(
+                    "status.imported",
+                    &[
+                        ("n", summary.imported.to_string()),
+                        ("skipped", summary.skipped.to_string()),
+                    ],
+                ),
+                Err(e) => trf("status.import_failed", &[("error", e.to_string())]),
+            });
+            refresh_stats(app);
+            Task::none()
+        }
+        Message::AdjustConfig(field, delta) => {
+            adjust_config(&mut app.settings, field, delta);
+            app.settings.match_preset();
+            update_daytime(app);
+            persist_settings(app);
+            if matches!(field, ConfigField::DayBoundaryHour) {
                 refresh_stats(app);
+                refresh_trend(app);
             }
             Task::none()
         }
-        Message::DismissLevelUp => {
-            app.level_up = None;
+        Message::ToggleAutoStart(enabled) => {
+            app.settings.auto_start = enabled;
+            if !enabled {
+                app.auto_start_in = None;
+            }
+            persist_settings(app);
             Task::none()
         }
-        Message::WindowReady(id) => {
-            app.window_id = Some(id);
+        Message::SetCycleMode(mode) => {
+            app.settings.cycle_mode = mode;
+            app.timer.cycle_mode = mode;
+            persist_settings(app);
             Task::none()
         }
-        Message::DragStart => {
-            if let Some(id) = app.window_id {
-                window::drag(id)
-            } else {
-                Task::none()
+        Message::ToggleResetCycleDaily(enabled) => {
+            app.settings.reset_cycle_daily = enabled;
+            if enabled {
+                app.timer.last_cycle_reset_date = Some(today(app));
             }
+            persist_settings(app);
+            Task::none()
         }
-        Message::Minimize => {
-            if let Some(id) = app.window_id {
-                window::minimize(id, true)
-            } else {
-                Task::none()
+        Message::SetTheme(choice) => {
+            app.settings.theme = match choice {
+                ThemeChoice::Auto => AUTO_THEME.to_string(),
+                ThemeChoice::Fixed(theme) => theme.to_string(),
+            };
+            update_daytime(app);
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ClockTick => {
+            update_daytime(app);
+            remind_if_streak_at_risk(app);
+            Task::none()
+        }
+        Message::TogglePromptForNotes(enabled) => {
+            app.settings.prompt_for_notes = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::TogglePromptForIntention(enabled) => {
+            app.settings.prompt_for_intention = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleSmoothRing(enabled) => {
+            app.settings.smooth_ring = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleReduceMotion(enabled) => {
+            app.settings.reduce_motion = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleStageChangesOnly(enabled) => {
+            app.settings.stage_changes_only = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::Toggle24hClock(enabled) => {
+            app.settings.use_24h_clock = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::SetAmbientSound(sound) => {
+            app.settings.ambient_sound = sound;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::SetSuspendAction(action) => {
+            app.settings.on_suspend = action;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::SelectPreset(name) => {
+            if app.settings.select_preset(&name) {
+                persist_settings(app);
             }
+            Task::none()
         }
-        Message::Close => {
-            if let Some(id) = app.window_id {
-                window::close(id)
-            } else {
-                Task::none()
+        Message::EditPresetName(name) => {
+            app.preset_name = name;
+            Task::none()
+        }
+        Message::SavePreset => {
+            let name = app.preset_name.trim().to_string();
+            if !name.is_empty() {
+                app.settings.save_preset(&name);
+                app.preset_name.clear();
+                persist_settings(app);
             }
+            Task::none()
         }
-    }
-}
-
-fn on_session_complete(app: &mut App) {
-    let session_type = app
-        .timer
-        .current_session_type()
-        .unwrap_or(SessionType::Focus);
-    let now = Local::now();
-    let today: NaiveDate = now.date_naive();
-    let completed_at = now.format("%Y-%m-%dT%H:%M:%S").to_string();
-
-    let mut xp_earned = None;
-    let old_level = app.profile.level;
-
-    if session_type == SessionType::Focus {
-        let new_streak = xp::update_streak(
-            app.profile.last_session_date,
-            today,
-            app.profile.current_streak,
-        );
-        app.profile.current_streak = new_streak;
-        if new_streak > app.profile.longest_streak {
-            app.profile.longest_streak = new_streak;
+        Message::DeletePreset => {
+            app.settings.delete_active_preset();
+            persist_settings(app);
+            Task::none()
         }
-        app.profile.last_session_date = Some(today);
-
-        let xp = xp::calculate_xp(app.profile.current_streak);
-        app.profile.total_xp += xp;
-        app.profile.level = xp::calculate_level(app.profile.total_xp);
-        xp_earned = Some(xp);
-
-        if app.profile.level > old_level {
-            let new_stage = xp::ferris_stage(app.profile.level);
-            notifications::notify_level_up(app.profile.level, new_stage);
-            app.level_up = Some(app.profile.level);
+        Message::SetHeatmapPalette(palette) => {
+            app.settings.heatmap_palette = palette;
+            persist_settings(app);
+            Task::none()
         }
-
-        app.today_sessions += 1;
-        app.total_sessions += 1;
-        app.total_focus_secs += FOCUS_DURATION_SECS;
-    }
-
-    if let Some(conn) = &app.db {
-        let session = Session {
-            id: None,
-            started_at: app.session_start_time.clone().unwrap_or_default(),
-            completed_at: Some(completed_at),
-            duration_secs: app.timer.total_duration_secs(),
-            session_type,
-            completed: true,
-        };
-        let _ = db::save_session(conn, &session);
-        let _ = db::update_profile(conn, &app.profile);
-    }
-
-    app.session_start_time = None;
-    notifications::notify_session_complete(session_type, xp_earned);
-}
-
-fn refresh_stats(app: &mut App) {
-    if let Some(conn) = &app.db {
-        let today = Local::now().format("%Y-%m-%d").to_string();
-        app.today_sessions = db::get_today_session_count(conn, &today).unwrap_or(0);
-        let (total, secs) = db::get_total_stats(conn).unwrap_or((0, 0));
-        app.total_sessions = total;
-        app.total_focus_secs = secs;
-
-        let week_start = (Local::now() - chrono::Duration::days(6))
-            .format("%Y-%m-%d")
-            .to_string();
-        app.weekly_data = db::get_sessions_in_range(conn, &week_start, &today).unwrap_or_default();
-
-        if let Ok(p) = db::get_profile(conn) {
-            app.profile = p;
+        Message::SetLanguage(language) => {
+            app.settings.language = language;
+            i18n::set_language(language);
+            persist_settings(app);
+            Task::none()
         }
-    }
-}
-
-fn subscription(app: &App) -> Subscription<Message> {
-    let timer_sub = if app.timer.is_running() {
-        time::every(Duration::from_secs(1)).map(|_| Message::Tick)
-    } else {
-        Subscription::none()
-    };
-
-    let window_sub = if app.window_id.is_none() {
-        window::open_events().map(Message::WindowReady)
-    } else {
-        Subscription::none()
-    };
-
-    Subscription::batch(vec![timer_sub, window_sub])
-}
-
-fn view_titlebar(_app: &App) -> Element<'_, Message> {
-    let drag_area = mouse_area(
-        space::horizontal()
-            .width(iced::Length::Fill)
-            .height(iced::Length::Fill),
-    )
-    .on_press(Message::DragStart);
-
-    let minimize = mouse_area(text("─").size(20)).on_press(Message::Minimize);
-
-    let close = mouse_area(text("✕").size(18)).on_press(Message::Close);
-
-    row![
-        drag_area,
-        minimize,
-        space::horizontal().width(iced::Length::Fixed(12.0)),
-        close,
-    ]
+        Message::SetWeekStart(week_start) => {
+            app.settings.week_starts_on = week_start;
+            persist_settings(app);
+            refresh_stats(app);
+            Task::none()
+        }
+        Message::SetAmbientVolume(percent) => {
+            app.settings.ambient_volume_percent = percent;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleQuietHours(enabled) => {
+            app.settings.quiet_hours_enabled = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleHideGamification(enabled) => {
+            app.settings.hide_gamification = enabled;
+            if enabled {
+                app.level_ups.clear();
+            }
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleTrackXp(enabled) => {
+            app.settings.track_xp = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleStreakReminder(enabled) => {
+            app.settings.streak_reminder_enabled = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::EditMessageTemplate(template, text) => {
+            let settings = &mut app.settings;
+            *match template {
+                MessageTemplate::FocusComplete => &mut settings.focus_complete_message,
+                MessageTemplate::BreakOver => &mut settings.break_over_message,
+                MessageTemplate::LevelUp => &mut settings.level_up_message,
+            } = text;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleSound(enabled) => {
+            app.settings.sound_enabled = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleStatusFile(enabled) => {
+            app.settings.status_file = enabled;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleIdlePause(enabled) => {
+            app.settings.idle_pause = enabled;
+            app.idle_paused = false;
+            // worth asking again, e.g. after switching desktops
+            app.idle_unsupported = false;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::CheckIdle => Task::perform(idle::idle_secs(), Message::IdleChecked),
+        Message::IdleChecked(idle_secs) => {
+            on_idle_checked(app, idle_secs);
+            Task::none()
+        }
+        #[cfg(feature = "http")]
+        Message::ToggleHttp(enabled) => {
+            app.settings.http_enabled = enabled;
+            persist_settings(app);
+            sync_http_server(app);
+            Task::none()
+        }
+        #[cfg(feature = "http")]
+        Message::EditHttpPort(text) => {
+            app.http_port_draft = text.chars().filter(char::is_ascii_digit).collect();
+            Task::none()
+        }
+        #[cfg(feature = "http")]
+        Message::ApplyHttpPort => {
+            match app.http_port_draft.parse::<u16>() {
+                Ok(port) if port > 0 => {
+                    app.settings.http_port = port as u32;
+                    persist_settings(app);
+                    sync_http_server(app);
+                }
+                _ => app.http_error = Some("Port must be 1 to 65535".to_string()),
+            }
+            Task::none()
+        }
+        Message::ToggleMinimizeToTray(enabled) => {
+            app.settings.minimize_to_tray = enabled;
+            if !enabled {
+                app.tray_ready = false;
+            }
+            persist_settings(app);
+            Task::none()
+        }
+        Message::Tray(event) => match (event, app.window_id) {
+            (TrayEvent::Ready, _) => {
+                app.tray_ready = true;
+                Task::none()
+            }
+            (TrayEvent::Show, Some(id)) => {
+                window::set_mode(id, window::Mode::Windowed).chain(window::gain_focus(id))
+            }
+            (TrayEvent::Quit, Some(id)) => window::close(id),
+            (_, None) => Task::none(),
+        },
+        Message::NotificationAction(ActionEvent::Ready(sender)) => {
+            app.notification_actions = Some(sender);
+            Task::none()
+        }
+        Message::NotificationAction(ActionEvent::StartNext) => {
+            // ignore stale clicks once the next session is already under way
+            if app.timer.is_finished() {
+                start_next(app);
+            }
+            Task::none()
+        }
+        Message::RetryDb => {
+            match db::init_db() {
+                Ok(conn) => {
+                    app.settings = db::get_settings(&conn).unwrap_or_default();
+                    i18n::set_language(app.settings.language);
+                    if let Ok(progress) = db::get_cycle_progress(&conn) {
+                        app.timer.restore_cycle(progress);
+                        app.saved_cycle = progress;
+                    }
+                    app.db = Some(conn);
+                    app.db_error = None;
+                    refresh_stats(app);
+                }
+                Err(e) => app.db_error = Some(e.to_string()),
+            }
+            Task::none()
+        }
+        Message::DismissWeekSummary => {
+            app.week_summary = None;
+            Task::none()
+        }
+        Message::DismissCycleSummary => {
+            app.cycle_summary = None;
+            Task::none()
+        }
+        Message::DismissLevelUp => {
+            app.level_ups.pop_front();
+            Task::none()
+        }
+        Message::WindowReady(id) => {
+            app.window_id = Some(id);
+            let size = window_size(&app.settings);
+            let position = match app.settings.window_position {
+                Some(_) => window::monitor_size(id).map(Message::RestorePosition),
+                None => Task::none(),
+            };
+            Task::batch([
+                window::set_level(id, window_level(&app.settings)),
+                window::resize(id, size),
+                position,
+            ])
+        }
+        Message::RestorePosition(monitor) => {
+            // iced only reports the monitor the window opened on, so that's the one we clamp to
+            match (app.window_id, app.settings.window_position, monitor) {
+                (Some(id), Some(position), Some(monitor)) => {
+                    let (x, y) = models::clamp_to_monitor(
+                        position,
+                        (app.settings.window_width, app.settings.window_height),
+                        (monitor.width as u32, monitor.height as u32),
+                    );
+                    window::move_to(id, iced::Point::new(x as f32, y as f32))
+                }
+                _ => Task::none(),
+            }
+        }
+        Message::WindowMoved(position) => {
+            app.settings.window_position = Some((position.x as i32, position.y as i32));
+            app.window_geometry_dirty = true;
+            Task::none()
+        }
+        Message::ToggleCompact => {
+            app.settings.compact_mode = !app.settings.compact_mode;
+            persist_settings(app);
+            match app.window_id {
+                Some(id) => window::resize(id, window_size(&app.settings)),
+                None => Task::none(),
+            }
+        }
+        Message::WindowFocused => {
+            // ticks may have been throttled while minimized, so check the deadline now
+            // rather than on the next one
+            reset_cycle_if_new_day(app);
+            if app.timer.is_running() && !reconcile_suspend(app) {
+                finish_if_due(app);
+            }
+            Task::none()
+        }
+        // the compact size is fixed, so only the full layout's size is remembered
+        Message::WindowResized(_) if app.settings.compact_mode => Task::none(),
+        Message::WindowResized(size) => {
+            app.settings.window_width = size.width as u32;
+            app.settings.window_height = size.height as u32;
+            app.window_geometry_dirty = true;
+            Task::none()
+        }
+        Message::SaveWindowGeometry => {
+            app.window_geometry_dirty = false;
+            persist_settings(app);
+            Task::none()
+        }
+        Message::ToggleOnTop => {
+            app.settings.always_on_top = !app.settings.always_on_top;
+            persist_settings(app);
+            match app.window_id {
+                Some(id) => window::set_level(id, window_level(&app.settings)),
+                None => Task::none(),
+            }
+        }
+        Message::DragStart => {
+            if let Some(id) = app.window_id {
+                window::drag(id)
+            } else {
+                Task::none()
+            }
+        }
+        Message::Minimize => match app.window_id {
+            Some(id) if hides_to_tray(app) => window::set_mode(id, window::Mode::Hidden),
+            Some(id) => window::minimize(id, true),
+            None => Task::none(),
+        },
+        Message::Close => match app.window_id {
+            Some(id) if hides_to_tray(app) => window::set_mode(id, window::Mode::Hidden),
+            Some(_) if has_active_session(app) => {
+                app.confirm_close = true;
+                Task::none()
+            }
+            Some(id) => window::close(id),
+            None => Task::none(),
+        },
+        Message::ConfirmClose => {
+            app.confirm_close = false;
+            // keep what was done so far instead of dropping it
+            if app.timer.is_counting() {
+                on_stopwatch_stop(app);
+            } else if has_active_session(app) {
+                on_skip(app);
+            }
+            match app.window_id {
+                Some(id) => window::close(id),
+                None => Task::none(),
+            }
+        }
+        Message::CancelClose => {
+            app.confirm_close = false;
+            Task::none()
+        }
+        Message::RequestReset => {
+            app.reset_step = Some(ResetStep::Ask);
+            Task::none()
+        }
+        Message::ConfirmReset => match app.reset_step {
+            Some(ResetStep::Ask) => {
+                app.reset_step = Some(ResetStep::Confirm);
+                Task::none()
+            }
+            Some(ResetStep::Confirm) => {
+                app.reset_step = None;
+                reset_all_data(app)
+            }
+            None => Task::none(),
+        },
+        Message::CancelReset => {
+            app.reset_step = None;
+            Task::none()
+        }
+    }
+}
+
+fn window_size(settings: &Settings) -> iced::Size {
+    if settings.compact_mode {
+        iced::Size::new(COMPACT_WINDOW_SIZE as f32, COMPACT_WINDOW_SIZE as f32)
+    } else {
+        iced::Size::new(settings.window_width as f32, settings.window_height as f32)
+    }
+}
+
+fn window_level(settings: &Settings) -> window::Level {
+    if settings.always_on_top {
+        window::Level::AlwaysOnTop
+    } else {
+        window::Level::Normal
+    }
+}
+
+fn has_active_session(app: &App) -> bool {
+    app.timer.is_running() || app.timer.is_paused() || app.timer.is_counting()
+}
+
+/// Only hide once the tray icon is up, otherwise the window can't be brought back.
+fn hides_to_tray(app: &App) -> bool {
+    app.settings.minimize_to_tray && app.tray_ready
+}
+
+/// Start, which asks for an intention first if focus is next and that's turned on.
+fn on_start(app: &mut App) {
+    // a second Start, or Enter in the prompt, goes ahead with the intention
+    if app.settings.prompt_for_intention
+        && app.intention_draft.is_none()
+        && app.timer.next_session_type() == SessionType::Focus
+    {
+        app.auto_start_in = None;
+        app.intention_draft = Some(String::new());
+    } else {
+        start_next(app);
+    }
+}
+
+/// Starts whatever comes next in the cycle, without asking for an intention.
+fn start_next(app: &mut App) {
+    app.auto_start_in = None;
+    reset_cycle_if_new_day(app);
+    app.timer.start_next(&app.settings.timer);
+    on_session_start(app);
+}
+
+/// Stamps the start time and picks up the tag and intention for a focus session.
+fn on_session_start(app: &mut App) {
+    app.last_tick = None;
+    let is_focus = app.timer.current_session_type() == Some(SessionType::Focus);
+    let intention = app.intention_draft.take();
+    app.late_hint = is_focus && app.settings.is_quiet_hour(Local::now().hour());
+    let non_empty = |s: &str| {
+        let s = s.trim();
+        (is_focus && !s.is_empty()).then(|| s.to_string())
+    };
+    app.session = Some(SessionStart {
+        started_at: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        tag: non_empty(&app.active_tag),
+        intention: intention.as_deref().and_then(non_empty),
+    });
+}
+
+fn on_session_complete(app: &mut App) {
+    let session_type = app
+        .timer
+        .current_session_type()
+        .unwrap_or(SessionType::Focus);
+    let result = engine::complete_session(
+        app.db.as_ref(),
+        &mut app.profile,
+        &app.settings,
+        &app.timer,
+        // kept until the next start, in case the session is extended
+        app.session.clone().unwrap_or_default(),
+        Local::now().naive_local(),
+    );
+    // announced ahead of any level-up or achievement it leads to
+    let xp_earned = result.as_ref().ok().and_then(|completion| completion.xp);
+    notifications::notify_session_complete(
+        session_type,
+        xp_earned.filter(|_| !app.settings.hide_gamification),
+        &app.profile,
+        app.notification_actions.clone(),
+        &app.settings,
+    );
+    notifications::play_completion_sound(session_type, &app.settings);
+    if session_type == SessionType::LongBreak {
+        app.cycle_summary = app.timer.finished_cycle.take();
+    }
+    on_recorded(app, result);
+}
+
+/// Stopwatch runs shorter than this are dropped rather than saved.
+const MIN_STOPWATCH_SECS: u32 = 60;
+
+/// Stopping a stopwatch saves it as a completed focus session with XP for the time spent.
+fn on_stopwatch_stop(app: &mut App) {
+    let Some(elapsed) = app.timer.stop_stopwatch() else {
+        return;
+    };
+    if elapsed < MIN_STOPWATCH_SECS {
+        app.session = None;
+        return;
+    }
+
+    let result = engine::complete_stopwatch(
+        app.db.as_ref(),
+        &mut app.profile,
+        &app.settings,
+        elapsed,
+        app.timer.interruptions,
+        app.session.take().unwrap_or_default(),
+        Local::now().naive_local(),
+    );
+    on_recorded(app, result);
+}
+
+/// Brings counters, the level-up queue and undo in line with a session the
+/// engine has recorded.
+fn on_recorded(app: &mut App, result: rusqlite::Result<Completion>) {
+    let completion = match result {
+        Ok(completion) => completion,
+        Err(e) => {
+            eprintln!("Failed to save session: {}", e);
+            app.last_completed = None;
+            return;
+        }
+    };
+
+    app.stats.record(&completion);
+    refresh_trend(app);
+
+    let celebrate = !app.settings.hide_gamification;
+    let stage_changes_only = app.settings.stage_changes_only;
+    let mut leveled_up = false;
+    for event in &completion.events {
+        match *event {
+            Event::LeveledUp(level)
+                if celebrate && (!stage_changes_only || xp::reaches_new_stage(level)) =>
+            {
+                app.level_ups.push_back(level);
+                leveled_up = true;
+            }
+            Event::LeveledUp(_) => {}
+            Event::Unlocked(achievement) => {
+                let now = Local::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+                app.stats.achievements.push((achievement, now));
+                if celebrate {
+                    notifications::notify_achievement(achievement, &app.settings);
+                }
+            }
+            Event::WeeklyGoalMet(goal) => notifications::notify_weekly_goal(goal, &app.settings),
+        }
+    }
+    if leveled_up {
+        notifications::notify_level_up(&app.profile, &app.settings);
+    }
+
+    // a stopwatch has already gone back to idle and never joins the cycle
+    let in_cycle = matches!(
+        app.timer.state,
+        TimerState::Finished {
+            session_type: SessionType::Focus
+        }
+    );
+    app.last_completed = completion.id.map(|id| CompletedSession {
+        id,
+        xp: completion.xp.unwrap_or(0),
+        in_cycle,
+        duration_secs: completion.duration_secs,
+        at: Instant::now(),
+    });
+    if let (Some(id), SessionType::Focus, true) = (
+        completion.id,
+        completion.session_type,
+        app.settings.prompt_for_notes,
+    ) {
+        app.pending_note = Some((id, String::new()));
+    }
+}
+
+/// Takes back the session that just completed, if still within the undo window.
+fn undo_last_session(app: &mut App) {
+    if app.db.is_none() {
+        return;
+    }
+    let Some(last) = app.last_completed.take() else {
+        return;
+    };
+    if last.at.elapsed() > UNDO_WINDOW {
+        app.data_status = Some(tr("status.undo_too_late").to_string());
+        return;
+    }
+
+    let status = match take_back(app, last) {
+        Ok(true) => {
+            if last.in_cycle {
+                app.timer.uncount_focus(last.duration_secs);
+            }
+            tr("status.undone").to_string()
+        }
+        Ok(false) => tr("status.nothing_to_undo").to_string(),
+        Err(e) => trf("status.undo_failed", &[("error", e.to_string())]),
+    };
+    app.data_status = Some(status);
+}
+
+/// Removes `last` from the history and everything counted from it.
+fn take_back(app: &mut App, last: CompletedSession) -> rusqlite::Result<bool> {
+    let Some(conn) = &app.db else {
+        return Ok(false);
+    };
+    if !engine::undo_session(conn, last.id, last.xp, app.settings.day_boundary_hour)? {
+        return Ok(false);
+    }
+    app.pending_note = app.pending_note.take().filter(|(id, _)| *id != last.id);
+    refresh_stats(app);
+    refresh_trend(app);
+    let level = app.profile.level;
+    app.level_ups.retain(|&l| l <= level);
+    Ok(true)
+}
+
+/// Adds time to the current session. One that already ran out was recorded
+/// then, so it comes back out to be recorded again, longer, when it ends.
+fn on_extend(app: &mut App) {
+    if app.timer.is_finished() {
+        let Some(last) = app.last_completed.take() else {
+            return;
+        };
+        match take_back(app, last) {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                eprintln!("Failed to reopen session: {}", e);
+                return;
+            }
+        }
+    }
+    app.auto_start_in = None;
+    app.timer.extend(app.settings.extend_secs);
+}
+
+/// Gives up the current session, e.g. after a long pause or on close. A
+/// focus session is recorded as abandoned with the time actually spent.
+/// Returns true if it was a break.
+fn on_skip(app: &mut App) -> bool {
+    let skipped = engine::skip_session(
+        app.db.as_ref(),
+        &mut app.timer,
+        app.session.take().unwrap_or_default(),
+    );
+    match skipped {
+        Ok(engine::Skipped::Focus(Some(elapsed))) => app.stats.record_abandoned(elapsed),
+        Ok(engine::Skipped::Focus(None)) => {}
+        Ok(engine::Skipped::Break) => return true,
+        Err(e) => eprintln!("Failed to save abandoned session: {}", e),
+    }
+    false
+}
+
+/// The Skip button, which unlike `on_skip` goes on from a break to the next
+/// focus session, the same way Start would.
+fn on_skip_to_focus(app: &mut App) {
+    if on_skip(app) {
+        on_start(app);
+    }
+}
+
+/// A running focus session can go idle, and one the idle check paused can come back.
+fn watching_idle(app: &App) -> bool {
+    let in_focus = app.timer.current_session_type() == Some(SessionType::Focus);
+    in_focus && (app.timer.is_running() || (app.idle_paused && app.timer.is_paused()))
+}
+
+fn on_idle_checked(app: &mut App, idle_secs: Option<u64>) {
+    let Some(idle_secs) = idle_secs else {
+        if !app.idle_unsupported {
+            eprintln!("Idle time isn't available on this desktop; not pausing when away");
+        }
+        app.idle_unsupported = true;
+        return;
+    };
+    let focus_running =
+        app.timer.is_running() && app.timer.current_session_type() == Some(SessionType::Focus);
+    let idle_paused = app.idle_paused && app.timer.is_paused();
+    match idle::react(
+        idle_secs,
+        app.settings.idle_threshold_secs,
+        focus_running,
+        idle_paused,
+    ) {
+        idle::IdleAction::Pause => {
+            app.last_tick = None;
+            app.timer
+                .pause_idle(Duration::from_secs(idle_secs), Instant::now());
+            app.idle_paused = true;
+            notifications::notify_idle_paused(&app.settings);
+        }
+        idle::IdleAction::Resume => {
+            app.last_tick = None;
+            app.timer.resume();
+            app.idle_paused = false;
+        }
+        idle::IdleAction::Nothing => {}
+    }
+}
+
+fn adjust_config(settings: &mut Settings, field: ConfigField, delta: i32) {
+    let config = &mut settings.timer;
+    let wrap_hour = |hour: u32| (hour as i32 + delta).rem_euclid(24) as u32;
+    match field {
+        ConfigField::FocusMinutes => {
+            config.focus_secs = step_minutes(config.focus_secs, delta, models::DURATION_SECS_RANGE)
+        }
+        ConfigField::ShortBreakMinutes => {
+            config.short_break_secs =
+                step_minutes(config.short_break_secs, delta, models::DURATION_SECS_RANGE)
+        }
+        ConfigField::LongBreakMinutes => {
+            config.long_break_secs =
+                step_minutes(config.long_break_secs, delta, models::DURATION_SECS_RANGE)
+        }
+        ConfigField::SessionsBeforeLongBreak => {
+            config.sessions_before_long_break = step(
+                config.sessions_before_long_break,
+                delta,
+                models::SESSIONS_BEFORE_LONG_BREAK_RANGE,
+            )
+        }
+        ConfigField::AutoStartDelaySecs => {
+            settings.auto_start_delay_secs = step(
+                settings.auto_start_delay_secs,
+                delta,
+                models::AUTO_START_DELAY_SECS_RANGE,
+            )
+        }
+        ConfigField::AutoAbandonMinutes => {
+            settings.auto_abandon_after_secs = step_minutes(
+                settings.auto_abandon_after_secs,
+                delta,
+                models::AUTO_ABANDON_AFTER_SECS_RANGE,
+            )
+        }
+        ConfigField::IdleMinutes => {
+            settings.idle_threshold_secs = step_minutes(
+                settings.idle_threshold_secs,
+                delta,
+                models::IDLE_THRESHOLD_SECS_RANGE,
+            )
+        }
+        ConfigField::ExtendMinutes => {
+            settings.extend_secs =
+                step_minutes(settings.extend_secs, delta, models::EXTEND_SECS_RANGE)
+        }
+        ConfigField::LightFromHour => {
+            settings.light_from_hour = wrap_hour(settings.light_from_hour)
+        }
+        ConfigField::BackupsToKeep => {
+            settings.backups_to_keep = step(
+                settings.backups_to_keep,
+                delta,
+                models::BACKUPS_TO_KEEP_RANGE,
+            )
+        }
+        ConfigField::QuietFromHour => {
+            settings.quiet_from_hour = wrap_hour(settings.quiet_from_hour)
+        }
+        ConfigField::QuietUntilHour => {
+            settings.quiet_until_hour = wrap_hour(settings.quiet_until_hour)
+        }
+        ConfigField::StreakReminderHour => {
+            settings.streak_reminder_hour = wrap_hour(settings.streak_reminder_hour)
+        }
+        ConfigField::WeeklyGoal => {
+            settings.weekly_goal = step(settings.weekly_goal, delta, models::WEEKLY_GOAL_RANGE)
+        }
+        ConfigField::DailyGoal => {
+            settings.daily_goal = step(settings.daily_goal, delta, models::DAILY_GOAL_RANGE)
+        }
+        ConfigField::DayBoundaryHour => {
+            settings.day_boundary_hour = step(
+                settings.day_boundary_hour,
+                delta,
+                models::DAY_BOUNDARY_HOUR_RANGE,
+            )
+        }
+        ConfigField::DarkFromHour => settings.dark_from_hour = wrap_hour(settings.dark_from_hour),
+        ConfigField::BaseXp => {
+            settings.xp.base_xp = step(settings.xp.base_xp, delta * 10, models::BASE_XP_RANGE)
+        }
+        ConfigField::StreakBonusPerDay => {
+            settings.xp.streak_bonus_per_day = step(
+                settings.xp.streak_bonus_per_day,
+                delta,
+                models::STREAK_BONUS_PER_DAY_RANGE,
+            )
+        }
+        ConfigField::MaxStreakBonus => {
+            settings.xp.max_streak_bonus = step(
+                settings.xp.max_streak_bonus,
+                delta * 10,
+                models::MAX_STREAK_BONUS_RANGE,
+            )
+        }
+    }
+}
+
+/// `value` moved by `delta`, kept within `range`.
+fn step(value: u32, delta: i32, range: RangeInclusive<u32>) -> u32 {
+    (value as i64 + delta as i64).clamp(*range.start() as i64, *range.end() as i64) as u32
+}
+
+/// Steps a length in seconds by whole minutes, kept within `range`.
+fn step_minutes(secs: u32, delta: i32, range: RangeInclusive<u32>) -> u32 {
+    step(secs / 60, delta, range.start() / 60..=range.end() / 60) * 60
+}
+
+/// Runs the completion flow if the session's deadline has passed.
+fn finish_if_due(app: &mut App) -> bool {
+    if !app.timer.tick() {
+        return false;
+    }
+    on_session_complete(app);
+    if app.settings.auto_start {
+        app.auto_start_in = Some(app.settings.auto_start_delay_secs);
+    }
+    true
+}
+
+/// Without a window to capture, share a text summary through the clipboard instead.
+fn share_as_text(app: &mut App) -> Task<Message> {
+    app.data_status = Some(tr("status.copied_instead").to_string());
+    copy_summary(app)
+}
+
+fn copy_summary(app: &App) -> Task<Message> {
+    iced::clipboard::write(share::summary(&app.profile, &app.stats, today(app)))
+}
+
+/// Catches the timer up after the machine slept through some ticks. Returns
+/// true if the session ran out meanwhile and was abandoned per the settings.
+fn reconcile_suspend(app: &mut App) -> bool {
+    let now = (SystemTime::now(), Instant::now());
+    let last_tick = std::mem::replace(&mut app.last_tick, app.timer.is_running().then_some(now));
+    let Some((last_wall, last_mono)) = last_tick.filter(|_| app.timer.is_running()) else {
+        return false;
+    };
+
+    let wall_elapsed = now.0.duration_since(last_wall).unwrap_or_default();
+    let ran_out = app
+        .timer
+        .reconcile_gap(wall_elapsed, now.1 - last_mono, now.1);
+    if ran_out && app.settings.on_suspend == SuspendAction::Abandon {
+        on_skip(app);
+        return true;
+    }
+    false
+}
+
+/// Picks up a countdown left under way when the app last closed; one that
+/// ran out in the meantime is recorded as completed straight away.
+fn restore_timer(app: &mut App) {
+    let Some(conn) = &app.db else {
+        return;
+    };
+    let (snapshot, start) = match db::get_active_timer(conn) {
+        Ok(Some(active)) => active,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("Failed to restore timer: {}", e);
+            return;
+        }
+    };
+    app.timer.restore(&snapshot, Local::now().naive_local());
+    app.session = Some(start);
+    app.saved_timer = Some(snapshot);
+    finish_if_due(app);
+}
+
+/// Saves the countdown whenever it changes course: started, paused, resumed,
+/// extended, or over.
+fn sync_saved_timer(app: &mut App) {
+    let snapshot = app.timer.snapshot(Local::now().naive_local());
+    let unchanged = match (&snapshot, &app.saved_timer) {
+        (Some(now), Some(saved)) => now.same_course(saved),
+        (None, None) => true,
+        _ => false,
+    };
+    if unchanged {
+        return;
+    }
+    if let Some(conn) = &app.db {
+        let active = snapshot.as_ref().zip(app.session.as_ref());
+        if let Err(e) = db::save_active_timer(conn, active) {
+            eprintln!("Failed to save timer: {}", e);
+        }
+    }
+    app.saved_timer = snapshot;
+}
+
+fn sync_saved_cycle(app: &mut App) {
+    let progress = app.timer.cycle_progress();
+    if progress == app.saved_cycle {
+        return;
+    }
+    let saved = match &app.db {
+        Some(conn) => db::save_cycle_progress(conn, &progress),
+        None => Ok(()),
+    };
+    if let Err(e) = saved {
+        eprintln!("Failed to save cycle: {}", e);
+    }
+    app.saved_cycle = progress;
+}
+
+fn sync_status_file(app: &mut App) {
+    if !app.settings.status_file {
+        if app.written_status.take().is_some() {
+            status::clear();
+        }
+        return;
+    }
+    let status = Status::new(&app.timer, app.stats.today_sessions);
+    if app.written_status.as_ref() == Some(&status) {
+        return;
+    }
+    if let Err(e) = status::write(&status) {
+        eprintln!("Failed to write status file: {}", e);
+    }
+    app.written_status = Some(status);
+}
+
+/// Starts, stops or moves the stats endpoint to match the settings.
+#[cfg(feature = "http")]
+fn sync_http_server(app: &mut App) {
+    app.http_port_draft = app.settings.http_port.to_string();
+    let wanted = app
+        .settings
+        .http_enabled
+        .then_some(app.settings.http_port as u16);
+    if app.http_server.as_ref().map(|server| server.port) == wanted {
+        return;
+    }
+    // the old server stops as it's dropped
+    app.http_server = None;
+    app.http_error = None;
+    if let Some(port) = wanted {
+        match http::Server::start(port) {
+            Ok(server) => app.http_server = Some(server),
+            Err(e) => app.http_error = Some(format!("Couldn't serve stats: {}", e)),
+        }
+    }
+}
+
+/// Ambient noise plays only while a focus session is actually running.
+fn sync_ambient(app: &mut App) {
+    let focusing = app.timer.is_counting()
+        || (app.timer.is_running() && app.timer.current_session_type() == Some(SessionType::Focus));
+    if focusing && app.settings.ambient_sound != AmbientSound::Off {
+        let volume = app.settings.ambient_volume_percent as f32 / 100.0;
+        app.ambient.play(app.settings.ambient_sound, volume);
+    } else {
+        app.ambient.stop();
+    }
+}
+
+fn remind_if_streak_at_risk(app: &mut App) {
+    let now = Local::now().naive_local();
+    if notifications::streak_reminder_due(&app.settings, &app.profile, app.last_reminder_date, now)
+    {
+        notifications::notify_streak_at_risk(app.profile.current_streak);
+        let today = app.settings.day_of(now);
+        app.last_reminder_date = Some(today);
+        if let Some(conn) = &app.db {
+            let _ = db::set_last_reminder_date(conn, today);
+        }
+    }
+}
+
+fn update_daytime(app: &mut App) {
+    app.daytime = app.settings.is_daytime(Local::now().hour());
+}
+
+/// Idle time produces no ticks, so this runs on `Start` as well as `Tick`.
+fn reset_cycle_if_new_day(app: &mut App) {
+    if app.settings.reset_cycle_daily {
+        app.timer.reset_cycle_if_new_day(today(app));
+    }
+}
+
+/// Takes on settings from another machine, keeping this one's window placement.
+fn apply_imported_settings(app: &mut App, mut settings: Settings) -> Task<Message> {
+    settings.window_position = app.settings.window_position;
+    settings.window_width = app.settings.window_width;
+    settings.window_height = app.settings.window_height;
+    app.settings = settings;
+    app.timer.cycle_mode = app.settings.cycle_mode;
+    i18n::set_language(app.settings.language);
+    update_daytime(app);
+    persist_settings(app);
+    refresh_stats(app);
+    refresh_trend(app);
+    #[cfg(feature = "http")]
+    sync_http_server(app);
+    match app.window_id {
+        Some(id) => Task::batch([
+            window::set_level(id, window_level(&app.settings)),
+            window::resize(id, window_size(&app.settings)),
+        ]),
+        None => Task::none(),
+    }
+}
+
+fn persist_settings(app: &App) {
+    if let Some(conn) = &app.db {
+        let _ = db::save_settings(conn, &app.settings);
+    }
+}
+
+/// The day stats and streaks count as today, which starts at the day boundary.
+fn today(app: &App) -> NaiveDate {
+    app.settings.day_of(Local::now().naive_local())
+}
+
+fn refresh_stats(app: &mut App) {
+    if let Some(conn) = &app.db {
+        match Stats::load(conn, today(app), &app.settings) {
+            Ok(stats) => app.stats = stats,
+            Err(e) => eprintln!("Failed to load stats: {}", e),
+        }
+        if let Ok(p) = db::get_profile(conn) {
+            app.profile = p;
+        }
+    }
+}
+
+/// Fetches the next page of history, or starts again from the newest session.
+fn load_history(app: &mut App, from_start: bool) {
+    let Some(conn) = &app.db else {
+        return;
+    };
+    if from_start {
+        app.history.clear();
+        app.history_open = None;
+    }
+    let today = today(app);
+    let filter = db::SessionFilter {
+        session_type: app.history_type.0,
+        start: app.history_range.days().map(|days| {
+            (today - chrono::Duration::days(days - 1))
+                .format("%Y-%m-%d")
+                .to_string()
+        }),
+        end: None,
+    };
+    // one extra row tells whether there's another page
+    match db::get_sessions_paginated(
+        conn,
+        app.history.len() as u32,
+        HISTORY_PAGE + 1,
+        &filter,
+        app.settings.day_boundary_hour,
+    ) {
+        Ok(mut page) => {
+            app.history_more = page.len() > HISTORY_PAGE as usize;
+            page.truncate(HISTORY_PAGE as usize);
+            app.history.extend(page);
+        }
+        Err(e) => eprintln!("Failed to load history: {}", e),
+    }
+}
+
+/// Wipes the database and puts the app back the way a fresh install starts.
+fn reset_all_data(app: &mut App) -> Task<Message> {
+    let Some(conn) = &app.db else {
+        return Task::none();
+    };
+    if let Err(e) = db::reset_all(conn) {
+        app.data_status = Some(trf("status.reset_failed", &[("error", e.to_string())]));
+        return Task::none();
+    }
+
+    app.profile = UserProfile::default();
+    app.settings = Settings::default();
+    i18n::set_language(app.settings.language);
+    app.timer = Timer::new();
+    app.timer.last_cycle_reset_date = Some(today(app));
+    app.session = None;
+    app.active_tag.clear();
+    app.intention_draft = None;
+    app.preset_name.clear();
+    app.pending_note = None;
+    app.last_completed = None;
+    app.level_ups.clear();
+    app.week_summary = None;
+    app.cycle_summary = None;
+    app.auto_start_in = None;
+    app.last_tick = None;
+    app.last_reminder_date = None;
+    app.daytime = app.settings.is_daytime(Local::now().hour());
+    refresh_stats(app);
+    refresh_trend(app);
+    app.data_status = Some(tr("status.deleted").to_string());
+
+    match app.window_id {
+        Some(id) => Task::batch([
+            window::set_level(id, window_level(&app.settings)),
+            window::resize(id, window_size(&app.settings)),
+        ]),
+        None => Task::none(),
+    }
+}
+
+fn refresh_trend(app: &mut App) {
+    if let Some(conn) = &app.db {
+        let boundary = app.settings.day_boundary_hour;
+        match engine::daily_counts(conn, today(app), engine::TREND_DAYS, boundary) {
+            Ok(trend) => app.trend = trend,
+            Err(e) => eprintln!("Failed to load trend: {}", e),
+        }
+    }
+}
+
+/// Only a running focus session on screen animates, so idle costs nothing.
+fn is_animating(app: &App) -> bool {
+    !app.settings.reduce_motion
+        && app.timer.is_running()
+        && app.timer.current_session_type() == Some(SessionType::Focus)
+        && (app.settings.compact_mode || app.current_view == View::Timer)
+}
+
+/// The ring redraws between ticks; the seconds themselves still tick once a second.
+fn is_smooth_ring(app: &App) -> bool {
+    app.settings.smooth_ring
+        && !app.settings.reduce_motion
+        && app.timer.is_running()
+        && (app.settings.compact_mode || app.current_view == View::Timer)
+}
+
+/// Starts the fade when a session gives way to one of another type, e.g. a
+/// break following focus. Reduced motion switches at once.
+fn sync_switch(app: &mut App) {
+    let current = app.timer.current_session_type();
+    let switched = matches!(
+        (app.shown_session_type, current),
+        (Some(from), Some(to)) if from != to
+    );
+    if switched && !app.settings.reduce_motion {
+        app.switched_at = Some(Instant::now());
+    }
+    app.shown_session_type = current;
+}
+
+fn is_switching(app: &App) -> bool {
+    app.switched_at
+        .is_some_and(|at| at.elapsed() < SWITCH_DURATION)
+}
+
+/// 0.0..1.0 as the new session type eases in; 1.0 once it has
+fn switch_progress(app: &App) -> f32 {
+    let Some(at) = app.switched_at else {
+        return 1.0;
+    };
+    let t = (at.elapsed().as_secs_f32() / SWITCH_DURATION.as_secs_f32()).min(1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn animation_frame(app: &App) -> Duration {
+    if is_smooth_ring(app) || is_switching(app) {
+        SMOOTH_FRAME
+    } else {
+        ANIMATION_FRAME
+    }
+}
+
+/// 0.0..1.0 and back over each animation cycle; rests at 0.0 when not animating
+fn animation_wave(app: &App) -> f32 {
+    if !is_animating(app) {
+        return 0.0;
+    }
+    (1.0 - (app.animation_phase * std::f32::consts::TAU).cos()) / 2.0
+}
+
+fn subscription(app: &App) -> Subscription<Message> {
+    let timer_sub =
+        if app.timer.is_running() || app.timer.is_counting() || app.auto_start_in.is_some() {
+            time::every(Duration::from_secs(1)).map(|_| Message::Tick)
+        } else {
+            Subscription::none()
+        };
+
+    let pause_sub = if app.timer.is_paused() && app.settings.auto_abandon_after_secs > 0 {
+        time::every(Duration::from_secs(1)).map(|_| Message::PauseTick)
+    } else {
+        Subscription::none()
+    };
+
+    let idle_sub = if app.settings.idle_pause && !app.idle_unsupported && watching_idle(app) {
+        time::every(idle::POLL_INTERVAL).map(|_| Message::CheckIdle)
+    } else {
+        Subscription::none()
+    };
+
+    let animation_sub = if is_animating(app) || is_smooth_ring(app) || is_switching(app) {
+        time::every(animation_frame(app)).map(|_| Message::AnimationTick)
+    } else {
+        Subscription::none()
+    };
+
+    let window_sub = if app.window_id.is_none() {
+        window::open_events().map(Message::WindowReady)
+    } else {
+        Subscription::none()
+    };
+
+    let geometry_sub = iced::event::listen_with(|event, _status, _id| match event {
+        iced::Event::Window(window::Event::Moved(position)) => Some(Message::WindowMoved(position)),
+        iced::Event::Window(window::Event::Resized(size)) => Some(Message::WindowResized(size)),
+        iced::Event::Window(window::Event::Focused) => Some(Message::WindowFocused),
+        _ => None,
+    });
+
+    // a drag produces a stream of moves, so save at most once a second
+    let save_geometry_sub = if app.window_geometry_dirty {
+        time::every(Duration::from_secs(1)).map(|_| Message::SaveWindowGeometry)
+    } else {
+        Subscription::none()
+    };
+
+    // runs independently of window visibility, so ticks continue while hidden
+    let tray_sub = if app.settings.minimize_to_tray {
+        Subscription::run(tray::events).map(Message::Tray)
+    } else {
+        Subscription::none()
+    };
+
+    let clock_sub = if app.settings.theme == AUTO_THEME || app.settings.streak_reminder_enabled {
+        time::every(Duration::from_secs(60)).map(|_| Message::ClockTick)
+    } else {
+        Subscription::none()
+    };
+
+    let actions_sub =
+        Subscription::run(notifications::action_events).map(Message::NotificationAction);
+
+    Subscription::batch(vec![
+        timer_sub,
+        pause_sub,
+        idle_sub,
+        animation_sub,
+        window_sub,
+        geometry_sub,
+        save_geometry_sub,
+        tray_sub,
+        actions_sub,
+        clock_sub,
+    ])
+}
+
+fn theme(app: &App) -> Theme {
+    match theme_choice(&app.settings.theme) {
+        ThemeChoice::Auto if app.daytime => Theme::CatppuccinLatte,
+        ThemeChoice::Auto => Theme::CatppuccinMocha,
+        ThemeChoice::Fixed(theme) => theme,
+    }
+}
+
+/// Unknown names (e.g. a theme dropped from iced) fall back to the default.
+fn theme_choice(name: &str) -> ThemeChoice {
+    if name == AUTO_THEME {
+        return ThemeChoice::Auto;
+    }
+    let theme = Theme::ALL
+        .iter()
+        .find(|theme| theme.to_string() == name)
+        .cloned()
+        .unwrap_or(Theme::CatppuccinMocha);
+    ThemeChoice::Fixed(theme)
+}
+
+fn view_titlebar(app: &App) -> Element<'_, Message> {
+    let drag_area = mouse_area(
+        space::horizontal()
+            .width(iced::Length::Fill)
+            .height(iced::Length::Fill),
+    )
+    .on_press(Message::DragStart);
+
+    let pinned = app.settings.always_on_top;
+    let pin = mouse_area(text("📌").size(14).style(move |theme: &Theme| text::Style {
+        color: (!pinned).then(|| Color {
+            a: 0.35,
+            ..theme.palette().text
+        }),
+    }))
+    .on_press(Message::ToggleOnTop);
+
+    let compact = mouse_area(text("⊟").size(18)).on_press(Message::ToggleCompact);
+
+    let minimize = mouse_area(text("─").size(20)).on_press(Message::Minimize);
+
+    let close = mouse_area(text("✕").size(18)).on_press(Message::Close);
+
+    row![
+        drag_area,
+        pin,
+        space::horizontal().width(iced::Length::Fixed(12.0)),
+        compact,
+        space::horizontal().width(iced::Length::Fixed(12.0)),
+        minimize,
+        space::horizontal().width(iced::Length::Fixed(12.0)),
+        close,
+    ]
+    .align_y(Center)
+    .padding(Padding::from([0u16, 8]))
+    .height(40)
+    .into()
+}
+
+fn view(app: &App) -> Element<'_, Message> {
+    if app.settings.compact_mode {
+        return view_compact(app);
+    }
+
+    let titlebar = view_titlebar(app);
+
+    let content: Element<Message> = match app.current_view {
+        View::Timer => view_timer(app),
+        View::Stats => view_stats(app),
+        View::History => view_history(app),
+        View::Settings => view_settings(app),
+    };
+
+    let nav = view_nav(app);
+
+    let layout = column![
+        titlebar,
+        view_db_error(app),
+        content,
+        space::vertical().height(8),
+        nav,
+    ]
+    .padding(Padding::from([0u16, 24]))
+    .spacing(0)
+    .width(Fill)
+    .height(Fill);
+
+    let main_view = container(layout).width(Fill).height(Fill);
+
+    let modal = if let Some(&level) = app.level_ups.front() {
+        view_level_up(level, app.level_ups.len())
+    } else if app.confirm_close {
+        view_close_confirm()
+    } else if let Some(step) = app.reset_step {
+        view_reset_confirm(step)
+    } else if let Some((summary, xp_gained)) = &app.week_summary {
+        view_week_summary(
+            summary,
+            (!app.settings.hide_gamification).then_some(*xp_gained),
+        )
+    } else if let Some(cycle) = app.cycle_summary {
+        view_cycle_summary(cycle)
+    } else {
+        return main_view.into();
+    };
+
+    let modal_container = container(modal)
+        .width(iced::Length::Fill)
+        .center_x(iced::Length::Fill)
+        .center_y(iced::Length::Fill);
+
+    column![main_view, modal_container].into()
+}
+
+fn view_level_up(level: u32, queued: usize) -> Element<'static, Message> {
+    let stage = xp::ferris_stage(level);
+    let prev_stage = xp::ferris_stage(level.saturating_sub(1));
+
+    column![
+        text(tr("level_up.title")).size(28),
+        space::vertical().height(20),
+        row![
+            text(prev_stage.emoji()).size(48),
+            text(" → ").size(32),
+            text(stage.emoji()).size(48),
+        ],
+        space::vertical().height(12),
+        text(trf("common.level", &[("level", level.to_string())])).size(24),
+        space::vertical().height(8),
+        text(stage.label()).size(18),
+        space::vertical().height(24),
+        button(
+            text(tr(if queued > 1 {
+                "common.next"
+            } else {
+                "common.continue"
+            }))
+            .size(16)
+        )
+        .on_press(Message::DismissLevelUp)
+        .padding([12, 24])
+        .style(button::primary),
+    ]
+    .align_x(Center)
+    .spacing(0)
+    .padding(32)
+    .into()
+}
+
+/// `xp_gained` is `None` in focus-only mode.
+fn view_week_summary(summary: &db::WeekSummary, xp_gained: Option<u32>) -> Element<'_, Message> {
+    let best_day = match &summary.best_day {
+        Some((day, count)) => trf(
+            "week.best_day",
+            &[("day", day.to_string()), ("n", count.to_string())],
+        ),
+        None => tr("week.best_day_none").to_string(),
+    };
+    column![
+        text(tr("week.title")).size(24),
+        text(trf(
+            "week.of",
+            &[("date", summary.week_start.format("%b %-d").to_string())]
+        ))
+        .size(14),
+        space::vertical().height(16),
+        text(trf("week.sessions", &[("n", summary.sessions.to_string())])).size(16),
+        text(trf(
+            "week.focused",
+            &[
+                ("h", (summary.focus_secs / 3600).to_string()),
+                ("m", ((summary.focus_secs % 3600) / 60).to_string()),
+            ]
+        ))
+        .size(16),
+        text(best_day).size(14),
+        text(
+            xp_gained
+                .map(|xp| format!("⭐ +{} XP", xp))
+                .unwrap_or_default()
+        )
+        .size(14),
+        space::vertical().height(24),
+        button(text(tr("week.dismiss")).size(16))
+            .on_press(Message::DismissWeekSummary)
+            .padding([12, 24])
+            .style(button::primary),
+    ]
+    .align_x(Center)
+    .spacing(4)
+    .padding(32)
+    .into()
+}
+
+fn view_cycle_summary(cycle: CycleTotals) -> Element<'static, Message> {
+    column![
+        text(tr("cycle.title")).size(24),
+        space::vertical().height(16),
+        text(trf("cycle.sessions", &[("n", cycle.sessions.to_string())])).size(16),
+        text(trf(
+            "cycle.focused",
+            &[("m", (cycle.focus_secs / 60).to_string())]
+        ))
+        .size(16),
+        space::vertical().height(24),
+        button(text(tr("cycle.dismiss")).size(16))
+            .on_press(Message::DismissCycleSummary)
+            .padding([12, 24])
+            .style(button::primary),
+    ]
+    .align_x(Center)
+    .spacing(4)
+    .padding(32)
+    .into()
+}
+
+fn view_close_confirm() -> Element<'static, Message> {
+    column![
+        text(tr("close.question")).size(18),
+        space::vertical().height(24),
+        row![
+            button(text(tr("common.cancel")).size(16))
+                .on_press(Message::CancelClose)
+                .padding([12, 24])
+                .style(button::secondary),
+            button(text(tr("common.close")).size(16))
+                .on_press(Message::ConfirmClose)
+                .padding([12, 24])
+                .style(button::danger),
+        ]
+        .spacing(12),
+    ]
+    .align_x(Center)
+    .spacing(0)
+    .padding(32)
+    .into()
+}
+
+fn view_reset_confirm(step: ResetStep) -> Element<'static, Message> {
+    let (question, confirm) = match step {
+        ResetStep::Ask => (tr("reset.ask"), tr("common.continue")),
+        ResetStep::Confirm => (tr("reset.confirm"), tr("reset.everything")),
+    };
+    column![
+        text(question).size(18).align_x(Center),
+        space::vertical().height(24),
+        row![
+            button(text(tr("common.cancel")).size(16))
+                .on_press(Message::CancelReset)
+                .padding([12, 24])
+                .style(button::secondary),
+            button(text(confirm).size(16))
+                .on_press(Message::ConfirmReset)
+                .padding([12, 24])
+                .style(button::danger),
+        ]
+        .spacing(12),
+    ]
+    .align_x(Center)
+    .spacing(0)
+    .padding(32)
+    .into()
+}
+
+fn view_db_error(app: &App) -> Element<'_, Message> {
+    let Some(error) = &app.db_error else {
+        return space::vertical().height(0).into();
+    };
+
+    let banner = row![
+        column![text(tr("db.not_saved")).size(13), text(error).size(11),].width(Fill),
+        button(text(tr("common.retry")).size(12))
+            .on_press(Message::RetryDb)
+            .padding([4, 10])
+            .style(button::secondary),
+    ]
+    .spacing(8)
+    .align_y(Center);
+
+    container(banner)
+        .padding(8)
+        .width(Fill)
+        .style(container::danger)
+        .into()
+}
+
+/// Just the ring and a play/pause control, for keeping in a corner of the screen.
+fn view_compact(app: &App) -> Element<'_, Message> {
+    let top = row![
+        mouse_area(space::horizontal().width(Fill).height(Fill)).on_press(Message::DragStart),
+        mouse_area(text("⊞").size(16)).on_press(Message::ToggleCompact),
+    ]
     .align_y(Center)
     .padding(Padding::from([0u16, 8]))
-    .height(40)
+    .height(24);
+
+    let (label, message) = if app.timer.is_counting() {
+        ("⏹", Message::StopStopwatch)
+    } else if app.timer.is_running() {
+        ("⏸", Message::PauseResume)
+    } else if app.timer.is_paused() {
+        ("▶", Message::PauseResume)
+    } else {
+        ("▶", Message::Start)
+    };
+    let control = button(text(label).size(12).align_x(Center))
+        .on_press(message)
+        .padding([2, 12])
+        .style(button::secondary);
+
+    let timer_canvas = Canvas::new(timer_widget(app)).width(124).height(124);
+
+    container(
+        column![top, timer_canvas, control]
+            .align_x(Center)
+            .spacing(2)
+            .width(Fill),
+    )
+    .width(Fill)
+    .height(Fill)
+    .into()
+}
+
+fn timer_widget(app: &App) -> TimerWidget {
+    let is_idle = matches!(app.timer.state, TimerState::Idle);
+    let remaining = if is_idle {
+        let focus_secs = app.settings.timer.focus_secs;
+        (focus_secs / 60, focus_secs % 60)
+    } else if app.timer.is_counting() {
+        let elapsed = app.timer.elapsed_secs();
+        (elapsed / 60, elapsed % 60)
+    } else {
+        app.timer.remaining_display()
+    };
+
+    TimerWidget {
+        progress: if is_smooth_ring(app) {
+            app.timer.progress_at(Instant::now())
+        } else {
+            app.timer.progress()
+        },
+        remaining,
+        session_label: match app.auto_start_in {
+            Some(secs) => {
+                let key = match app.timer.next_session_type() {
+                    SessionType::Focus => "timer.starting_focus",
+                    SessionType::ShortBreak | SessionType::LongBreak => "timer.starting_break",
+                };
+                trf(key, &[("n", secs.to_string())])
+            }
+            None if app.timer.is_counting() => tr("timer.stopwatch").to_string(),
+            None => app
+                .timer
+                .current_session_type()
+                .map(session_label)
+                .unwrap_or(tr("timer.ready"))
+                .to_string(),
+        },
+        session_type: app.timer.current_session_type(),
+        is_idle,
+        is_paused: app.timer.is_paused(),
+        is_finished: app.timer.is_finished(),
+        pulse: animation_wave(app),
+        fade_in: switch_progress(app),
+        continuous_arc: app.settings.reduce_motion || is_smooth_ring(app),
+    }
+}
+
+/// The session type as shown inside the ring.
+fn session_label(session_type: SessionType) -> &'static str {
+    tr(match session_type {
+        SessionType::Focus => "session.focus",
+        SessionType::ShortBreak => "session.short_break",
+        SessionType::LongBreak => "session.long_break",
+    })
+}
+
+fn view_timer(app: &App) -> Element<'_, Message> {
+    let plain = app.settings.hide_gamification;
+    let stage = xp::ferris_stage(app.profile.level);
+    let header: Element<Message> = if plain {
+        text("Ferris Focus").size(20).into()
+    } else {
+        // Ferris bobs while focusing; the padding pair keeps the row height fixed
+        let bob = 3.0 * animation_wave(app);
+        row![
+            container(text(stage.emoji()).size(20)).padding(Padding {
+                top: bob,
+                bottom: 3.0 - bob,
+                ..Padding::ZERO
+            }),
+            text("Ferris Focus").size(20),
+            space::horizontal(),
+            text(trf(
+                "timer.level_short",
+                &[("level", app.profile.level.to_string())]
+            ))
+            .size(18),
+        ]
+        .spacing(6)
+        .width(Fill)
+        .into()
+    };
+
+    let timer_canvas = Canvas::new(timer_widget(app)).width(220).height(220);
+
+    let timer_row = row![space::horizontal(), timer_canvas, space::horizontal()];
+
+    // blank rather than absent, so the layout doesn't jump on start and stop
+    let ends_at = app
+        .timer
+        .ends_at(Local::now().naive_local())
+        .map(|end| {
+            trf(
+                "timer.ends_at",
+                &[("time", format_clock(end.time(), app.settings.use_24h_clock))],
+            )
+        })
+        .unwrap_or_default();
+    let ends_at_row = row![
+        space::horizontal(),
+        text(ends_at).size(12),
+        space::horizontal()
+    ];
+    let intention = app
+        .session
+        .as_ref()
+        .and_then(|session| session.intention.as_deref())
+        .filter(|_| app.timer.is_running() || app.timer.is_paused())
+        .map(|intention| format!("🎯 {}", intention))
+        .unwrap_or_default();
+    let intention_row = row![
+        space::horizontal(),
+        text(intention).size(14),
+        space::horizontal()
+    ];
+
+    let controls = view_controls(app);
+
+    // durations are fixed once a session is under way
+    let between_sessions = matches!(
+        app.timer.state,
+        TimerState::Idle | TimerState::Finished { .. }
+    );
+    let preset_row = between_sessions.then(|| {
+        column![
+            space::vertical().height(8),
+            row![
+                text(tr("timer.preset")).size(14),
+                space::horizontal(),
+                view_preset_picker(app)
+            ]
+            .align_y(Center),
+        ]
+    });
+
+    let tag_input: Element<Message> = match (&app.intention_draft, &app.pending_note) {
+        (Some(draft), _) => row![
+            text_input(tr("timer.intention"), draft)
+                .on_input(Message::EditIntention)
+                .on_submit(Message::Start)
+                .size(14)
+                .padding([4, 8]),
+            button(text(tr("common.start")).size(12))
+                .on_press(Message::Start)
+                .padding([4, 8])
+                .style(button::primary),
+            button(text("✕").size(12))
+                .on_press(Message::CancelIntention)
+                .padding([4, 8])
+                .style(button::secondary),
+        ]
+        .spacing(4)
+        .align_y(Center)
+        .into(),
+        (None, Some((_, note))) => row![
+            text_input(tr("timer.note"), note)
+                .on_input(Message::EditNote)
+                .on_submit(Message::SaveNote)
+                .size(14)
+                .padding([4, 8]),
+            button(text(tr("common.save")).size(12))
+                .on_press(Message::SaveNote)
+                .padding([4, 8])
+                .style(button::primary),
+            button(text("✕").size(12))
+                .on_press(Message::DiscardNote)
+                .padding([4, 8])
+                .style(button::secondary),
+        ]
+        .spacing(4)
+        .align_y(Center)
+        .into(),
+        (None, None) => text_input(tr("timer.tag"), &app.active_tag)
+            .on_input(Message::SetTag)
+            .size(14)
+            .padding([4, 8])
+            .into(),
+    };
+
+    let streak_xp = row![
+        text(trf(
+            "timer.streak",
+            &[("n", app.profile.current_streak.to_string())]
+        ))
+        .size(14),
+        space::horizontal(),
+        text(format!("⭐ {} XP", app.profile.total_xp)).size(14),
+    ]
+    .width(Fill);
+
+    let level_progress = xp::level_progress(app.profile.total_xp);
+    let xp_bar = view_progress_bar(level_progress, 12.0);
+
+    let cadence = app.settings.timer.sessions_before_long_break.max(1);
+    let session_count = app.timer.cycle_position(cadence);
+    let until_long_break = match app.timer.focus_secs_until_long_break(&app.settings.timer) {
+        0 => tr("timer.long_break_next").to_string(),
+        secs => trf(
+            "timer.until_long_break",
+            &[("n", secs.div_ceil(60).to_string())],
+        ),
+    };
+    let focusing = app.timer.current_session_type() == Some(SessionType::Focus);
+    let dots = Canvas::new(CycleDots {
+        total: cadence,
+        done: session_count,
+        current: focusing,
+        pulse: animation_wave(app),
+    })
+    .width(cadence as f32 * CycleDots::SPACING)
+    .height(CycleDots::SPACING);
+    let session_info = row![dots, text(until_long_break).size(12)]
+        .spacing(8)
+        .align_y(Center);
+
+    let mut content = column![
+        header,
+        space::vertical().height(20),
+        timer_row,
+        ends_at_row,
+        intention_row,
+        space::vertical().height(8),
+        controls,
+        view_late_hint(app),
+        preset_row,
+        space::vertical().height(12),
+        tag_input,
+    ]
+    .spacing(0)
+    .width(Fill);
+
+    if !plain {
+        content = content
+            .push(space::vertical().height(12))
+            .push(streak_xp)
+            .push(space::vertical().height(6))
+            .push(xp_bar)
+            .push(space::vertical().height(2))
+            .push(view_level_xp(app));
+    }
+
+    content
+        .push(space::vertical().height(8))
+        .push(session_info)
+        .push(space::vertical().height(8))
+        .push(
+            Canvas::new(Sparkline {
+                counts: app.trend.clone(),
+            })
+            .width(Fill)
+            .height(24),
+        )
+        .into()
+}
+
+/// "350 / 500 XP to Level 9" under the XP bar.
+fn view_level_xp(app: &App) -> Element<'_, Message> {
+    let (earned, span) = xp::level_xp(app.profile.total_xp);
+    let next_level = xp::calculate_level(app.profile.total_xp) + 1;
+    row![
+        space::horizontal(),
+        text(trf(
+            "timer.level_xp",
+            &[
+                ("earned", earned.to_string()),
+                ("span", span.to_string()),
+                ("level", next_level.to_string()),
+            ]
+        ))
+        .size(11),
+    ]
+    .into()
+}
+
+/// A nudge towards bed for focus started in quiet hours; it never stops the session.
+fn view_late_hint(app: &App) -> Option<Element<'_, Message>> {
+    let focusing = app.timer.is_running() || app.timer.is_paused() || app.timer.is_counting();
+    (app.late_hint && focusing).then(|| {
+        column![
+            space::vertical().height(8),
+            row![
+                text(tr("timer.late_hint")).size(12),
+                space::horizontal(),
+                button(text("✕").size(12))
+                    .on_press(Message::DismissLateHint)
+                    .padding([2, 8])
+                    .style(button::text),
+            ]
+            .align_y(Center),
+        ]
+        .into()
+    })
+}
+
+/// Counts distractions during focus; the label shows the tally so far.
+fn view_distraction(app: &App) -> Option<Element<'_, Message>> {
+    (app.timer.current_session_type() == Some(SessionType::Focus)).then(|| {
+        button(text(format!("💭 {}", app.timer.interruptions)).size(14))
+            .on_press(Message::MarkDistraction)
+            .padding([8, 14])
+            .style(button::secondary)
+            .into()
+    })
+}
+
+/// Offered in a session's last minute, and once it's over while it can
+/// still be taken back.
+fn view_extend(app: &App) -> Option<Element<'_, Message>> {
+    let available = match app.timer.state {
+        TimerState::Running { remaining_secs, .. } => remaining_secs < 60,
+        TimerState::Finished { .. } => app.last_completed.is_some(),
+        _ => false,
+    };
+    available.then(|| {
+        button(text(format!("+{}", minutes(app.settings.extend_secs))).size(14))
+            .on_press(Message::Extend)
+            .padding([8, 14])
+            .style(button::secondary)
+            .into()
+    })
+}
+
+fn format_clock(time: NaiveTime, use_24h: bool) -> String {
+    let format = if use_24h { "%H:%M" } else { "%-I:%M %p" };
+    time.format(format).to_string()
+}
+
+fn view_controls(app: &App) -> Element<'_, Message> {
+    let is_idle = matches!(app.timer.state, TimerState::Idle);
+    let is_finished = app.timer.is_finished();
+
+    if is_idle {
+        row![
+            space::horizontal(),
+            button(text(tr("controls.start_focus")).size(16).align_x(Center))
+                .on_press(Message::Start)
+                .padding([10, 28])
+                .style(button::primary),
+            button(text("⏱").size(16).align_x(Center))
+                .on_press(Message::StartStopwatch)
+                .padding([10, 14])
+                .style(button::secondary),
+            space::horizontal(),
+        ]
+        .spacing(8)
+        .width(Fill)
+        .into()
+    } else if app.timer.is_counting() {
+        row![
+            space::horizontal(),
+            button(text(tr("controls.stop")).size(16).align_x(Center))
+                .on_press(Message::StopStopwatch)
+                .padding([10, 28])
+                .style(button::primary),
+            view_distraction(app),
+            space::horizontal(),
+        ]
+        .spacing(8)
+        .width(Fill)
+        .into()
+    } else if is_finished {
+        let next_label = match app.timer.next_session_type() {
+            SessionType::Focus => tr("controls.start_focus"),
+            SessionType::ShortBreak => tr("controls.short_break"),
+            SessionType::LongBreak => tr("controls.long_break"),
+        };
+        row![
+            space::horizontal(),
+            button(text(next_label).size(16).align_x(Center))
+                .on_press(Message::Start)
+                .padding([10, 28])
+                .style(button::primary),
+            (app.timer.next_session_type() != SessionType::Focus).then(|| {
+                button(text("⏭").size(16).align_x(Center))
+                    .on_press(Message::SkipBreak)
+                    .padding([10, 14])
+                    .style(button::secondary)
+            }),
+            view_extend(app),
+            space::horizontal(),
+        ]
+        .spacing(8)
+        .width(Fill)
+        .into()
+    } else {
+        let pause_label = if app.timer.is_paused() {
+            tr("controls.resume")
+        } else {
+            tr("controls.pause")
+        };
+        row![
+            space::horizontal(),
+            button(text(pause_label).size(14).align_x(Center))
+                .on_press(Message::PauseResume)
+                .padding([8, 20])
+                .style(button::primary),
+            button(text(tr("controls.skip")).size(14).align_x(Center))
+                .on_press(Message::Skip)
+                .padding([8, 20])
+                .style(button::secondary),
+            view_distraction(app),
+            view_extend(app),
+            space::horizontal(),
+        ]
+        .spacing(12)
+        .width(Fill)
+        .into()
+    }
+}
+
+fn view_progress_bar(progress: f32, height: f32) -> Element<'static, Message> {
+    Canvas::new(ProgressBarWidget {
+        progress: progress.clamp(0.0, 1.0),
+    })
+    .width(Fill)
+    .height(height)
+    .into()
+}
+
+fn view_stats(app: &App) -> Element<'_, Message> {
+    let stage = xp::ferris_stage(app.profile.level);
+
+    let title = text(tr("stats.title")).size(22);
+
+    let ferris_info = row![
+        text(stage.emoji()).size(48),
+        column![
+            text(stage.label()).size(18),
+            text(trf(
+                "common.level",
+                &[("level", app.profile.level.to_string())]
+            ))
+            .size(14),
+        ]
+        .spacing(4),
+    ]
+    .spacing(16)
+    .align_y(Center);
+
+    let today_label = text(trf(
+        "stats.today",
+        &[("n", app.stats.today_sessions.to_string())],
+    ))
+    .size(14);
+    let abandoned_label = text(trf(
+        "stats.abandoned",
+        &[("n", app.stats.today_abandoned.to_string())],
+    ))
+    .size(14);
+    let week_label = text(trf(
+        "stats.week",
+        &[("n", app.stats.week_sessions.to_string())],
+    ))
+    .size(14);
+    let best_day_label = text(match &app.stats.best_day {
+        Some((day, count)) => trf(
+            "stats.best_day",
+            &[("day", day.to_string()), ("n", count.to_string())],
+        ),
+        None => tr("stats.best_day_none").to_string(),
+    })
+    .size(14);
+
+    let total_hours = app.stats.total_focus_secs / 3600;
+    let total_mins = (app.stats.total_focus_secs % 3600) / 60;
+    let total_label = text(trf(
+        "stats.all_time",
+        &[
+            ("n", app.stats.total_sessions.to_string()),
+            ("h", total_hours.to_string()),
+            ("m", total_mins.to_string()),
+        ],
+    ))
+    .size(14);
+    let partial_secs = app.stats.total_focus_secs_with_partial;
+    let partial_label = text(trf(
+        "stats.partial",
+        &[
+            ("h", (partial_secs / 3600).to_string()),
+            ("m", ((partial_secs % 3600) / 60).to_string()),
+        ],
+    ))
+    .size(14);
+
+    let avg_interruptions = match app.stats.total_sessions {
+        0 => 0.0,
+        n => app.stats.total_interruptions as f32 / n as f32,
+    };
+    let interruptions_label = text(trf(
+        "stats.interruptions",
+        &[("n", format!("{:.1}", avg_interruptions))],
+    ))
+    .size(14);
+
+    let (short_breaks, long_breaks) = app.stats.break_counts;
+    let breaks_label = text(trf(
+        "stats.breaks",
+        &[
+            ("n", (short_breaks + long_breaks).to_string()),
+            ("short", short_breaks.to_string()),
+            ("long", long_breaks.to_string()),
+        ],
+    ))
+    .size(14);
+
+    let streak_label = text(trf(
+        "stats.streak",
+        &[
+            ("n", app.profile.current_streak.to_string()),
+            ("best", app.profile.longest_streak.to_string()),
+        ],
+    ))
+    .size(14);
+    let freeze_label = text(trf(
+        "stats.freezes",
+        &[
+            ("n", app.profile.freezes_available.to_string()),
+            ("levels", xp::LEVELS_PER_FREEZE.to_string()),
+        ],
+    ))
+    .size(14);
+
+    let xp_label = text(trf(
+        "stats.total_xp",
+        &[("n", app.profile.total_xp.to_string())],
+    ))
+    .size(14);
+
+    let range_button = |label, range| {
+        let style = if app.heatmap_range == range {
+            button::primary
+        } else {
+            button::secondary
+        };
+        button(text(label).size(12))
+            .on_press(Message::SetHeatmapRange(range))
+            .padding([2, 10])
+            .style(style)
+    };
+
+    let (heatmap_label, heatmap) = match app.heatmap_range {
+        HeatmapRange::Week => (tr("stats.this_week"), view_weekly_heatmap(app)),
+        HeatmapRange::Month => (tr("stats.last_weeks"), view_monthly_heatmap(app)),
+    };
+
+    let heatmap_title = row![
+        text(heatmap_label).size(16),
+        space::horizontal(),
+        range_button(tr("stats.range_week"), HeatmapRange::Week),
+        range_button(tr("stats.range_month"), HeatmapRange::Month),
+    ]
+    .spacing(4)
+    .align_y(Center)
+    .width(Fill);
+
+    let plain = app.settings.hide_gamification;
+    let ferris_info: Element<Message> = if plain {
+        space::vertical().height(0).into()
+    } else {
+        column![
+            space::vertical().height(16),
+            ferris_info,
+            space::vertical().height(16),
+            rule::horizontal(1),
+        ]
+        .into()
+    };
+    let progress_labels: Element<Message> = if plain {
+        space::vertical().height(0).into()
+    } else {
+        column![
+            space::vertical().height(8),
+            streak_label,
+            freeze_label,
+            xp_label,
+            space::vertical().height(16),
+            rule::horizontal(1),
+            space::vertical().height(12),
+            text(tr("stats.achievements")).size(16),
+            space::vertical().height(8),
+            view_achievements(app),
+            space::vertical().height(16),
+            rule::horizontal(1),
+            space::vertical().height(12),
+            text(tr("stats.xp_per_day")).size(16),
+            space::vertical().height(8),
+            view_xp_chart(app),
+            space::vertical().height(16),
+            text(tr("stats.active_days")).size(16),
+            space::vertical().height(8),
+            view_streak_history(app),
+        ]
+        .into()
+    };
+
+    let content = column![
+        title,
+        ferris_info,
+        space::vertical().height(12),
+        today_label,
+        view_daily_goal(app),
+        view_weekly_goal(app),
+        abandoned_label,
+        week_label,
+        total_label,
+        partial_label,
+        breaks_label,
+        interruptions_label,
+        best_day_label,
+        progress_labels,
+        space::vertical().height(16),
+        rule::horizontal(1),
+        space::vertical().height(12),
+        heatmap_title,
+        space::vertical().height(8),
+        heatmap,
+        space::vertical().height(16),
+        rule::horizontal(1),
+        space::vertical().height(12),
+        text(tr("stats.by_hour")).size(16),
+        space::vertical().height(8),
+        view_hourly_chart(app),
+        space::vertical().height(16),
+        text(tr("stats.by_weekday")).size(16),
+        space::vertical().height(8),
+        view_weekday_chart(app),
+        space::vertical().height(16),
+        rule::horizontal(1),
+        space::vertical().height(12),
+        text(tr("stats.by_tag")).size(16),
+        space::vertical().height(8),
+        view_tag_breakdown(app),
+        space::vertical().height(16),
+        rule::horizontal(1),
+        space::vertical().height(12),
+        text(tr("stats.recent_notes")).size(16),
+        space::vertical().height(8),
+        view_recent_notes(app),
+        space::vertical().height(8),
+        row![
+            button(text(tr("stats.export_notes")).size(14))
+                .on_press(Message::ExportNotes)
+                .padding([6, 16])
+                .style(button::secondary),
+            button(text(tr("stats.all_sessions")).size(14))
+                .on_press(Message::SwitchView(View::History))
+                .padding([6, 16])
+                .style(button::secondary),
+        ]
+        .spacing(8),
+        space::vertical().height(16),
+        rule::horizontal(1),
+        space::vertical().height(12),
+        view_undo(app),
+        row![
+            button(text(tr("stats.export_csv")).size(14))
+                .on_press(Message::ExportCsv)
+                .padding([6, 16])
+                .style(button::secondary),
+            button(text(tr("stats.import_csv")).size(14))
+                .on_press(Message::ImportCsv)
+                .padding([6, 16])
+                .style(button::secondary),
+            button(text(tr("stats.share")).size(14))
+                .on_press(Message::Share)
+                .padding([6, 16])
+                .style(button::secondary),
+            button(text(tr("stats.copy")).size(14))
+                .on_press(Message::CopyStats)
+                .padding([6, 16])
+                .style(button::secondary),
+        ]
+        .spacing(8),
+        text(app.data_status.as_deref().unwrap_or_default()).size(12),
+    ]
+    .spacing(2)
+    .width(Fill);
+
+    scrollable(content).height(Fill).into()
+}
+
+/// Today's sessions against the daily goal, when one is set.
+fn view_daily_goal(app: &App) -> Option<Element<'_, Message>> {
+    let goal = app.settings.daily_goal;
+    (goal > 0).then(|| {
+        let done = app.stats.today_sessions;
+        column![
+            text(trf(
+                "stats.daily_goal",
+                &[("n", done.to_string()), ("goal", goal.to_string())]
+            ))
+            .size(14),
+            view_progress_bar(done as f32 / goal as f32, 8.0),
+        ]
+        .spacing(4)
+        .into()
+    })
+}
+
+fn view_weekly_goal(app: &App) -> Option<Element<'_, Message>> {
+    let goal = app.settings.weekly_goal;
+    (goal > 0).then(|| {
+        let done = app.stats.week_sessions;
+        column![
+            text(trf(
+                "stats.weekly_goal",
+                &[("n", done.to_string()), ("goal", goal.to_string())]
+            ))
+            .size(14),
+            view_progress_bar(done as f32 / goal as f32, 8.0),
+        ]
+        .spacing(4)
+        .into()
+    })
+}
+
+fn view_achievements(app: &App) -> Element<'_, Message> {
+    let mut list = column![].spacing(4);
+    for achievement in Achievement::ALL {
+        let unlocked = app
+            .stats
+            .achievements
+            .iter()
+            .find(|(a, _)| *a == achievement);
+        let line = match unlocked {
+            // date part of the timestamp
+            Some((_, at)) => text(format!(
+                "{} {} — {}",
+                achievement.emoji(),
+                achievement.label(),
+                at.get(..10).unwrap_or(at)
+            ))
+            .size(13),
+            None => text(format!(
+                "🔒 {} — {}",
+                achievement.label(),
+                achievement.description()
+            ))
+            .size(13)
+            .style(|theme: &Theme| text::Style {
+                color: Some(Color {
+                    a: 0.5,
+                    ..theme.palette().text
+                }),
+            }),
+        };
+        list = list.push(line);
+    }
+    list.into()
+}
+
+fn view_hourly_chart(app: &App) -> Element<'_, Message> {
+    let axis = row![
+        text("0h").size(11),
+        space::horizontal(),
+        text("6h").size(11),
+        space::horizontal(),
+        text("12h").size(11),
+        space::horizontal(),
+        text("18h").size(11),
+        space::horizontal(),
+        text("23h").size(11),
+    ]
+    .width(Fill);
+
+    column![
+        Canvas::new(BarChart {
+            values: app.stats.hourly_data.to_vec(),
+        })
+        .width(Fill)
+        .height(60),
+        axis,
+    ]
+    .spacing(2)
     .into()
 }
 
-fn view(app: &App) -> Element<'_, Message> {
-    let titlebar = view_titlebar(app);
+fn view_weekday_chart(app: &App) -> Element<'_, Message> {
+    let week_start = app.settings.week_starts_on;
+    let labels = week_start
+        .day_labels()
+        .map(|label| text(label).size(11).width(Fill).align_x(Center).into());
 
-    let content: Element<Message> = match app.current_view {
-        View::Timer => view_timer(app),
-        View::Stats => view_stats(app),
-    };
+    column![
+        Canvas::new(BarChart {
+            values: week_start.in_week_order(app.stats.weekday_data).to_vec(),
+        })
+        .width(Fill)
+        .height(60),
+        row(labels).width(Fill),
+    ]
+    .spacing(2)
+    .into()
+}
 
-    let nav = view_nav(app);
+fn view_xp_chart(app: &App) -> Element<'_, Message> {
+    let axis = row![
+        text(trf(
+            "common.days_ago",
+            &[("n", (engine::XP_CHART_DAYS - 1).to_string())]
+        ))
+        .size(11),
+        space::horizontal(),
+        text(format!("{} XP", app.stats.daily_xp.iter().sum::<u32>())).size(11),
+        space::horizontal(),
+        text(tr("common.today")).size(11),
+    ]
+    .width(Fill);
+
+    column![
+        Canvas::new(BarChart {
+            values: app.stats.daily_xp.clone(),
+        })
+        .width(Fill)
+        .height(60),
+        axis,
+    ]
+    .spacing(2)
+    .into()
+}
+
+fn view_streak_history(app: &App) -> Element<'_, Message> {
+    let active_days = app
+        .stats
+        .streak_history
+        .iter()
+        .filter(|(_, active)| *active)
+        .count();
+    let axis = row![
+        text(trf(
+            "common.days_ago",
+            &[("n", (engine::STREAK_HISTORY_DAYS - 1).to_string())]
+        ))
+        .size(11),
+        space::horizontal(),
+        text(trf("stats.active", &[("n", active_days.to_string())])).size(11),
+        space::horizontal(),
+        text(tr("common.today")).size(11),
+    ]
+    .width(Fill);
 
-    let layout = column![titlebar, content, space::vertical().height(8), nav,]
-        .padding(Padding::from([0u16, 24]))
-        .spacing(0)
+    column![
+        Canvas::new(StreakStrip {
+            days: app.stats.streak_history.iter().map(|(_, a)| *a).collect(),
+        })
         .width(Fill)
-        .height(Fill);
+        .height(16),
+        axis,
+    ]
+    .spacing(2)
+    .into()
+}
 
-    let main_view = container(layout).width(Fill).height(Fill);
+fn view_undo(app: &App) -> Element<'_, Message> {
+    let recent = app
+        .last_completed
+        .is_some_and(|last| last.at.elapsed() <= UNDO_WINDOW);
+    if !recent {
+        return space::vertical().height(0).into();
+    }
+    button(text(tr("stats.undo")).size(14))
+        .on_press(Message::UndoLastSession)
+        .padding([6, 16])
+        .style(button::secondary)
+        .into()
+}
 
-    if let Some(level) = app.level_up {
-        let stage = xp::ferris_stage(level);
-        let prev_stage = xp::ferris_stage(level.saturating_sub(1));
+fn view_tag_breakdown(app: &App) -> Element<'_, Message> {
+    if app.stats.tag_stats.is_empty() {
+        return text(tr("stats.no_focus")).size(12).into();
+    }
 
-        let modal = column![
-            text("🎉 Level Up! 🎉").size(28),
-            space::vertical().height(20),
-            row![
-                text(format!("{}", prev_stage.emoji())).size(48),
-                text(" → ").size(32),
-                text(format!("{}", stage.emoji())).size(48),
-            ],
-            space::vertical().height(12),
-            text(format!("Level {}", level)).size(24),
-            space::vertical().height(8),
-            text(format!("{}", stage.label())).size(18),
-            space::vertical().height(24),
-            button(text("Continue").size(16))
-                .on_press(Message::DismissLevelUp)
-                .padding([12, 24])
-                .style(button::primary),
+    let rows = app.stats.tag_stats.iter().map(|(tag, secs)| {
+        row![
+            text(tag.as_str()).size(14),
+            space::horizontal(),
+            text(format!("{}h {}m", secs / 3600, (secs % 3600) / 60)).size(14),
         ]
-        .align_x(Center)
-        .spacing(0)
-        .padding(32);
+        .width(Fill)
+        .into()
+    });
+    column(rows).spacing(2).into()
+}
 
-        let modal_container = container(modal)
-            .width(iced::Length::Fill)
-            .center_x(iced::Length::Fill)
-            .center_y(iced::Length::Fill);
+fn view_history(app: &App) -> Element<'_, Message> {
+    let title = row![
+        button(text("←").size(16))
+            .on_press(Message::SwitchView(View::Stats))
+            .padding([2, 10])
+            .style(button::text),
+        text(tr("history.title")).size(22),
+    ]
+    .spacing(4)
+    .align_y(Center);
 
-        column![main_view, modal_container].into()
-    } else {
-        main_view.into()
+    let filters = row![
+        pick_list(
+            TypeFilter::ALL,
+            Some(app.history_type),
+            Message::SetHistoryType
+        )
+        .text_size(13),
+        pick_list(
+            HistoryRange::ALL,
+            Some(app.history_range),
+            Message::SetHistoryRange
+        )
+        .text_size(13),
+    ]
+    .spacing(8);
+
+    let mut list = column![].spacing(2);
+    if app.history.is_empty() {
+        list = list.push(text(tr("history.empty")).size(12));
+    }
+    for session in &app.history {
+        list = list.push(view_history_entry(app, session));
+    }
+    if app.history_more {
+        list = list.push(
+            button(text(tr("history.load_more")).size(13))
+                .on_press(Message::LoadMoreHistory)
+                .padding([6, 16])
+                .style(button::secondary),
+        );
     }
-}
 
-fn view_timer(app: &App) -> Element<'_, Message> {
-    let stage = xp::ferris_stage(app.profile.level);
-    let header = row![
-        text(format!("{} Ferris Focus", stage.emoji())).size(20),
-        space::horizontal(),
-        text(format!("Lv. {}", app.profile.level)).size(18),
+    let content = column![
+        title,
+        space::vertical().height(8),
+        filters,
+        space::vertical().height(12),
+        list,
     ]
     .width(Fill);
 
-    let timer_canvas = Canvas::new(TimerWidget {
-        progress: app.timer.progress(),
-        remaining: app.timer.remaining_display(),
-        session_label: app
-            .timer
-            .current_session_type()
-            .map(|t| t.label())
-            .unwrap_or("READY"),
-        is_idle: matches!(app.timer.state, TimerState::Idle),
-        is_finished: app.timer.is_finished(),
-    })
-    .width(220)
-    .height(220);
-
-    let timer_row = row![space::horizontal(), timer_canvas, space::horizontal()];
+    scrollable(content).height(Fill).into()
+}
 
-    let controls = view_controls(app);
+fn view_history_entry<'a>(app: &App, session: &'a Session) -> Element<'a, Message> {
+    let when = session
+        .started_at
+        .get(..16)
+        .unwrap_or(&session.started_at)
+        .replace('T', " ");
+    let kind = TypeFilter(Some(session.session_type)).to_string();
+    let kind = if session.completed {
+        kind
+    } else {
+        trf("history.abandoned", &[("kind", kind)])
+    };
+    let duration = format!(
+        "{}:{:02}",
+        session.duration_secs / 60,
+        session.duration_secs % 60
+    );
 
-    let streak_xp = row![
-        text(format!("🔥 Streak: {} days", app.profile.current_streak)).size(14),
+    let summary = row![
+        column![text(when).size(11), text(kind).size(13),].spacing(1),
         space::horizontal(),
-        text(format!("⭐ {} XP", app.profile.total_xp)).size(14),
+        text(session.tag.as_deref().unwrap_or_default()).size(12),
+        text(duration).size(13),
     ]
-    .width(Fill);
+    .spacing(8)
+    .align_y(Center);
 
-    let level_progress = xp::level_progress(app.profile.total_xp);
-    let xp_bar = view_progress_bar(level_progress, 12.0);
+    let mut entry = column![button(summary)
+        .on_press_maybe(session.id.map(Message::ToggleHistoryEntry))
+        .padding([4, 6])
+        .width(Fill)
+        .style(button::text)];
 
-    let session_count = app.timer.focus_sessions_completed % SESSIONS_BEFORE_LONG_BREAK;
-    let session_info = text(format!(
-        "Session: {}/{} until long break",
-        session_count, SESSIONS_BEFORE_LONG_BREAK
-    ))
-    .size(12);
+    if session.id.is_some() && session.id == app.history_open {
+        let mut details = column![].spacing(2).padding([0, 12]);
+        if let Some(intention) = &session.intention {
+            details = details.push(text(format!("🎯 {}", intention)).size(12));
+        }
+        if let Some(note) = &session.note {
+            details = details.push(text(format!("📝 {}", note)).size(12));
+        }
+        if session.intention.is_none() && session.note.is_none() {
+            details = details.push(text(tr("history.no_details")).size(12));
+        }
+        entry = entry.push(details);
+    }
+    entry.into()
+}
+
+fn view_recent_notes(app: &App) -> Element<'_, Message> {
+    if app.stats.recent_notes.is_empty() {
+        return text(tr("stats.no_notes")).size(12).into();
+    }
+
+    let notes = app.stats.recent_notes.iter().map(|(started_at, note)| {
+        let when = started_at.get(..16).unwrap_or(started_at).replace('T', " ");
+        column![text(when).size(11), text(note.as_str()).size(14)]
+            .spacing(1)
+            .into()
+    });
+    column(notes).spacing(6).into()
+}
+
+fn view_weekly_heatmap(app: &App) -> Element<'_, Message> {
+    let today = today(app);
+    let week_start = app.settings.week_starts_on;
+    let start = engine::current_week_start(today, week_start);
+
+    let boxes: Vec<Element<Message>> = week_start
+        .day_labels()
+        .into_iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let date = start + chrono::Duration::days(i as i64);
+            // days still to come stay blank
+            let cell: Element<Message> = if date > today {
+                space().width(32).height(32).into()
+            } else {
+                let count = sessions_on(&app.stats.weekly_data, date);
+                Canvas::new(HeatmapCell {
+                    count,
+                    palette: app.settings.heatmap_palette,
+                    goal: app.settings.daily_goal,
+                })
+                .width(32)
+                .height(32)
+                .into()
+            };
+
+            column![cell, text(label).size(10)]
+                .spacing(4)
+                .align_x(Center)
+                .into()
+        })
+        .collect();
+
+    let mut heatmap_row = row![].spacing(4);
+    for b in boxes {
+        heatmap_row = heatmap_row.push(b);
+    }
+
+    heatmap_row.into()
+}
+
+fn view_monthly_heatmap(app: &App) -> Element<'_, Message> {
+    let today = today(app);
+    let week_start = app.settings.week_starts_on;
+    let start = engine::monthly_heatmap_start(today, week_start);
+    let cell_size = 24;
+
+    let mut labels = column![].spacing(4);
+    for label in week_start.day_labels() {
+        labels = labels.push(text(label).size(10).height(cell_size).align_y(Center));
+    }
+
+    let mut grid = row![labels].spacing(4);
+    for week in 0..engine::MONTHLY_HEATMAP_WEEKS {
+        let mut week_column = column![].spacing(4);
+        for day in 0..7 {
+            let date = start + chrono::Duration::days(week * 7 + day);
+            let cell: Element<Message> = if date > today {
+                space().width(cell_size).height(cell_size).into()
+            } else {
+                let count = sessions_on(&app.stats.monthly_data, date);
+                Canvas::new(HeatmapCell {
+                    count,
+                    palette: app.settings.heatmap_palette,
+                    goal: app.settings.daily_goal,
+                })
+                .width(cell_size)
+                .height(cell_size)
+                .into()
+            };
+            week_column = week_column.push(cell);
+        }
+        grid = grid.push(week_column);
+    }
+
+    let month_label = if start.month() == today.month() {
+        today.format("%B %Y").to_string()
+    } else {
+        format!("{} – {}", start.format("%b"), today.format("%b %Y"))
+    };
+
+    column![text(month_label).size(12), grid].spacing(6).into()
+}
+
+fn sessions_on(data: &[(String, u32)], date: NaiveDate) -> u32 {
+    let date_str = date.format("%Y-%m-%d").to_string();
+    data.iter()
+        .find(|(d, _)| d == &date_str)
+        .map(|(_, c)| *c)
+        .unwrap_or(0)
+}
+
+fn view_preset_picker(app: &App) -> Element<'_, Message> {
+    let names: Vec<String> = app
+        .settings
+        .presets
+        .iter()
+        .map(|p| p.name.clone())
+        .collect();
+    pick_list(
+        names,
+        app.settings.active_preset.clone(),
+        Message::SelectPreset,
+    )
+    .placeholder(tr("preset.custom"))
+    .text_size(14)
+    .into()
+}
 
+fn view_preset_editor(app: &App) -> Element<'_, Message> {
+    let delete = app.settings.active_preset.as_ref().map(|_| {
+        button(text(tr("common.delete")).size(12))
+            .on_press(Message::DeletePreset)
+            .padding([4, 8])
+            .style(button::secondary)
+    });
     column![
-        header,
-        space::vertical().height(20),
-        timer_row,
-        space::vertical().height(20),
-        controls,
-        space::vertical().height(20),
-        streak_xp,
-        space::vertical().height(6),
-        xp_bar,
-        space::vertical().height(8),
-        session_info,
+        row![
+            text(tr("timer.preset")).size(14),
+            space::horizontal(),
+            view_preset_picker(app),
+            delete,
+        ]
+        .spacing(4)
+        .align_y(Center),
+        row![
+            text_input(tr("preset.save_as"), &app.preset_name)
+                .on_input(Message::EditPresetName)
+                .on_submit(Message::SavePreset)
+                .size(14)
+                .padding([4, 8]),
+            button(text(tr("common.save")).size(12))
+                .on_press(Message::SavePreset)
+                .padding([4, 8])
+                .style(button::secondary),
+        ]
+        .spacing(4)
+        .align_y(Center),
     ]
-    .spacing(0)
-    .width(Fill)
+    .spacing(4)
     .into()
 }
 
-fn view_controls(app: &App) -> Element<'_, Message> {
-    let is_idle = matches!(app.timer.state, TimerState::Idle);
-    let is_finished = app.timer.is_finished();
+fn view_settings(app: &App) -> Element<'_, Message> {
+    let title = text(tr("settings.title")).size(22);
 
-    if is_idle {
+    let config = &app.settings.timer;
+
+    let content = column![
+        title,
+        space::vertical().height(16),
+        text(tr("settings.timer")).size(16),
+        space::vertical().height(8),
+        view_stepper(
+            tr("settings.focus"),
+            minutes(config.focus_secs),
+            ConfigField::FocusMinutes,
+        ),
+        view_stepper(
+            tr("settings.short_break"),
+            minutes(config.short_break_secs),
+            ConfigField::ShortBreakMinutes,
+        ),
+        view_stepper(
+            tr("settings.long_break"),
+            minutes(config.long_break_secs),
+            ConfigField::LongBreakMinutes,
+        ),
+        view_stepper(
+            tr("settings.sessions_per_cycle"),
+            config.sessions_before_long_break.to_string(),
+            ConfigField::SessionsBeforeLongBreak,
+        ),
+        view_stepper(
+            tr("settings.daily_goal"),
+            match app.settings.daily_goal {
+                0 => tr("settings.off").to_string(),
+                n => n.to_string(),
+            },
+            ConfigField::DailyGoal,
+        ),
+        view_stepper(
+            tr("settings.weekly_goal"),
+            match app.settings.weekly_goal {
+                0 => tr("settings.off").to_string(),
+                n => n.to_string(),
+            },
+            ConfigField::WeeklyGoal,
+        ),
+        view_preset_editor(app),
+        space::vertical().height(8),
+        text(tr("settings.next_session")).size(12),
+        space::vertical().height(16),
+        text(tr("settings.flow")).size(16),
+        space::vertical().height(8),
+        toggler(app.settings.auto_start)
+            .label(tr("settings.auto_start"))
+            .text_size(14)
+            .on_toggle(Message::ToggleAutoStart),
+        toggler(app.settings.reset_cycle_daily)
+            .label(tr("settings.reset_cycle_daily"))
+            .text_size(14)
+            .on_toggle(Message::ToggleResetCycleDaily),
         row![
+            text(tr("settings.long_break")).size(14),
             space::horizontal(),
-            button(text("▶  Start Focus").size(16).align_x(Center))
-                .on_press(Message::Start)
-                .padding([10, 28])
-                .style(button::primary),
+            pick_list(
+                CycleMode::ALL,
+                Some(app.settings.cycle_mode),
+                Message::SetCycleMode
+            )
+            .text_size(14),
+        ]
+        .align_y(Center),
+        toggler(app.settings.prompt_for_notes)
+            .label(tr("settings.prompt_note"))
+            .text_size(14)
+            .on_toggle(Message::TogglePromptForNotes),
+        toggler(app.settings.prompt_for_intention)
+            .label(tr("settings.prompt_intention"))
+            .text_size(14)
+            .on_toggle(Message::TogglePromptForIntention),
+        view_stepper(
+            tr("settings.auto_start_delay"),
+            format!("{} s", app.settings.auto_start_delay_secs),
+            ConfigField::AutoStartDelaySecs,
+        ),
+        view_stepper(
+            tr("settings.auto_abandon"),
+            match app.settings.auto_abandon_after_secs {
+                0 => tr("settings.never").to_string(),
+                secs => minutes(secs),
+            },
+            ConfigField::AutoAbandonMinutes,
+        ),
+        toggler(app.settings.idle_pause)
+            .label(tr("settings.idle_pause"))
+            .text_size(14)
+            .on_toggle(Message::ToggleIdlePause),
+        view_stepper(
+            tr("settings.idle_after"),
+            minutes(app.settings.idle_threshold_secs),
+            ConfigField::IdleMinutes,
+        ),
+        (app.settings.idle_pause && app.idle_unsupported)
+            .then(|| text(tr("settings.idle_unsupported")).size(12)),
+        view_stepper(
+            tr("settings.extend_by"),
+            minutes(app.settings.extend_secs),
+            ConfigField::ExtendMinutes,
+        ),
+        view_cycle_reset(app),
+        row![
+            text(tr("settings.on_suspend")).size(14),
             space::horizontal(),
+            pick_list(
+                SuspendAction::ALL,
+                Some(app.settings.on_suspend),
+                Message::SetSuspendAction
+            )
+            .text_size(14),
         ]
-        .width(Fill)
-        .into()
-    } else if is_finished {
-        let next_label = match app.timer.next_session_type() {
-            SessionType::Focus => "▶  Start Focus",
-            SessionType::ShortBreak => "☕  Short Break",
-            SessionType::LongBreak => "🎉  Long Break",
-        };
+        .align_y(Center),
+        space::vertical().height(16),
+        text(tr("settings.appearance")).size(16),
+        space::vertical().height(8),
         row![
+            text(tr("settings.language")).size(14),
             space::horizontal(),
-            button(text(next_label).size(16).align_x(Center))
-                .on_press(Message::Start)
-                .padding([10, 28])
-                .style(button::primary),
+            pick_list(
+                Language::ALL,
+                Some(app.settings.language),
+                Message::SetLanguage
+            )
+            .text_size(14),
+        ]
+        .align_y(Center),
+        view_theme_settings(app),
+        toggler(app.settings.use_24h_clock)
+            .label(tr("settings.24h_clock"))
+            .text_size(14)
+            .on_toggle(Message::Toggle24hClock),
+        toggler(app.settings.reduce_motion)
+            .label(tr("settings.reduce_motion"))
+            .text_size(14)
+            .on_toggle(Message::ToggleReduceMotion),
+        toggler(app.settings.smooth_ring && !app.settings.reduce_motion)
+            .label(tr("settings.smooth_ring"))
+            .text_size(14)
+            .on_toggle_maybe((!app.settings.reduce_motion).then_some(Message::ToggleSmoothRing)),
+        row![
+            text(tr("settings.week_start")).size(14),
             space::horizontal(),
+            pick_list(
+                WeekStart::ALL,
+                Some(app.settings.week_starts_on),
+                Message::SetWeekStart
+            )
+            .text_size(14),
         ]
-        .width(Fill)
-        .into()
-    } else {
-        let pause_label = if app.timer.is_paused() {
-            "▶  Resume"
-        } else {
-            "⏸  Pause"
-        };
+        .align_y(Center),
+        view_stepper(
+            tr("settings.day_boundary"),
+            format!("{:02}:00", app.settings.day_boundary_hour),
+            ConfigField::DayBoundaryHour,
+        ),
         row![
+            text(tr("settings.heatmap_palette")).size(14),
             space::horizontal(),
-            button(text(pause_label).size(14).align_x(Center))
-                .on_press(Message::PauseResume)
-                .padding([8, 20])
-                .style(button::primary),
-            button(text("⏭  Skip").size(14).align_x(Center))
-                .on_press(Message::Skip)
-                .padding([8, 20])
-                .style(button::secondary),
+            pick_list(
+                HeatmapPalette::ALL,
+                Some(app.settings.heatmap_palette),
+                Message::SetHeatmapPalette
+            )
+            .text_size(14),
+        ]
+        .align_y(Center),
+        view_focus_only(app),
+        space::vertical().height(16),
+        text(tr("settings.rewards")).size(16),
+        space::vertical().height(8),
+        view_stepper(
+            tr("settings.base_xp"),
+            app.settings.xp.base_xp.to_string(),
+            ConfigField::BaseXp,
+        ),
+        view_stepper(
+            tr("settings.streak_bonus"),
+            app.settings.xp.streak_bonus_per_day.to_string(),
+            ConfigField::StreakBonusPerDay,
+        ),
+        view_stepper(
+            tr("settings.max_streak_bonus"),
+            app.settings.xp.max_streak_bonus.to_string(),
+            ConfigField::MaxStreakBonus,
+        ),
+        toggler(app.settings.stage_changes_only)
+            .label(tr("settings.stage_changes_only"))
+            .text_size(14)
+            .on_toggle(Message::ToggleStageChangesOnly),
+        space::vertical().height(16),
+        text(tr("settings.alerts")).size(16),
+        space::vertical().height(8),
+        toggler(app.settings.sound_enabled)
+            .label(tr("settings.sound"))
+            .text_size(14)
+            .on_toggle(Message::ToggleSound),
+        view_quiet_hours(app),
+        view_streak_reminder(app),
+        view_message_templates(app),
+        row![
+            text(tr("settings.ambience")).size(14),
             space::horizontal(),
+            pick_list(
+                AmbientSound::ALL,
+                Some(app.settings.ambient_sound),
+                Message::SetAmbientSound
+            )
+            .text_size(14),
         ]
-        .spacing(12)
-        .width(Fill)
-        .into()
+        .align_y(Center),
+        row![
+            text(tr("settings.volume")).size(14),
+            slider(
+                models::AMBIENT_VOLUME_PERCENT_RANGE,
+                app.settings.ambient_volume_percent,
+                Message::SetAmbientVolume
+            )
+            .width(Fill),
+            text(format!("{}%", app.settings.ambient_volume_percent))
+                .size(14)
+                .width(40),
+        ]
+        .spacing(8)
+        .align_y(Center),
+        space::vertical().height(16),
+        text(tr("settings.window")).size(16),
+        space::vertical().height(8),
+        toggler(app.settings.minimize_to_tray)
+            .label(tr("settings.minimize_to_tray"))
+            .text_size(14)
+            .on_toggle(Message::ToggleMinimizeToTray),
+        toggler(app.settings.status_file)
+            .label(tr("settings.status_file"))
+            .text_size(14)
+            .on_toggle(Message::ToggleStatusFile),
+        view_http_settings(app),
+        space::vertical().height(16),
+        text(tr("settings.data")).size(16),
+        space::vertical().height(8),
+        view_stepper(
+            tr("settings.backups"),
+            match app.settings.backups_to_keep {
+                0 => tr("settings.off").to_string(),
+                n => n.to_string(),
+            },
+            ConfigField::BackupsToKeep,
+        ),
+        button(text(tr("settings.export_json")).size(14))
+            .on_press(Message::ExportJson)
+            .padding([6, 16])
+            .style(button::secondary),
+        button(text(tr("settings.export_settings")).size(14))
+            .on_press(Message::ExportSettings)
+            .padding([6, 16])
+            .style(button::secondary),
+        button(text(tr("settings.import_settings")).size(14))
+            .on_press(Message::ImportSettings)
+            .padding([6, 16])
+            .style(button::secondary),
+        button(text(tr("settings.recalculate")).size(14))
+            .on_press(Message::RecalculateStats)
+            .padding([6, 16])
+            .style(button::secondary),
+        button(text(tr("settings.reset_all")).size(14))
+            .on_press_maybe(app.db.is_some().then_some(Message::RequestReset))
+            .padding([6, 16])
+            .style(button::danger),
+        text(app.data_status.as_deref().unwrap_or_default()).size(12),
+    ]
+    .spacing(6)
+    .width(Fill);
+
+    scrollable(content).height(Fill).into()
+}
+
+fn view_theme_settings(app: &App) -> Element<'_, Message> {
+    let choices: Vec<ThemeChoice> = std::iter::once(ThemeChoice::Auto)
+        .chain(Theme::ALL.iter().cloned().map(ThemeChoice::Fixed))
+        .collect();
+    let selected = theme_choice(&app.settings.theme);
+    let is_auto = selected == ThemeChoice::Auto;
+
+    let mut content = column![row![
+        text(tr("settings.theme")).size(14),
+        space::horizontal(),
+        pick_list(choices, Some(selected), Message::SetTheme).text_size(14),
+    ]
+    .align_y(Center)]
+    .spacing(6);
+
+    if is_auto {
+        content = content
+            .push(view_stepper(
+                tr("settings.light_from"),
+                format!("{:02}:00", app.settings.light_from_hour),
+                ConfigField::LightFromHour,
+            ))
+            .push(view_stepper(
+                tr("settings.dark_from"),
+                format!("{:02}:00", app.settings.dark_from_hour),
+                ConfigField::DarkFromHour,
+            ));
     }
+
+    content.into()
 }
 
-fn view_progress_bar(progress: f32, height: f32) -> Element<'static, Message> {
-    Canvas::new(ProgressBarWidget {
-        progress: progress.clamp(0.0, 1.0),
-    })
-    .width(Fill)
-    .height(height)
+fn view_cycle_reset(app: &App) -> Element<'_, Message> {
+    let cadence = app.settings.timer.sessions_before_long_break.max(1);
+    let done = app.timer.cycle_position(cadence);
+    row![
+        text(trf(
+            "settings.cycle",
+            &[("done", done.to_string()), ("total", cadence.to_string())]
+        ))
+        .size(14),
+        space::horizontal(),
+        button(text(tr("settings.reset_cycle")).size(12))
+            .on_press_maybe((app.timer.cycle_sessions > 0).then_some(Message::ResetCycle))
+            .padding([4, 10])
+            .style(button::secondary),
+    ]
+    .align_y(Center)
     .into()
 }
 
-fn view_stats(app: &App) -> Element<'_, Message> {
-    let stage = xp::ferris_stage(app.profile.level);
+fn view_focus_only(app: &App) -> Element<'_, Message> {
+    let mut content = column![toggler(app.settings.hide_gamification)
+        .label(tr("settings.focus_only"))
+        .text_size(14)
+        .on_toggle(Message::ToggleHideGamification)]
+    .spacing(6);
 
-    let title = text("📊 Stats & Progress").size(22);
+    if app.settings.hide_gamification {
+        content = content.push(
+            toggler(app.settings.track_xp)
+                .label(tr("settings.track_xp"))
+                .text_size(14)
+                .on_toggle(Message::ToggleTrackXp),
+        );
+    }
 
-    let ferris_info = row![
-        text(format!("{}", stage.emoji())).size(48),
-        column![
-            text(stage.label()).size(18),
-            text(format!("Level {}", app.profile.level)).size(14),
-        ]
-        .spacing(4),
-    ]
-    .spacing(16)
-    .align_y(Center);
+    content.into()
+}
 
-    let today_label = text(format!("Today: {} focus sessions", app.today_sessions)).size(14);
+fn view_quiet_hours(app: &App) -> Element<'_, Message> {
+    let mut content = column![toggler(app.settings.quiet_hours_enabled)
+        .label(tr("settings.quiet_hours"))
+        .text_size(14)
+        .on_toggle(Message::ToggleQuietHours)]
+    .spacing(6);
 
-    let total_hours = app.total_focus_secs / 3600;
-    let total_mins = (app.total_focus_secs % 3600) / 60;
-    let total_label = text(format!(
-        "All time: {} sessions • {}h {}m focused",
-        app.total_sessions, total_hours, total_mins
-    ))
-    .size(14);
+    if app.settings.quiet_hours_enabled {
+        content = content
+            .push(view_stepper(
+                tr("settings.quiet_from"),
+                format!("{:02}:00", app.settings.quiet_from_hour),
+                ConfigField::QuietFromHour,
+            ))
+            .push(view_stepper(
+                tr("settings.quiet_until"),
+                format!("{:02}:00", app.settings.quiet_until_hour),
+                ConfigField::QuietUntilHour,
+            ));
+    }
 
-    let streak_label = text(format!(
-        "🔥 Current streak: {} days  •  Best: {} days",
-        app.profile.current_streak, app.profile.longest_streak
-    ))
-    .size(14);
+    content.into()
+}
 
-    let xp_label = text(format!("⭐ Total XP: {}", app.profile.total_xp)).size(14);
+#[cfg(feature = "http")]
+fn view_http_settings(app: &App) -> Option<Element<'_, Message>> {
+    let mut content = column![toggler(app.settings.http_enabled)
+        .label(tr("settings.http"))
+        .text_size(14)
+        .on_toggle(Message::ToggleHttp)]
+    .spacing(6);
 
-    let heatmap_title = text("Last 7 Days").size(16);
-    let heatmap = view_weekly_heatmap(app);
+    if app.settings.http_enabled {
+        let address = format!("http://127.0.0.1:{}/stats", app.settings.http_port);
+        content = content
+            .push(
+                row![
+                    text(tr("settings.http_port")).size(14),
+                    space::horizontal(),
+                    text_input("7878", &app.http_port_draft)
+                        .on_input(Message::EditHttpPort)
+                        .on_submit(Message::ApplyHttpPort)
+                        .size(14)
+                        .padding([4, 8])
+                        .width(80),
+                ]
+                .align_y(Center),
+            )
+            .push(text(app.http_error.clone().unwrap_or(address)).size(12));
+    }
 
-    column![
-        title,
-        space::vertical().height(16),
-        ferris_info,
-        space::vertical().height(16),
-        rule::horizontal(1),
-        space::vertical().height(12),
-        today_label,
-        total_label,
-        space::vertical().height(8),
-        streak_label,
-        xp_label,
-        space::vertical().height(16),
-        rule::horizontal(1),
-        space::vertical().height(12),
-        heatmap_title,
-        space::vertical().height(8),
-        heatmap,
-    ]
-    .spacing(2)
-    .width(Fill)
-    .into()
+    Some(content.into())
 }
 
-fn view_weekly_heatmap(app: &App) -> Element<'_, Message> {
-    let now = Local::now();
-    let today = now.date_naive();
-    let days: Vec<NaiveDate> = (0..7)
-        .rev()
-        .map(|i| today - chrono::Duration::days(i))
-        .collect();
-
-    let day_labels = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+#[cfg(not(feature = "http"))]
+fn view_http_settings(_app: &App) -> Option<Element<'_, Message>> {
+    None
+}
 
-    let boxes: Vec<Element<Message>> = days
-        .iter()
-        .map(|date| {
-            let date_str = date.format("%Y-%m-%d").to_string();
-            let count = app
-                .weekly_data
-                .iter()
-                .find(|(d, _)| d == &date_str)
-                .map(|(_, c)| *c)
-                .unwrap_or(0);
-
-            let weekday_idx = date.weekday().num_days_from_monday() as usize;
-            let label = day_labels[weekday_idx];
-
-            column![
-                Canvas::new(HeatmapCell { count }).width(32).height(32),
-                text(label).size(10),
-            ]
-            .spacing(4)
-            .align_x(Center)
-            .into()
-        })
-        .collect();
+fn view_streak_reminder(app: &App) -> Element<'_, Message> {
+    let mut content = column![toggler(app.settings.streak_reminder_enabled)
+        .label(tr("settings.streak_reminder"))
+        .text_size(14)
+        .on_toggle(Message::ToggleStreakReminder)]
+    .spacing(6);
 
-    let mut heatmap_row = row![].spacing(4);
-    for b in boxes {
-        heatmap_row = heatmap_row.push(b);
+    if app.settings.streak_reminder_enabled {
+        content = content.push(view_stepper(
+            tr("settings.remind_from"),
+            format!("{:02}:00", app.settings.streak_reminder_hour),
+            ConfigField::StreakReminderHour,
+        ));
     }
 
-    heatmap_row.into()
+    content.into()
 }
 
-fn view_nav(app: &App) -> Element<'_, Message> {
-    let timer_style = if app.current_view == View::Timer {
-        button::primary
-    } else {
-        button::secondary
+fn view_message_templates(app: &App) -> Element<'_, Message> {
+    let input = |placeholder, value, template| {
+        text_input(placeholder, value)
+            .on_input(move |text| Message::EditMessageTemplate(template, text))
+            .size(13)
+            .padding([4, 8])
     };
-    let stats_style = if app.current_view == View::Stats {
-        button::primary
-    } else {
-        button::secondary
+    column![
+        text(tr("settings.messages")).size(14),
+        input(
+            tr("notify.focus_body_xp"),
+            &app.settings.focus_complete_message,
+            MessageTemplate::FocusComplete,
+        ),
+        input(
+            tr("notify.break_body"),
+            &app.settings.break_over_message,
+            MessageTemplate::BreakOver,
+        ),
+        input(
+            tr("notify.level_up_body"),
+            &app.settings.level_up_message,
+            MessageTemplate::LevelUp,
+        ),
+        text(tr("settings.messages_hint")).size(12),
+    ]
+    .spacing(4)
+    .into()
+}
+
+/// A whole-minute duration such as "25 min".
+fn minutes(secs: u32) -> String {
+    trf("common.minutes", &[("n", (secs / 60).to_string())])
+}
+
+fn view_stepper(label: &str, value: String, field: ConfigField) -> Element<'_, Message> {
+    row![
+        text(label).size(14),
+        space::horizontal(),
+        button(text("−").size(14).align_x(Center))
+            .on_press(Message::AdjustConfig(field, -1))
+            .padding([2, 10])
+            .style(button::secondary),
+        text(value).size(14).width(64).align_x(Center),
+        button(text("+").size(14).align_x(Center))
+            .on_press(Message::AdjustConfig(field, 1))
+            .padding([2, 10])
+            .style(button::secondary),
+    ]
+    .spacing(6)
+    .align_y(Center)
+    .width(Fill)
+    .into()
+}
+
+fn view_nav(app: &App) -> Element<'_, Message> {
+    let nav_style = |view: View| {
+        if app.current_view == view {
+            button::primary
+        } else {
+            button::secondary
+        }
     };
 
     row![
-        button(text("⏱  Timer").size(14).align_x(Center))
+        button(text(tr("nav.timer")).size(14).align_x(Center))
             .on_press(Message::SwitchView(View::Timer))
-            .padding([8, 20])
+            .padding([8, 8])
             .width(Fill)
-            .style(timer_style),
-        button(text("📊  Stats").size(14).align_x(Center))
+            .style(nav_style(View::Timer)),
+        button(text(tr("nav.stats")).size(14).align_x(Center))
             .on_press(Message::SwitchView(View::Stats))
-            .padding([8, 20])
+            .padding([8, 8])
+            .width(Fill)
+            .style(nav_style(View::Stats)),
+        button(text(tr("nav.settings")).size(14).align_x(Center))
+            .on_press(Message::SwitchView(View::Settings))
+            .padding([8, 8])
             .width(Fill)
-            .style(stats_style),
+            .style(nav_style(View::Settings)),
     ]
     .spacing(8)
     .width(Fill)
@@ -613,15 +4171,24 @@ fn view_nav(app: &App) -> Element<'_, Message> {
 
 // -- canvas widgets --
 
-struct TimerWidget<'a> {
+struct TimerWidget {
     progress: f32,
     remaining: (u32, u32),
-    session_label: &'a str,
+    session_label: String,
+    /// breaks get a calmer accent than focus
+    session_type: Option<SessionType>,
     is_idle: bool,
+    is_paused: bool,
     is_finished: bool,
+    /// 0.0..1.0, swells the ring while focusing
+    pulse: f32,
+    /// 0.0..1.0 as a new session type fades and slides in; 1.0 at rest
+    fade_in: f32,
+    /// draw the arc in one piece rather than in 60 steps
+    continuous_arc: bool,
 }
 
-impl<'a> canvas::Program<Message> for TimerWidget<'a> {
+impl canvas::Program<Message> for TimerWidget {
     type State = ();
 
     fn draw(
@@ -635,8 +4202,15 @@ impl<'a> canvas::Program<Message> for TimerWidget<'a> {
         let mut frame = Frame::new(renderer, bounds.size());
         let center = frame.center();
         let radius = bounds.width.min(bounds.height) / 2.0 - 10.0;
+        // text is laid out for the full-size 100px ring
+        let scale = radius / 100.0;
 
         let palette = theme.palette();
+        let on_break = matches!(
+            self.session_type,
+            Some(SessionType::ShortBreak | SessionType::LongBreak)
+        );
+        let break_color = Color::from_rgb(0.25, 0.7, 0.72);
 
         let bg_circle = Path::circle(center, radius);
         frame.stroke(
@@ -650,6 +4224,10 @@ impl<'a> canvas::Program<Message> for TimerWidget<'a> {
         if !self.is_idle {
             let progress_color = if self.is_finished {
                 Color::from_rgb(0.4, 0.9, 0.4)
+            } else if self.is_paused {
+                Color::from_rgb(0.9, 0.65, 0.2)
+            } else if on_break {
+                break_color
             } else {
                 palette.primary
             };
@@ -657,29 +4235,51 @@ impl<'a> canvas::Program<Message> for TimerWidget<'a> {
             let start_angle = -std::f32::consts::FRAC_PI_2;
             let segments = 60;
             let steps = (segments as f32 * self.progress) as usize;
-            if steps > 0 {
-                let mut builder = canvas::path::Builder::new();
-                for i in 0..=steps {
-                    let angle = start_angle + (i as f32 / segments as f32) * std::f32::consts::TAU;
-                    let x = center.x + radius * angle.cos();
-                    let y = center.y + radius * angle.sin();
-                    if i == 0 {
-                        builder.move_to(iced::Point::new(x, y));
-                    } else {
-                        builder.line_to(iced::Point::new(x, y));
+            let arc_path = if self.continuous_arc {
+                // one smooth arc that creeps along instead of jumping a step at a time
+                (self.progress > 0.0).then(|| {
+                    Path::new(|builder| {
+                        builder.arc(canvas::path::Arc {
+                            center,
+                            radius,
+                            start_angle: iced::Radians(start_angle),
+                            end_angle: iced::Radians(
+                                start_angle + self.progress * std::f32::consts::TAU,
+                            ),
+                        })
+                    })
+                })
+            } else {
+                (steps > 0).then(|| {
+                    let mut builder = canvas::path::Builder::new();
+                    for i in 0..=steps {
+                        let angle =
+                            start_angle + (i as f32 / segments as f32) * std::f32::consts::TAU;
+                        let x = center.x + radius * angle.cos();
+                        let y = center.y + radius * angle.sin();
+                        if i == 0 {
+                            builder.move_to(iced::Point::new(x, y));
+                        } else {
+                            builder.line_to(iced::Point::new(x, y));
+                        }
                     }
-                }
-                let arc_path = builder.build();
+                    builder.build()
+                })
+            };
+            if let Some(arc_path) = arc_path {
                 frame.stroke(
                     &arc_path,
-                    Stroke::default().with_width(8.0).with_color(progress_color),
+                    Stroke::default()
+                        .with_width(8.0 + 2.0 * self.pulse)
+                        .with_color(Color {
+                            a: progress_color.a * self.fade_in,
+                            ..progress_color
+                        }),
                 );
             }
         }
 
-        let time_str = if self.is_idle {
-            "25:00".to_string()
-        } else if self.is_finished {
+        let time_str = if self.is_finished {
             "Done!".to_string()
         } else {
             format!("{:02}:{:02}", self.remaining.0, self.remaining.1)
@@ -687,22 +4287,37 @@ impl<'a> canvas::Program<Message> for TimerWidget<'a> {
 
         frame.fill_text(canvas::Text {
             content: time_str,
-            position: iced::Point::new(center.x, center.y - 10.0),
-            color: palette.text,
-            size: iced::Pixels(42.0),
+            position: iced::Point::new(center.x, center.y - 10.0 * scale),
+            color: Color {
+                a: (if self.is_paused { 0.5 } else { 1.0 }) * self.fade_in,
+                ..palette.text
+            },
+            size: iced::Pixels(42.0 * scale),
             align_x: iced::alignment::Horizontal::Center.into(),
             align_y: alignment::Vertical::Center,
             ..canvas::Text::default()
         });
 
-        frame.fill_text(canvas::Text {
-            content: self.session_label.to_string(),
-            position: iced::Point::new(center.x, center.y + 25.0),
-            color: Color {
+        let label_color = if on_break && !self.is_finished {
+            break_color
+        } else {
+            Color {
                 a: 0.6,
                 ..palette.text
+            }
+        };
+        frame.fill_text(canvas::Text {
+            content: self.session_label.clone(),
+            // rises into place as a new session type comes in
+            position: iced::Point::new(
+                center.x,
+                center.y + (25.0 + 12.0 * (1.0 - self.fade_in)) * scale,
+            ),
+            color: Color {
+                a: label_color.a * self.fade_in,
+                ..label_color
             },
-            size: iced::Pixels(14.0),
+            size: iced::Pixels((14.0 * scale).max(10.0)),
             align_x: iced::alignment::Horizontal::Center.into(),
             align_y: alignment::Vertical::Center,
             ..canvas::Text::default()
@@ -712,6 +4327,105 @@ impl<'a> canvas::Program<Message> for TimerWidget<'a> {
     }
 }
 
+/// One dot per focus session in the cycle: filled once done, hollow to go.
+struct CycleDots {
+    total: u32,
+    done: u32,
+    /// the next dot is the session under way
+    current: bool,
+    pulse: f32,
+}
+
+impl CycleDots {
+    const SPACING: f32 = 14.0;
+}
+
+impl canvas::Program<Message> for CycleDots {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &iced::Renderer,
+        theme: &Theme,
+        bounds: iced::Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let primary = theme.palette().primary;
+        let radius = 4.0;
+
+        for i in 0..self.total {
+            let center = iced::Point::new((i as f32 + 0.5) * Self::SPACING, bounds.height / 2.0);
+            if i < self.done {
+                frame.fill(&Path::circle(center, radius), primary);
+                continue;
+            }
+            if i == self.done && self.current {
+                let fill = Color {
+                    a: 0.3 + 0.5 * self.pulse,
+                    ..primary
+                };
+                frame.fill(&Path::circle(center, radius + self.pulse), fill);
+            }
+            frame.stroke(
+                &Path::circle(center, radius),
+                Stroke::default().with_width(1.5).with_color(primary),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Daily session counts as a thin line, scaled to the busiest day.
+struct Sparkline {
+    counts: Vec<u32>,
+}
+
+impl canvas::Program<Message> for Sparkline {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &iced::Renderer,
+        theme: &Theme,
+        bounds: iced::Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        if self.counts.len() < 2 {
+            return vec![frame.into_geometry()];
+        }
+
+        let max = self.counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let step = bounds.width / (self.counts.len() - 1) as f32;
+        // keep the stroke inside the frame at the top and bottom
+        let height = bounds.height - 2.0;
+        let line = Path::new(|builder| {
+            for (i, &count) in self.counts.iter().enumerate() {
+                let point =
+                    iced::Point::new(i as f32 * step, 1.0 + height * (1.0 - count as f32 / max));
+                if i == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default().with_width(1.5).with_color(Color {
+                a: 0.7,
+                ..theme.palette().primary
+            }),
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
+
 struct ProgressBarWidget {
     progress: f32,
 }
@@ -755,8 +4469,151 @@ impl canvas::Program<Message> for ProgressBarWidget {
     }
 }
 
+/// Evenly spaced bars scaled to the largest value.
+struct BarChart {
+    values: Vec<u32>,
+}
+
+impl canvas::Program<Message> for BarChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &iced::Renderer,
+        theme: &Theme,
+        bounds: iced::Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+
+        let max = self.values.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let slot = bounds.width / self.values.len().max(1) as f32;
+        for (i, &count) in self.values.iter().enumerate() {
+            // empty slots keep a sliver so the axis reads as a full range
+            let height = (bounds.height * count as f32 / max).max(2.0);
+            let bar = Path::rectangle(
+                iced::Point::new(i as f32 * slot + 1.0, bounds.height - height),
+                iced::Size::new((slot - 2.0).max(1.0), height),
+            );
+            let color = if count > 0 {
+                palette.primary
+            } else {
+                Color {
+                    a: 0.15,
+                    ..palette.text
+                }
+            };
+            frame.fill(&bar, color);
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// One cell per day, lit when the day had a focus session, so streaks show
+/// up as unbroken runs.
+struct StreakStrip {
+    days: Vec<bool>,
+}
+
+impl canvas::Program<Message> for StreakStrip {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &iced::Renderer,
+        theme: &Theme,
+        bounds: iced::Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+
+        let slot = bounds.width / self.days.len().max(1) as f32;
+        for (i, &active) in self.days.iter().enumerate() {
+            let next_active = self.days.get(i + 1).copied().unwrap_or(false);
+            // consecutive active days join up into a bar
+            let gap = if active && next_active { 0.0 } else { 1.0 };
+            let cell = Path::rectangle(
+                iced::Point::new(i as f32 * slot, 0.0),
+                iced::Size::new((slot - gap).max(1.0), bounds.height),
+            );
+            let color = if active {
+                palette.success
+            } else {
+                Color {
+                    a: 0.12,
+                    ..palette.text
+                }
+            };
+            frame.fill(&cell, color);
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
 struct HeatmapCell {
     count: u32,
+    palette: HeatmapPalette,
+    /// days reaching it get a ring; 0 for no goal
+    goal: u32,
+}
+
+/// Cell colors for one, two, three and four-plus sessions
+const GREEN_RAMP: [Color; 4] = [
+    color!(0x9be9a8),
+    color!(0x40c463),
+    color!(0x30a14e),
+    color!(0x216e39),
+];
+const VIRIDIS_RAMP: [Color; 4] = [
+    color!(0x3b528b),
+    color!(0x21918c),
+    color!(0x5ec962),
+    color!(0xfde725),
+];
+
+impl HeatmapCell {
+    fn colors(&self, palette: &iced::theme::Palette) -> (Color, Color) {
+        let ramp = match self.palette {
+            HeatmapPalette::Theme => {
+                let intensity = match self.count {
+                    0 => 0.08,
+                    1 => 0.3,
+                    2 => 0.5,
+                    3 => 0.7,
+                    _ => 0.9,
+                };
+                let fill = Color {
+                    a: intensity,
+                    ..palette.primary
+                };
+                return (fill, palette.text);
+            }
+            HeatmapPalette::Green => GREEN_RAMP,
+            HeatmapPalette::Viridis => VIRIDIS_RAMP,
+        };
+        if self.count == 0 {
+            let empty = Color {
+                a: 0.08,
+                ..palette.text
+            };
+            return (empty, palette.text);
+        }
+        let fill = ramp[(self.count.min(4) - 1) as usize];
+        // dark digits on the bright end of the ramp
+        let luma = 0.299 * fill.r + 0.587 * fill.g + 0.114 * fill.b;
+        let label = if luma > 0.5 {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        };
+        (fill, label)
+    }
 }
 
 impl canvas::Program<Message> for HeatmapCell {
@@ -773,18 +4630,7 @@ impl canvas::Program<Message> for HeatmapCell {
         let mut frame = Frame::new(renderer, bounds.size());
         let palette = theme.palette();
 
-        let intensity = match self.count {
-            0 => 0.08,
-            1 => 0.3,
-            2 => 0.5,
-            3 => 0.7,
-            _ => 0.9,
-        };
-
-        let color = Color {
-            a: intensity,
-            ..palette.primary
-        };
+        let (color, label_color) = self.colors(&palette);
 
         let rect = Path::rectangle(
             iced::Point::new(2.0, 2.0),
@@ -792,11 +4638,24 @@ impl canvas::Program<Message> for HeatmapCell {
         );
         frame.fill(&rect, color);
 
+        if self.goal > 0 && self.count >= self.goal {
+            let ring = Path::rectangle(
+                iced::Point::new(1.0, 1.0),
+                iced::Size::new(bounds.width - 2.0, bounds.height - 2.0),
+            );
+            frame.stroke(
+                &ring,
+                Stroke::default()
+                    .with_width(2.0)
+                    .with_color(palette.warning),
+            );
+        }
+
         if self.count > 0 {
             frame.fill_text(canvas::Text {
                 content: self.count.to_string(),
                 position: iced::Point::new(bounds.width / 2.0, bounds.height / 2.0),
-                color: palette.text,
+                color: label_color,
                 size: iced::Pixels(13.0),
                 align_x: iced::alignment::Horizontal::Center.into(),
                 align_y: alignment::Vertical::Center,
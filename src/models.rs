@@ -1,5 +1,10 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::i18n::tr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SessionType {
@@ -24,34 +29,353 @@ impl SessionType {
             SessionType::LongBreak => "long_break",
         }
     }
+}
+
+impl FromStr for SessionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "focus" => Ok(SessionType::Focus),
+            "short_break" => Ok(SessionType::ShortBreak),
+            "long_break" => Ok(SessionType::LongBreak),
+            _ => Err(format!("unknown session type '{}'", s)),
+        }
+    }
+}
+
+/// Background noise looped during focus sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmbientSound {
+    Off,
+    WhiteNoise,
+    BrownNoise,
+}
+
+impl AmbientSound {
+    pub const ALL: [AmbientSound; 3] = [
+        AmbientSound::Off,
+        AmbientSound::WhiteNoise,
+        AmbientSound::BrownNoise,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            AmbientSound::Off => "ambience.off",
+            AmbientSound::WhiteNoise => "ambience.white",
+            AmbientSound::BrownNoise => "ambience.brown",
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AmbientSound::Off => "off",
+            AmbientSound::WhiteNoise => "white",
+            AmbientSound::BrownNoise => "brown",
+        }
+    }
+}
+
+impl fmt::Display for AmbientSound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for AmbientSound {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(AmbientSound::Off),
+            "white" => Ok(AmbientSound::WhiteNoise),
+            "brown" => Ok(AmbientSound::BrownNoise),
+            _ => Err(format!("unknown ambient sound '{}'", s)),
+        }
+    }
+}
+
+/// What happens to a session whose time ran out while the computer was asleep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuspendAction {
+    Finish,
+    Abandon,
+}
+
+impl SuspendAction {
+    pub const ALL: [SuspendAction; 2] = [SuspendAction::Finish, SuspendAction::Abandon];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            SuspendAction::Finish => "suspend.finish",
+            SuspendAction::Abandon => "suspend.abandon",
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuspendAction::Finish => "finish",
+            SuspendAction::Abandon => "abandon",
+        }
+    }
+}
+
+impl fmt::Display for SuspendAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
 
-    pub fn from_str(s: &str) -> Self {
+impl FromStr for SuspendAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "focus" => SessionType::Focus,
-            "short_break" => SessionType::ShortBreak,
-            "long_break" => SessionType::LongBreak,
-            _ => SessionType::Focus,
+            "finish" => Ok(SuspendAction::Finish),
+            "abandon" => Ok(SuspendAction::Abandon),
+            _ => Err(format!("unknown suspend action '{}'", s)),
         }
     }
 }
 
+/// First day of the week in the heatmaps and weekly summaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeekStart {
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub const ALL: [WeekStart; 2] = [WeekStart::Monday, WeekStart::Sunday];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            WeekStart::Monday => "week_start.monday",
+            WeekStart::Sunday => "week_start.sunday",
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeekStart::Monday => "monday",
+            WeekStart::Sunday => "sunday",
+        }
+    }
+
+    /// Days between the start of `date`'s week and `date`
+    pub fn days_into_week(&self, date: NaiveDate) -> u32 {
+        match self {
+            WeekStart::Monday => date.weekday().num_days_from_monday(),
+            WeekStart::Sunday => date.weekday().num_days_from_sunday(),
+        }
+    }
+
+    /// Weekday abbreviations in week order
+    pub fn day_labels(&self) -> [&'static str; 7] {
+        match self {
+            WeekStart::Monday => ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+            WeekStart::Sunday => ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        }
+    }
+
+    /// Reorders per-weekday values given Monday first to match `day_labels`
+    pub fn in_week_order<T>(&self, mut monday_first: [T; 7]) -> [T; 7] {
+        if *self == WeekStart::Sunday {
+            monday_first.rotate_right(1);
+        }
+        monday_first
+    }
+}
+
+impl fmt::Display for WeekStart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for WeekStart {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "monday" => Ok(WeekStart::Monday),
+            "sunday" => Ok(WeekStart::Sunday),
+            _ => Err(format!("unknown week start '{}'", s)),
+        }
+    }
+}
+
+/// What decides when the long break comes round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CycleMode {
+    /// every Nth focus session by the running total
+    Running,
+    /// after N focus sessions since the last long break or cycle reset
+    FullCycle,
+}
+
+impl CycleMode {
+    pub const ALL: [CycleMode; 2] = [CycleMode::Running, CycleMode::FullCycle];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            CycleMode::Running => "cycle_mode.running",
+            CycleMode::FullCycle => "cycle_mode.full_cycle",
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CycleMode::Running => "running",
+            CycleMode::FullCycle => "full_cycle",
+        }
+    }
+}
+
+impl fmt::Display for CycleMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for CycleMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CycleMode::ALL
+            .into_iter()
+            .find(|m| m.as_str() == s)
+            .ok_or_else(|| format!("unknown cycle mode '{}'", s))
+    }
+}
+
+/// Colors for the heatmap cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeatmapPalette {
+    /// shades of the theme's primary color
+    Theme,
+    Green,
+    Viridis,
+}
+
+impl HeatmapPalette {
+    pub const ALL: [HeatmapPalette; 3] = [
+        HeatmapPalette::Theme,
+        HeatmapPalette::Green,
+        HeatmapPalette::Viridis,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            HeatmapPalette::Theme => "palette.theme",
+            HeatmapPalette::Green => "palette.green",
+            HeatmapPalette::Viridis => "palette.viridis",
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HeatmapPalette::Theme => "theme",
+            HeatmapPalette::Green => "green",
+            HeatmapPalette::Viridis => "viridis",
+        }
+    }
+}
+
+impl fmt::Display for HeatmapPalette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for HeatmapPalette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HeatmapPalette::ALL
+            .into_iter()
+            .find(|p| p.as_str() == s)
+            .ok_or_else(|| format!("unknown heatmap palette '{}'", s))
+    }
+}
+
+/// Language the interface is shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// Each language's name in itself, so it can be found without reading the current one
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .into_iter()
+            .find(|l| l.as_str() == s)
+            .ok_or_else(|| format!("unknown language '{}'", s))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     pub id: Option<i64>,
+    /// local wall-clock time where the session happened, `%Y-%m-%dT%H:%M:%S`;
+    /// days and ranges go by this clock, so travel never moves past sessions
     pub started_at: String,
     pub completed_at: Option<String>,
     pub duration_secs: u32,
     pub session_type: SessionType,
     pub completed: bool,
+    /// project the focus time is attributed to
+    pub tag: Option<String>,
+    /// what got done, jotted after a focus session
+    pub note: Option<String>,
+    /// what the session was meant for, stated before it started
+    pub intention: Option<String>,
+    /// XP this session was rewarded with
+    pub xp_earned: u32,
+    /// distractions marked during the session
+    pub interruptions: u32,
+    /// how far `started_at` was ahead of UTC, for its exact instant; `None`
+    /// for sessions saved before it was recorded
+    #[serde(default)]
+    pub utc_offset_secs: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UserProfile {
     pub total_xp: u32,
     pub level: u32,
     pub current_streak: u32,
     pub longest_streak: u32,
     pub last_session_date: Option<NaiveDate>,
+    pub freezes_available: u32,
 }
 
 impl Default for UserProfile {
@@ -62,6 +386,7 @@ impl Default for UserProfile {
             current_streak: 0,
             longest_streak: 0,
             last_session_date: None,
+            freezes_available: 0,
         }
     }
 }
@@ -73,6 +398,9 @@ pub enum FerrisStage {
     Junior,
     Senior,
     King,
+    Emperor,
+    Legend,
+    Mythic,
 }
 
 impl FerrisStage {
@@ -83,17 +411,23 @@ impl FerrisStage {
             FerrisStage::Junior => "🦀",
             FerrisStage::Senior => "⭐",
             FerrisStage::King => "👑",
+            FerrisStage::Emperor => "🔱",
+            FerrisStage::Legend => "🐉",
+            FerrisStage::Mythic => "🌌",
         }
     }
 
     pub fn label(&self) -> &'static str {
-        match self {
-            FerrisStage::Egg => "Egg",
-            FerrisStage::Hatchling => "Hatchling",
-            FerrisStage::Junior => "Junior Crab",
-            FerrisStage::Senior => "Senior Crab",
-            FerrisStage::King => "King Crab",
-        }
+        tr(match self {
+            FerrisStage::Egg => "stage.egg",
+            FerrisStage::Hatchling => "stage.hatchling",
+            FerrisStage::Junior => "stage.junior",
+            FerrisStage::Senior => "stage.senior",
+            FerrisStage::King => "stage.king",
+            FerrisStage::Emperor => "stage.emperor",
+            FerrisStage::Legend => "stage.legend",
+            FerrisStage::Mythic => "stage.mythic",
+        })
     }
 }
 
@@ -101,3 +435,650 @@ pub const FOCUS_DURATION_SECS: u32 = 25 * 60;
 pub const SHORT_BREAK_SECS: u32 = 5 * 60;
 pub const LONG_BREAK_SECS: u32 = 15 * 60;
 pub const SESSIONS_BEFORE_LONG_BREAK: u32 = 4;
+/// bounds on any one session's length
+pub const MIN_DURATION_SECS: u32 = 60;
+pub const MAX_DURATION_SECS: u32 = 180 * 60;
+pub const MAX_SESSIONS_BEFORE_LONG_BREAK: u32 = 12;
+pub const DURATION_SECS_RANGE: RangeInclusive<u32> = MIN_DURATION_SECS..=MAX_DURATION_SECS;
+pub const SESSIONS_BEFORE_LONG_BREAK_RANGE: RangeInclusive<u32> =
+    1..=MAX_SESSIONS_BEFORE_LONG_BREAK;
+pub const BASE_XP: u32 = 100;
+pub const STREAK_BONUS_PER_DAY: u32 = 10;
+pub const MAX_STREAK_BONUS: u32 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimerConfig {
+    pub focus_secs: u32,
+    pub short_break_secs: u32,
+    pub long_break_secs: u32,
+    pub sessions_before_long_break: u32,
+}
+
+impl TimerConfig {
+    pub fn duration_for(&self, session_type: SessionType) -> u32 {
+        match session_type {
+            SessionType::Focus => self.focus_secs,
+            SessionType::ShortBreak => self.short_break_secs,
+            SessionType::LongBreak => self.long_break_secs,
+        }
+    }
+
+    /// Checks every duration is within `DURATION_SECS_RANGE` and the cadence
+    /// within `SESSIONS_BEFORE_LONG_BREAK_RANGE`.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for session_type in [
+            SessionType::Focus,
+            SessionType::ShortBreak,
+            SessionType::LongBreak,
+        ] {
+            let secs = self.duration_for(session_type);
+            if !DURATION_SECS_RANGE.contains(&secs) {
+                return Err(ConfigError::Duration(session_type, secs));
+            }
+        }
+        if !SESSIONS_BEFORE_LONG_BREAK_RANGE.contains(&self.sessions_before_long_break) {
+            return Err(ConfigError::Cadence(self.sessions_before_long_break));
+        }
+        Ok(())
+    }
+}
+
+/// Why a `TimerConfig` was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// seconds given for a session type
+    Duration(SessionType, u32),
+    /// sessions before a long break
+    Cadence(u32),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Duration(session_type, secs) => write!(
+                f,
+                "{} duration must be {} to {} minutes, not {}s",
+                session_type.as_str(),
+                MIN_DURATION_SECS / 60,
+                MAX_DURATION_SECS / 60,
+                secs
+            ),
+            ConfigError::Cadence(sessions) => write!(
+                f,
+                "sessions before a long break must be 1 to {}, not {}",
+                MAX_SESSIONS_BEFORE_LONG_BREAK, sessions
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            focus_secs: FOCUS_DURATION_SECS,
+            short_break_secs: SHORT_BREAK_SECS,
+            long_break_secs: LONG_BREAK_SECS,
+            sessions_before_long_break: SESSIONS_BEFORE_LONG_BREAK,
+        }
+    }
+}
+
+/// Named durations to switch between, e.g. a short pomodoro and a long deep-work block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimerPreset {
+    pub name: String,
+    pub config: TimerConfig,
+}
+
+impl TimerPreset {
+    pub fn defaults() -> Vec<TimerPreset> {
+        let preset =
+            |name: &str, focus_mins: u32, short_mins: u32, long_mins: u32, sessions| TimerPreset {
+                name: name.to_string(),
+                config: TimerConfig {
+                    focus_secs: focus_mins * 60,
+                    short_break_secs: short_mins * 60,
+                    long_break_secs: long_mins * 60,
+                    sessions_before_long_break: sessions,
+                },
+            };
+        vec![
+            preset("Pomodoro", 25, 5, 15, 4),
+            preset("Deep Work", 90, 20, 30, 2),
+            preset("Short", 15, 3, 10, 4),
+        ]
+    }
+}
+
+/// XP for a full focus session: a base amount plus a capped bonus per streak day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XpConfig {
+    pub base_xp: u32,
+    pub streak_bonus_per_day: u32,
+    pub max_streak_bonus: u32,
+}
+
+impl Default for XpConfig {
+    fn default() -> Self {
+        Self {
+            base_xp: BASE_XP,
+            streak_bonus_per_day: STREAK_BONUS_PER_DAY,
+            max_streak_bonus: MAX_STREAK_BONUS,
+        }
+    }
+}
+
+pub const AUTO_START_DELAY_SECS: u32 = 3;
+pub const AUTO_ABANDON_AFTER_SECS: u32 = 15 * 60;
+pub const IDLE_THRESHOLD_SECS: u32 = 5 * 60;
+/// Display name of one of iced's built-in themes
+pub const DEFAULT_THEME: &str = "Catppuccin Mocha";
+/// Theme setting that follows the clock between a light and a dark theme
+pub const AUTO_THEME: &str = "Auto";
+pub const LIGHT_FROM_HOUR: u32 = 7;
+pub const DARK_FROM_HOUR: u32 = 19;
+pub const BACKUPS_TO_KEEP: u32 = 5;
+pub const AMBIENT_VOLUME_PERCENT: u32 = 30;
+pub const QUIET_FROM_HOUR: u32 = 22;
+pub const QUIET_UNTIL_HOUR: u32 = 7;
+pub const STREAK_REMINDER_HOUR: u32 = 20;
+pub const HTTP_PORT: u32 = 7878;
+pub const WINDOW_WIDTH: u32 = 320;
+pub const WINDOW_HEIGHT: u32 = 540;
+/// window size in compact mode, which shows only the timer
+pub const COMPACT_WINDOW_SIZE: u32 = 180;
+
+// what `Settings::validate` accepts and the Settings steppers stay within
+pub const AUTO_START_DELAY_SECS_RANGE: RangeInclusive<u32> = 0..=30;
+pub const AUTO_ABANDON_AFTER_SECS_RANGE: RangeInclusive<u32> = 0..=120 * 60;
+pub const EXTEND_SECS_RANGE: RangeInclusive<u32> = 60..=30 * 60;
+pub const BACKUPS_TO_KEEP_RANGE: RangeInclusive<u32> = 0..=30;
+pub const AMBIENT_VOLUME_PERCENT_RANGE: RangeInclusive<u32> = 0..=100;
+pub const DAY_BOUNDARY_HOUR_RANGE: RangeInclusive<u32> = 0..=12;
+pub const DAILY_GOAL_RANGE: RangeInclusive<u32> = 0..=24;
+pub const WEEKLY_GOAL_RANGE: RangeInclusive<u32> = 0..=100;
+pub const IDLE_THRESHOLD_SECS_RANGE: RangeInclusive<u32> = 60..=60 * 60;
+pub const BASE_XP_RANGE: RangeInclusive<u32> = 10..=1000;
+pub const STREAK_BONUS_PER_DAY_RANGE: RangeInclusive<u32> = 0..=100;
+pub const MAX_STREAK_BONUS_RANGE: RangeInclusive<u32> = 0..=1000;
+pub const HTTP_PORT_RANGE: RangeInclusive<u32> = 1..=u16::MAX as u32;
+
+/// Missing fields take their defaults, so files from older versions still load.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub timer: TimerConfig,
+    /// named durations to pick `timer` from, in menu order
+    pub presets: Vec<TimerPreset>,
+    pub xp: XpConfig,
+    pub auto_start: bool,
+    pub auto_start_delay_secs: u32,
+    pub sound_enabled: bool,
+    /// 0 disables auto-abandoning paused sessions
+    pub auto_abandon_after_secs: u32,
+    /// Hide to the system tray instead of minimizing/closing
+    pub minimize_to_tray: bool,
+    /// Start the long-break cycle over at the start of each day
+    pub reset_cycle_daily: bool,
+    pub theme: String,
+    /// hours bounding daytime for the auto theme
+    pub light_from_hour: u32,
+    pub dark_from_hour: u32,
+    /// startup backups to retain; 0 disables them
+    pub backups_to_keep: u32,
+    /// Ask for a short note after each focus session
+    pub prompt_for_notes: bool,
+    pub ambient_sound: AmbientSound,
+    pub ambient_volume_percent: u32,
+    /// no OS notifications or sounds between these hours
+    pub quiet_hours_enabled: bool,
+    pub quiet_from_hour: u32,
+    pub quiet_until_hour: u32,
+    pub on_suspend: SuspendAction,
+    /// keep the window above other apps
+    pub always_on_top: bool,
+    /// where the window was last left; `None` centers it
+    pub window_position: Option<(i32, i32)>,
+    pub window_width: u32,
+    pub window_height: u32,
+    /// show just the timer ring in a small window
+    pub compact_mode: bool,
+    /// nudge once a day from this hour if the streak would otherwise end
+    pub streak_reminder_enabled: bool,
+    pub streak_reminder_hour: u32,
+    /// notification texts with `{xp}`, `{level}`, `{stage}`, `{streak}`
+    /// placeholders; empty uses the built-in message
+    pub focus_complete_message: String,
+    pub break_over_message: String,
+    pub level_up_message: String,
+    /// plain timer: no Ferris, XP, levels or streaks on screen
+    pub hide_gamification: bool,
+    /// award XP and keep streaks; off stops the progression entirely
+    pub track_xp: bool,
+    /// 24-hour times instead of AM/PM
+    pub use_24h_clock: bool,
+    /// ask what a focus session is for before it starts
+    pub prompt_for_intention: bool,
+    /// first day of the week in Stats
+    pub week_starts_on: WeekStart,
+    /// time the extend button adds to a session
+    pub extend_secs: u32,
+    /// preset the timer durations came from; `None` once they no longer match one
+    pub active_preset: Option<String>,
+    /// no bobbing, pulsing or stepped ring; also spares the battery
+    pub reduce_motion: bool,
+    pub heatmap_palette: HeatmapPalette,
+    /// redraw the ring at ~30fps instead of once a second
+    pub smooth_ring: bool,
+    /// how focus sessions are counted towards the long break
+    pub cycle_mode: CycleMode,
+    pub language: Language,
+    /// celebrate only the level-ups that reach a new Ferris stage
+    pub stage_changes_only: bool,
+    /// hour a new day starts for stats and streaks; earlier sessions count towards the day before
+    pub day_boundary_hour: u32,
+    /// focus sessions to aim for each day; 0 turns the goal off
+    pub daily_goal: u32,
+    /// keep a status file up to date for status bars to poll
+    pub status_file: bool,
+    /// serve stats on localhost; needs the `http` feature
+    pub http_enabled: bool,
+    pub http_port: u32,
+    /// focus sessions to aim for each week; 0 turns the goal off
+    pub weekly_goal: u32,
+    /// pause a running focus session after `idle_threshold_secs` without
+    /// keyboard or mouse input
+    pub idle_pause: bool,
+    pub idle_threshold_secs: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            timer: TimerConfig::default(),
+            presets: TimerPreset::defaults(),
+            xp: XpConfig::default(),
+            auto_start: false,
+            auto_start_delay_secs: AUTO_START_DELAY_SECS,
+            sound_enabled: true,
+            auto_abandon_after_secs: AUTO_ABANDON_AFTER_SECS,
+            minimize_to_tray: false,
+            reset_cycle_daily: false,
+            theme: DEFAULT_THEME.to_string(),
+            light_from_hour: LIGHT_FROM_HOUR,
+            dark_from_hour: DARK_FROM_HOUR,
+            backups_to_keep: BACKUPS_TO_KEEP,
+            prompt_for_notes: false,
+            ambient_sound: AmbientSound::Off,
+            ambient_volume_percent: AMBIENT_VOLUME_PERCENT,
+            quiet_hours_enabled: false,
+            quiet_from_hour: QUIET_FROM_HOUR,
+            quiet_until_hour: QUIET_UNTIL_HOUR,
+            on_suspend: SuspendAction::Finish,
+            always_on_top: false,
+            window_position: None,
+            window_width: WINDOW_WIDTH,
+            window_height: WINDOW_HEIGHT,
+            compact_mode: false,
+            streak_reminder_enabled: true,
+            streak_reminder_hour: STREAK_REMINDER_HOUR,
+            focus_complete_message: String::new(),
+            break_over_message: String::new(),
+            level_up_message: String::new(),
+            hide_gamification: false,
+            track_xp: true,
+            use_24h_clock: false,
+            prompt_for_intention: false,
+            week_starts_on: WeekStart::Monday,
+            extend_secs: 300,
+            active_preset: None,
+            reduce_motion: false,
+            heatmap_palette: HeatmapPalette::Theme,
+            smooth_ring: false,
+            cycle_mode: CycleMode::Running,
+            language: Language::English,
+            stage_changes_only: false,
+            day_boundary_hour: 0,
+            daily_goal: 4,
+            status_file: false,
+            http_enabled: false,
+            http_port: HTTP_PORT,
+            weekly_goal: 20,
+            idle_pause: false,
+            idle_threshold_secs: IDLE_THRESHOLD_SECS,
+        }
+    }
+}
+
+impl Settings {
+    /// Whether the auto theme should be light at `hour`
+    pub fn is_daytime(&self, hour: u32) -> bool {
+        hour_in_range(hour, self.light_from_hour, self.dark_from_hour)
+    }
+
+    /// The day `at` counts towards in stats and streaks
+    pub fn day_of(&self, at: NaiveDateTime) -> NaiveDate {
+        day_of(at, self.day_boundary_hour)
+    }
+
+    /// Whether notifications and sounds should be held back at `hour`
+    pub fn is_quiet_hour(&self, hour: u32) -> bool {
+        self.quiet_hours_enabled && hour_in_range(hour, self.quiet_from_hour, self.quiet_until_hour)
+    }
+
+    /// Switches the timer to the named preset's durations.
+    pub fn select_preset(&mut self, name: &str) -> bool {
+        let Some(preset) = self.presets.iter().find(|p| p.name == name) else {
+            return false;
+        };
+        self.timer = preset.config;
+        self.active_preset = Some(preset.name.clone());
+        true
+    }
+
+    /// Keeps the current durations as `name`, overwriting a preset of that name.
+    pub fn save_preset(&mut self, name: &str) {
+        match self.presets.iter_mut().find(|p| p.name == name) {
+            Some(preset) => preset.config = self.timer,
+            None => self.presets.push(TimerPreset {
+                name: name.to_string(),
+                config: self.timer,
+            }),
+        }
+        self.active_preset = Some(name.to_string());
+    }
+
+    /// Forgets the active preset; the durations themselves stay.
+    pub fn delete_active_preset(&mut self) {
+        if let Some(name) = self.active_preset.take() {
+            self.presets.retain(|p| p.name != name);
+        }
+    }
+
+    /// Checks values read from outside the app, e.g. an imported settings
+    /// file, against the ranges the settings screen allows.
+    pub fn validate(&self) -> Result<(), String> {
+        let timers = std::iter::once(("timer", &self.timer))
+            .chain(self.presets.iter().map(|p| (p.name.as_str(), &p.config)));
+        for (name, config) in timers {
+            config.validate().map_err(|e| format!("{}: {}", name, e))?;
+        }
+        let hours = [
+            ("light_from_hour", self.light_from_hour),
+            ("dark_from_hour", self.dark_from_hour),
+            ("quiet_from_hour", self.quiet_from_hour),
+            ("quiet_until_hour", self.quiet_until_hour),
+            ("streak_reminder_hour", self.streak_reminder_hour),
+        ];
+        if let Some((field, _)) = hours.iter().find(|(_, hour)| *hour >= 24) {
+            return Err(format!("{} must be an hour from 0 to 23", field));
+        }
+        let ranges = [
+            (
+                "auto_start_delay_secs",
+                self.auto_start_delay_secs,
+                AUTO_START_DELAY_SECS_RANGE,
+            ),
+            (
+                "auto_abandon_after_secs",
+                self.auto_abandon_after_secs,
+                AUTO_ABANDON_AFTER_SECS_RANGE,
+            ),
+            ("extend_secs", self.extend_secs, EXTEND_SECS_RANGE),
+            (
+                "backups_to_keep",
+                self.backups_to_keep,
+                BACKUPS_TO_KEEP_RANGE,
+            ),
+            (
+                "ambient_volume_percent",
+                self.ambient_volume_percent,
+                AMBIENT_VOLUME_PERCENT_RANGE,
+            ),
+            (
+                "day_boundary_hour",
+                self.day_boundary_hour,
+                DAY_BOUNDARY_HOUR_RANGE,
+            ),
+            ("daily_goal", self.daily_goal, DAILY_GOAL_RANGE),
+            ("weekly_goal", self.weekly_goal, WEEKLY_GOAL_RANGE),
+            (
+                "idle_threshold_secs",
+                self.idle_threshold_secs,
+                IDLE_THRESHOLD_SECS_RANGE,
+            ),
+            ("xp.base_xp", self.xp.base_xp, BASE_XP_RANGE),
+            (
+                "xp.streak_bonus_per_day",
+                self.xp.streak_bonus_per_day,
+                STREAK_BONUS_PER_DAY_RANGE,
+            ),
+            (
+                "xp.max_streak_bonus",
+                self.xp.max_streak_bonus,
+                MAX_STREAK_BONUS_RANGE,
+            ),
+            ("http_port", self.http_port, HTTP_PORT_RANGE),
+        ];
+        for (field, value, range) in ranges {
+            if !range.contains(&value) {
+                return Err(format!(
+                    "{} must be {} to {}, not {}",
+                    field,
+                    range.start(),
+                    range.end(),
+                    value
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Points `active_preset` at whichever preset the durations match after
+    /// editing them by hand.
+    pub fn match_preset(&mut self) {
+        self.active_preset = self
+            .presets
+            .iter()
+            .find(|p| p.config == self.timer)
+            .map(|p| p.name.clone());
+    }
+}
+
+/// The day `at` counts towards when each day starts at `day_boundary_hour`,
+/// so a 1 AM session with a 4 AM boundary still belongs to the evening before.
+pub fn day_of(at: NaiveDateTime, day_boundary_hour: u32) -> NaiveDate {
+    (at - Duration::hours(day_boundary_hour as i64)).date()
+}
+
+/// Seconds the wall-clock time `at` is ahead of UTC in `tz`. In the hour
+/// repeated when clocks go back, the earlier of the two readings wins; a time
+/// skipped when they go forward has no offset.
+pub fn utc_offset_secs<Tz: TimeZone>(tz: &Tz, at: NaiveDateTime) -> Option<i32> {
+    tz.offset_from_local_datetime(&at)
+        .earliest()
+        .map(|offset| offset.fix().local_minus_utc())
+}
+
+/// `[from, to)` in hours of the day; handles ranges that wrap midnight.
+fn hour_in_range(hour: u32, from: u32, to: u32) -> bool {
+    if from <= to {
+        (from..to).contains(&hour)
+    } else {
+        hour >= from || hour < to
+    }
+}
+
+/// Pulls a restored window fully onto a `monitor`-sized screen, e.g. after the
+/// screen it was last on got disconnected.
+pub fn clamp_to_monitor(
+    position: (i32, i32),
+    window: (u32, u32),
+    monitor: (u32, u32),
+) -> (i32, i32) {
+    let max_x = monitor.0.saturating_sub(window.0) as i32;
+    let max_y = monitor.1.saturating_sub(window.1) as i32;
+    (position.0.clamp(0, max_x), position.1.clamp(0, max_y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_emoji_codepoints() {
+        let expected = [
+            (FerrisStage::Egg, '\u{1F95A}'),
+            (FerrisStage::Hatchling, '\u{1F423}'),
+            (FerrisStage::Junior, '\u{1F980}'),
+            (FerrisStage::Senior, '\u{2B50}'),
+            (FerrisStage::King, '\u{1F451}'),
+            (FerrisStage::Emperor, '\u{1F531}'),
+            (FerrisStage::Legend, '\u{1F409}'),
+            (FerrisStage::Mythic, '\u{1F30C}'),
+        ];
+        for (stage, codepoint) in expected {
+            // a single glyph, not UTF-8 bytes decoded as Latin-1
+            assert_eq!(stage.emoji().chars().collect::<Vec<_>>(), vec![codepoint]);
+        }
+    }
+
+    #[test]
+    fn test_clamp_to_monitor() {
+        let window = (WINDOW_WIDTH, WINDOW_HEIGHT);
+        let monitor = (1920, 1080);
+        assert_eq!(clamp_to_monitor((100, 200), window, monitor), (100, 200));
+        // last seen on a second screen to the right
+        assert_eq!(clamp_to_monitor((2500, 300), window, monitor), (1600, 300));
+        assert_eq!(clamp_to_monitor((-40, 900), window, monitor), (0, 540));
+        // window bigger than the screen pins to the corner
+        assert_eq!(clamp_to_monitor((10, 10), window, (300, 400)), (0, 0));
+    }
+
+    #[test]
+    fn test_in_week_order() {
+        let days = [1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(WeekStart::Monday.in_week_order(days), days);
+        assert_eq!(WeekStart::Sunday.in_week_order(days), [7, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_day_of() {
+        let at = |d, h| {
+            NaiveDate::from_ymd_opt(2026, 3, d)
+                .unwrap()
+                .and_hms_opt(h, 30, 0)
+                .unwrap()
+        };
+        let date = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        assert_eq!(day_of(at(5, 1), 0), date(5));
+        assert_eq!(day_of(at(5, 1), 4), date(4));
+        assert_eq!(day_of(at(5, 3), 4), date(4));
+        assert_eq!(day_of(at(5, 4), 4), date(5));
+        assert_eq!(day_of(at(5, 23), 4), date(5));
+    }
+
+    #[test]
+    fn test_timer_config_validate() {
+        assert_eq!(TimerConfig::default().validate(), Ok(()));
+        let with = |f: fn(&mut TimerConfig)| {
+            let mut config = TimerConfig::default();
+            f(&mut config);
+            config.validate()
+        };
+        assert_eq!(
+            with(|c| c.focus_secs = 0),
+            Err(ConfigError::Duration(SessionType::Focus, 0))
+        );
+        assert_eq!(
+            with(|c| c.long_break_secs = u32::MAX),
+            Err(ConfigError::Duration(SessionType::LongBreak, u32::MAX))
+        );
+        assert_eq!(
+            with(|c| c.short_break_secs = MAX_DURATION_SECS + 1),
+            Err(ConfigError::Duration(
+                SessionType::ShortBreak,
+                MAX_DURATION_SECS + 1
+            ))
+        );
+        assert_eq!(
+            with(|c| c.sessions_before_long_break = 0),
+            Err(ConfigError::Cadence(0))
+        );
+        assert_eq!(with(|c| c.focus_secs = MIN_DURATION_SECS), Ok(()));
+    }
+
+    #[test]
+    fn test_utc_offset_secs() {
+        let at = NaiveDate::from_ymd_opt(2026, 3, 29)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let cet = chrono::FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(utc_offset_secs(&cet, at), Some(3600));
+        assert_eq!(utc_offset_secs(&chrono::Utc, at), Some(0));
+    }
+
+    #[test]
+    fn test_is_daytime() {
+        let mut settings = Settings::default();
+        assert!(!settings.is_daytime(6));
+        assert!(settings.is_daytime(7));
+        assert!(settings.is_daytime(18));
+        assert!(!settings.is_daytime(19));
+
+        // night shift: light from 20:00 until 08:00
+        settings.light_from_hour = 20;
+        settings.dark_from_hour = 8;
+        assert!(settings.is_daytime(23));
+        assert!(settings.is_daytime(3));
+        assert!(!settings.is_daytime(12));
+    }
+
+    #[test]
+    fn test_presets() {
+        let mut settings = Settings::default();
+        assert!(settings.select_preset("Deep Work"));
+        assert_eq!(settings.timer.focus_secs, 90 * 60);
+        assert!(!settings.select_preset("Nope"));
+        assert_eq!(settings.active_preset.as_deref(), Some("Deep Work"));
+
+        settings.timer.focus_secs = 50 * 60;
+        settings.match_preset();
+        assert_eq!(settings.active_preset, None);
+
+        settings.save_preset("Fifty");
+        assert_eq!(settings.presets.len(), 4);
+        settings.timer = TimerConfig::default();
+        settings.match_preset();
+        assert_eq!(settings.active_preset.as_deref(), Some("Pomodoro"));
+
+        settings.delete_active_preset();
+        assert_eq!(settings.active_preset, None);
+        assert!(!settings.presets.iter().any(|p| p.name == "Pomodoro"));
+        assert!(settings.select_preset("Fifty"));
+        assert_eq!(settings.timer.focus_secs, 50 * 60);
+    }
+
+    #[test]
+    fn test_week_start_labels() {
+        assert_eq!(WeekStart::Monday.day_labels()[0], "Mon");
+        assert_eq!(WeekStart::Sunday.day_labels()[0], "Sun");
+        assert_eq!(
+            WeekStart::Sunday.day_labels()[1..],
+            WeekStart::Monday.day_labels()[..6]
+        );
+
+        // every date lands under its own weekday's label
+        let sunday = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        for week_start in WeekStart::ALL {
+            for offset in 0..7 {
+                let date = sunday + chrono::Duration::days(offset);
+                let label = week_start.day_labels()[week_start.days_into_week(date) as usize];
+                assert_eq!(label, date.format("%a").to_string());
+            }
+        }
+    }
+}
@@ -1,52 +1,364 @@
-use crate::models::{FerrisStage, SessionType};
-
-pub fn notify_level_up(new_level: u32, stage: FerrisStage) {
-    let title = format!("🎉 Level Up! Level {}", new_level);
-    let body = format!(
-        "Your Ferris has hatched into a {}! {}",
-        stage.label(),
-        stage.emoji()
+use std::io::Cursor;
+use std::sync::OnceLock;
+
+use iced::futures::channel::mpsc;
+use iced::futures::{stream, Stream, StreamExt};
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::achievements::Achievement;
+use crate::i18n::{self, tr, trf};
+use crate::models::{SessionType, Settings, UserProfile};
+use crate::xp;
+
+/// Hands out clicks on a notification's "Start ..." button.
+pub type ActionSender = mpsc::UnboundedSender<()>;
+
+#[derive(Debug, Clone)]
+pub enum ActionEvent {
+    /// Sender to pass to `notify_session_complete`
+    Ready(ActionSender),
+    StartNext,
+}
+
+/// Emits `Ready` once, then `StartNext` for every clicked action.
+pub fn action_events() -> impl Stream<Item = ActionEvent> {
+    let (sender, receiver) = mpsc::unbounded();
+    stream::once(async { ActionEvent::Ready(sender) })
+        .chain(receiver.map(|()| ActionEvent::StartNext))
+}
+
+const FOCUS_COMPLETE_WAV: &[u8] = include_bytes!("../assets/sounds/focus_complete.wav");
+const BREAK_COMPLETE_WAV: &[u8] = include_bytes!("../assets/sounds/break_complete.wav");
+const START_NEXT_ACTION: &str = "start-next";
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Runs `job` on the notification thread: `show` can block on some backends,
+/// and one thread keeps notifications in the order they were sent.
+fn post(job: impl FnOnce() + Send + 'static) {
+    static QUEUE: OnceLock<std::sync::mpsc::Sender<Job>> = OnceLock::new();
+    let queue = QUEUE.get_or_init(|| {
+        let (sender, receiver) = std::sync::mpsc::channel::<Job>();
+        std::thread::spawn(move || receiver.into_iter().for_each(|job| job()));
+        sender
+    });
+    let _ = queue.send(Box::new(job));
+}
+
+fn show(notification: notify_rust::Notification, what: &'static str) {
+    post(move || {
+        if let Err(e) = notification.show() {
+            eprintln!("Failed to send {}: {}", what, e);
+        }
+    });
+}
+
+/// Whether OS notifications and sounds may go out at `time`.
+pub fn notifications_allowed(settings: &Settings, time: NaiveTime) -> bool {
+    !settings.is_quiet_hour(time.hour())
+}
+
+fn allowed_now(settings: &Settings) -> bool {
+    notifications_allowed(settings, Local::now().time())
+}
+
+/// Whether the once-a-day streak reminder should go out at `now`.
+pub fn streak_reminder_due(
+    settings: &Settings,
+    profile: &UserProfile,
+    last_reminder: Option<NaiveDate>,
+    now: NaiveDateTime,
+) -> bool {
+    let today = settings.day_of(now);
+    settings.streak_reminder_enabled
+        && last_reminder != Some(today)
+        && now.hour() >= settings.streak_reminder_hour
+        && notifications_allowed(settings, now.time())
+        && xp::streak_at_risk(profile.last_session_date, today, profile.current_streak)
+}
+
+pub fn notify_streak_at_risk(streak: u32) {
+    let body = trf("notify.streak_body", &[("streak", streak.to_string())]);
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .summary(tr("notify.streak_title"))
+        .body(&body)
+        .appname("Ferris Focus")
+        .timeout(8000);
+    show(notification, "streak reminder");
+}
+
+/// Fills `{name}` placeholders from `vars`; an empty template uses `default`.
+/// Unknown placeholders are left as typed.
+pub fn fill_template(template: &str, default: &str, vars: &[(&str, String)]) -> String {
+    let template = if template.trim().is_empty() {
+        default
+    } else {
+        template
+    };
+    i18n::fill(template, vars)
+}
+
+fn template_vars(xp: Option<u32>, profile: &UserProfile) -> Vec<(&'static str, String)> {
+    let stage = xp::ferris_stage(profile.level);
+    vec![
+        ("xp", xp.unwrap_or(0).to_string()),
+        ("level", profile.level.to_string()),
+        ("stage", stage.label().to_string()),
+        ("emoji", stage.emoji().to_string()),
+        ("streak", profile.current_streak.to_string()),
+    ]
+}
+
+pub fn notify_level_up(profile: &UserProfile, settings: &Settings) {
+    if !allowed_now(settings) {
+        return;
+    }
+
+    let title = trf(
+        "notify.level_up_title",
+        &[("level", profile.level.to_string())],
+    );
+    let body = fill_template(
+        &settings.level_up_message,
+        tr("notify.level_up_body"),
+        &template_vars(None, profile),
     );
 
-    if let Err(e) = notify_rust::Notification::new()
+    let mut notification = notify_rust::Notification::new();
+    notification
         .summary(&title)
         .body(&body)
         .appname("Ferris Focus")
-        .timeout(8000)
-        .show()
-    {
-        eprintln!("Failed to send level-up notification: {}", e);
-    }
-}
-
-pub fn notify_session_complete(session_type: SessionType, xp_earned: Option<u32>) {
-    let (title, body) = match session_type {
-        SessionType::Focus => {
-            let xp_msg = xp_earned
-                .map(|xp| format!(" +{} XP!", xp))
-                .unwrap_or_default();
-            (
-                "🦀 Focus Complete!".to_string(),
-                format!("Great work! Time for a break.{}", xp_msg),
-            )
-        }
+        .timeout(8000);
+    show(notification, "level-up notification");
+}
+
+pub fn notify_achievement(achievement: Achievement, settings: &Settings) {
+    if !allowed_now(settings) {
+        return;
+    }
+
+    let title = trf(
+        "notify.achievement_title",
+        &[("emoji", achievement.emoji().to_string())],
+    );
+    let body = format!("{}: {}", achievement.label(), achievement.description());
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .summary(&title)
+        .body(&body)
+        .appname("Ferris Focus")
+        .timeout(8000);
+    show(notification, "achievement notification");
+}
+
+pub fn notify_weekly_goal(goal: u32, settings: &Settings) {
+    if !allowed_now(settings) {
+        return;
+    }
+
+    let body = trf("notify.weekly_goal_body", &[("goal", goal.to_string())]);
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .summary(tr("notify.weekly_goal_title"))
+        .body(&body)
+        .appname("Ferris Focus")
+        .timeout(8000);
+    show(notification, "weekly goal notification");
+}
+
+pub fn notify_idle_paused(settings: &Settings) {
+    if !allowed_now(settings) {
+        return;
+    }
+
+    let minutes = (settings.idle_threshold_secs / 60).to_string();
+    let body = trf("notify.idle_body", &[("minutes", minutes)]);
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .summary(tr("notify.idle_title"))
+        .body(&body)
+        .appname("Ferris Focus")
+        .timeout(8000);
+    show(notification, "idle pause notification");
+}
+
+pub fn notify_session_complete(
+    session_type: SessionType,
+    xp_earned: Option<u32>,
+    profile: &UserProfile,
+    actions: Option<ActionSender>,
+    settings: &Settings,
+) {
+    if !allowed_now(settings) {
+        return;
+    }
+
+    let (title, template, default) = match session_type {
+        SessionType::Focus => (
+            tr("notify.focus_title"),
+            &settings.focus_complete_message,
+            if xp_earned.is_some() {
+                tr("notify.focus_body_xp")
+            } else {
+                tr("notify.focus_body")
+            },
+        ),
         SessionType::ShortBreak => (
-            "☕ Break Over!".to_string(),
-            "Ready to focus again?".to_string(),
+            tr("notify.break_title"),
+            &settings.break_over_message,
+            tr("notify.break_body"),
         ),
         SessionType::LongBreak => (
-            "🎉 Long Break Over!".to_string(),
-            "You've earned it! Ready to start a new cycle?".to_string(),
+            tr("notify.long_break_title"),
+            &settings.break_over_message,
+            tr("notify.long_break_body"),
         ),
     };
+    let body = fill_template(template, default, &template_vars(xp_earned, profile));
 
-    if let Err(e) = notify_rust::Notification::new()
-        .summary(&title)
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .summary(title)
         .body(&body)
         .appname("Ferris Focus")
-        .timeout(5000)
-        .show()
-    {
-        eprintln!("Failed to send notification: {}", e);
+        .timeout(5000);
+
+    let label = match session_type {
+        SessionType::Focus => tr("notify.start_break"),
+        SessionType::ShortBreak | SessionType::LongBreak => tr("notify.start_focus"),
+    };
+    if actions.is_some() {
+        notification.action(START_NEXT_ACTION, label);
+    }
+
+    post(move || match notification.show() {
+        Ok(handle) => {
+            if let Some(sender) = actions {
+                forward_action(handle, sender);
+            }
+        }
+        Err(e) => eprintln!("Failed to send notification: {}", e),
+    });
+}
+
+/// Waiting for the click blocks, so it gets its own thread.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn forward_action(handle: notify_rust::NotificationHandle, sender: ActionSender) {
+    std::thread::spawn(move || {
+        handle.wait_for_action(|action| {
+            if action == START_NEXT_ACTION {
+                let _ = sender.unbounded_send(());
+            }
+        });
+    });
+}
+
+/// Actions aren't supported by the platform notification backend.
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn forward_action(_handle: notify_rust::NotificationHandle, _sender: ActionSender) {}
+
+pub fn play_completion_sound(session_type: SessionType, settings: &Settings) {
+    if !settings.sound_enabled || !allowed_now(settings) {
+        return;
+    }
+
+    let wav = match session_type {
+        SessionType::Focus => FOCUS_COMPLETE_WAV,
+        SessionType::ShortBreak | SessionType::LongBreak => BREAK_COMPLETE_WAV,
+    };
+
+    // the output stream must outlive playback, so both live on a short-lived thread
+    std::thread::spawn(move || {
+        let mut stream = match rodio::OutputStreamBuilder::open_default_stream() {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Failed to open audio device: {}", e);
+                return;
+            }
+        };
+        stream.log_on_drop(false);
+
+        match rodio::play(stream.mixer(), Cursor::new(wav)) {
+            Ok(sink) => sink.sleep_until_end(),
+            Err(e) => eprintln!("Failed to play completion sound: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hours() {
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let mut settings = Settings::default();
+        assert!(notifications_allowed(&settings, at(23, 30)));
+
+        settings.quiet_hours_enabled = true;
+        assert!(notifications_allowed(&settings, at(21, 59)));
+        assert!(!notifications_allowed(&settings, at(22, 0)));
+        assert!(!notifications_allowed(&settings, at(3, 15)));
+        assert!(!notifications_allowed(&settings, at(6, 59)));
+        assert!(notifications_allowed(&settings, at(7, 0)));
+
+        // same-day range
+        settings.quiet_from_hour = 12;
+        settings.quiet_until_hour = 14;
+        assert!(!notifications_allowed(&settings, at(13, 0)));
+        assert!(notifications_allowed(&settings, at(14, 0)));
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let profile = UserProfile {
+            level: 4,
+            current_streak: 3,
+            ..UserProfile::default()
+        };
+        let vars = template_vars(Some(130), &profile);
+        assert_eq!(
+            fill_template("+{xp} XP, {streak} days, Lv {level} {stage}", "", &vars),
+            "+130 XP, 3 days, Lv 4 Junior Crab"
+        );
+        // empty or blank falls back to the default
+        assert_eq!(fill_template("  ", "Lv {level}", &vars), "Lv 4");
+        assert_eq!(fill_template("{xp}{xp} {nope}", "", &vars), "130130 {nope}");
+    }
+
+    #[test]
+    fn test_streak_reminder_due() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let at = |h| today.and_hms_opt(h, 0, 0).unwrap();
+        let mut settings = Settings::default();
+        let profile = UserProfile {
+            current_streak: 6,
+            last_session_date: today.pred_opt(),
+            ..UserProfile::default()
+        };
+
+        assert!(!streak_reminder_due(&settings, &profile, None, at(19)));
+        assert!(streak_reminder_due(&settings, &profile, None, at(20)));
+        // once a day
+        assert!(!streak_reminder_due(
+            &settings,
+            &profile,
+            Some(today),
+            at(21)
+        ));
+
+        settings.quiet_hours_enabled = true;
+        settings.quiet_from_hour = 20;
+        assert!(!streak_reminder_due(&settings, &profile, None, at(21)));
+
+        settings.quiet_hours_enabled = false;
+        let done_today = UserProfile {
+            last_session_date: Some(today),
+            ..profile
+        };
+        assert!(!streak_reminder_due(&settings, &done_today, None, at(21)));
     }
 }
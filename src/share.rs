@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+
+use crate::engine::Stats;
+use crate::i18n::trf;
+use crate::models::UserProfile;
+use crate::xp;
+
+/// One line of progress for pasting into a chat.
+pub fn summary(profile: &UserProfile, stats: &Stats, today: NaiveDate) -> String {
+    let stage = xp::ferris_stage(profile.level);
+    trf(
+        "share.summary",
+        &[
+            ("emoji", stage.emoji().to_string()),
+            ("level", profile.level.to_string()),
+            ("stage", stage.label().to_string()),
+            ("streak", profile.current_streak.to_string()),
+            ("h", (stats.total_focus_secs / 3600).to_string()),
+            ("n", stats.total_sessions.to_string()),
+            ("date", today.format("%Y-%m-%d").to_string()),
+        ],
+    )
+}
+
+/// Encodes raw RGBA pixels, e.g. a window screenshot, as a PNG file.
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>, String> {
+    if rgba.len() != (width as usize) * (height as usize) * 4 {
+        return Err(format!(
+            "expected {}x{} RGBA pixels, got {} bytes",
+            width,
+            height,
+            rgba.len()
+        ));
+    }
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(rgba).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let profile = UserProfile {
+            level: 3,
+            current_streak: 5,
+            ..UserProfile::default()
+        };
+        let stats = Stats {
+            total_sessions: 12,
+            total_focus_secs: 5 * 3600 + 30 * 60,
+            ..Stats::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let stage = xp::ferris_stage(3);
+        assert_eq!(
+            summary(&profile, &stats, today),
+            format!(
+                "{} Level 3 {} • 🔥 5-day streak • 5h focused • 12 sessions • 2026-03-14",
+                stage.emoji(),
+                stage.label()
+            )
+        );
+    }
+
+    #[test]
+    fn test_encode_png() {
+        let rgba = [255, 0, 0, 255, 0, 0, 255, 128];
+        let bytes = encode_png(2, 1, &rgba).unwrap();
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(pixels, rgba);
+
+        assert!(encode_png(2, 2, &rgba).is_err());
+    }
+}
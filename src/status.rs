@@ -0,0 +1,90 @@
+use serde::Serialize;
+use std::io;
+use std::path::PathBuf;
+
+use crate::timer::{Timer, TimerState};
+
+/// The timer as status bars (waybar, polybar, ...) see it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Status {
+    /// idle, running, paused, finished or counting
+    pub state: &'static str,
+    pub remaining_secs: u32,
+    pub session_type: Option<&'static str>,
+    pub today_sessions: u32,
+}
+
+impl Status {
+    pub fn new(timer: &Timer, today_sessions: u32) -> Self {
+        let state = match timer.state {
+            TimerState::Idle => "idle",
+            TimerState::Running { .. } => "running",
+            TimerState::Paused { .. } => "paused",
+            TimerState::Finished { .. } => "finished",
+            TimerState::Counting { .. } => "counting",
+        };
+        Status {
+            state,
+            remaining_secs: timer.remaining_secs(),
+            session_type: timer.current_session_type().map(|t| t.as_str()),
+            today_sessions,
+        }
+    }
+}
+
+/// `~/.local/state/ferris-focus/status.json` on Linux, the local data
+/// directory elsewhere.
+pub fn status_path() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ferris-focus")
+        .join("status.json")
+}
+
+/// Replaces the status file in one step, so a poller never reads half of it.
+pub fn write(status: &Status) -> io::Result<()> {
+    let path = status_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string(status)?;
+    let partial = path.with_extension("json.tmp");
+    std::fs::write(&partial, json)?;
+    std::fs::rename(&partial, &path)
+}
+
+/// Removes the status file, e.g. when the app exits or the file is turned off.
+pub fn clear() {
+    let _ = std::fs::remove_file(status_path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SessionType, TimerConfig};
+
+    #[test]
+    fn test_status() {
+        let mut timer = Timer::new();
+        assert_eq!(
+            Status::new(&timer, 2),
+            Status {
+                state: "idle",
+                remaining_secs: 0,
+                session_type: None,
+                today_sessions: 2,
+            }
+        );
+
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        let status = Status::new(&timer, 2);
+        assert_eq!(status.state, "running");
+        assert_eq!(status.remaining_secs, 25 * 60);
+        assert_eq!(status.session_type, Some("focus"));
+        assert_eq!(
+            serde_json::to_string(&status).unwrap(),
+            r#"{"state":"running","remaining_secs":1500,"session_type":"focus","today_sessions":2}"#
+        );
+    }
+}
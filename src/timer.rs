@@ -1,6 +1,10 @@
-use crate::models::{
-    SessionType, FOCUS_DURATION_SECS, LONG_BREAK_SECS, SESSIONS_BEFORE_LONG_BREAK, SHORT_BREAK_SECS,
-};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::time::{Duration, Instant};
+
+use crate::models::{CycleMode, SessionType, TimerConfig};
+
+/// A gap between ticks longer than this means the machine was most likely asleep.
+pub const SUSPEND_GAP: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone)]
 pub enum TimerState {
@@ -16,12 +20,95 @@ pub enum TimerState {
     Finished {
         session_type: SessionType,
     },
+    /// open-ended focus that counts up until stopped
+    Counting {
+        elapsed_secs: u32,
+    },
+}
+
+/// What's needed to pick a countdown back up after a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    pub session_type: SessionType,
+    /// wall-clock deadline of a running session; `None` while paused
+    pub ends_at: Option<NaiveDateTime>,
+    pub remaining_secs: u32,
+    pub config: TimerConfig,
+    pub extended_secs: u32,
+    pub interruptions: u32,
+}
+
+impl Snapshot {
+    /// Whether both describe the same countdown, allowing for the clock
+    /// having moved on between them.
+    pub fn same_course(&self, other: &Snapshot) -> bool {
+        let deadline_matches = match (self.ends_at, other.ends_at) {
+            (Some(a), Some(b)) => (a - b).num_seconds().abs() <= 1,
+            (None, None) => self.remaining_secs == other.remaining_secs,
+            _ => false,
+        };
+        deadline_matches
+            && self.session_type == other.session_type
+            && self.config == other.config
+            && self.extended_secs == other.extended_secs
+            && self.interruptions == other.interruptions
+    }
+}
+
+/// Where the long-break cycle stands. It's saved as it changes, so a restart
+/// picks up mid-cycle; only `reset_cycle_daily` starts it over with the day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CycleProgress {
+    pub focus_sessions_completed: u32,
+    pub cycle_sessions: u32,
+    pub cycle_focus_secs: u32,
+    /// the cycle a long break under way closed, still to be summed up
+    pub finished_cycle: Option<CycleTotals>,
+    /// day the cycle last started over
+    pub reset_date: Option<NaiveDate>,
+}
+
+/// The focus that led up to a long break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleTotals {
+    pub sessions: u32,
+    pub focus_secs: u32,
 }
 
 #[derive(Debug, Clone)]
 pub struct Timer {
     pub state: TimerState,
+    /// finished focus sessions the `Running` cadence counts off. Resetting the
+    /// cycle, by hand or with the day, zeroes it, so it isn't a lifetime
+    /// total; Stats counts those from the saved sessions.
     pub focus_sessions_completed: u32,
+    /// focus sessions since the last long break started or the cycle was reset
+    pub cycle_sessions: u32,
+    /// focus time in those sessions
+    pub cycle_focus_secs: u32,
+    /// the cycle the current long break closed, until it's taken for a summary
+    pub finished_cycle: Option<CycleTotals>,
+    pub cycle_mode: CycleMode,
+    /// config snapshot taken at `start`, so edits mid-session don't skew progress
+    pub config: TimerConfig,
+    /// seconds spent in the current pause
+    pub pause_secs: u32,
+    /// day the long-break cycle last started over
+    pub last_cycle_reset_date: Option<NaiveDate>,
+    /// wall-clock deadline of the running session; remaining time is derived from it
+    pub session_end_instant: Option<Instant>,
+    /// exact time left when paused, so pausing doesn't round away part of a second
+    paused_remaining: Option<Duration>,
+    /// seconds added to the current session with `extend`
+    pub extended_secs: u32,
+    /// distractions marked during the current focus session
+    pub interruptions: u32,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Timer {
@@ -29,33 +116,157 @@ impl Timer {
         Self {
             state: TimerState::Idle,
             focus_sessions_completed: 0,
+            cycle_sessions: 0,
+            cycle_focus_secs: 0,
+            finished_cycle: None,
+            cycle_mode: CycleMode::Running,
+            config: TimerConfig::default(),
+            pause_secs: 0,
+            last_cycle_reset_date: None,
+            session_end_instant: None,
+            paused_remaining: None,
+            extended_secs: 0,
+            interruptions: 0,
         }
     }
 
-    pub fn start(&mut self, session_type: SessionType) {
-        let duration = match session_type {
-            SessionType::Focus => FOCUS_DURATION_SECS,
-            SessionType::ShortBreak => SHORT_BREAK_SECS,
-            SessionType::LongBreak => LONG_BREAK_SECS,
-        };
+    pub fn start(&mut self, session_type: SessionType, config: &TimerConfig) {
+        self.config = *config;
+        let remaining_secs = config.duration_for(session_type);
+        self.session_end_instant =
+            Some(Instant::now() + Duration::from_secs(remaining_secs as u64));
+        self.paused_remaining = None;
+        self.extended_secs = 0;
+        self.interruptions = 0;
+        if session_type == SessionType::LongBreak {
+            self.finished_cycle = (self.cycle_sessions > 0).then_some(CycleTotals {
+                sessions: self.cycle_sessions,
+                focus_secs: self.cycle_focus_secs,
+            });
+            self.cycle_sessions = 0;
+            self.cycle_focus_secs = 0;
+        }
         self.state = TimerState::Running {
-            remaining_secs: duration,
+            remaining_secs,
             session_type,
         };
     }
 
-    pub fn start_next(&mut self) {
+    /// Adds `secs` to the current session. A finished session runs again,
+    /// and a finished focus session leaves the long-break cycle until it ends
+    /// for good.
+    pub fn extend(&mut self, secs: u32) {
+        self.extend_at(secs, Instant::now());
+    }
+
+    pub fn extend_at(&mut self, secs: u32, now: Instant) {
+        let added = Duration::from_secs(secs as u64);
+        match self.state {
+            TimerState::Running {
+                remaining_secs,
+                session_type,
+            } => {
+                self.session_end_instant = self.session_end_instant.map(|end| end + added);
+                self.state = TimerState::Running {
+                    remaining_secs: remaining_secs + secs,
+                    session_type,
+                };
+            }
+            TimerState::Paused {
+                remaining_secs,
+                session_type,
+            } => {
+                self.paused_remaining = self.paused_remaining.map(|left| left + added);
+                self.state = TimerState::Paused {
+                    remaining_secs: remaining_secs + secs,
+                    session_type,
+                };
+            }
+            TimerState::Finished { session_type } => {
+                if session_type == SessionType::Focus {
+                    self.uncount_focus(self.total_duration_secs());
+                }
+                self.session_end_instant = Some(now + added);
+                self.state = TimerState::Running {
+                    remaining_secs: secs,
+                    session_type,
+                };
+            }
+            TimerState::Idle | TimerState::Counting { .. } => return,
+        }
+        self.extended_secs += secs;
+    }
+
+    /// Goes from a finished focus session straight into the next one, leaving
+    /// the cycle count where finishing left it. Returns false if no break was due.
+    pub fn skip_break(&mut self, config: &TimerConfig) -> bool {
+        let break_due = matches!(
+            self.state,
+            TimerState::Finished {
+                session_type: SessionType::Focus
+            }
+        );
+        if break_due {
+            self.start(SessionType::Focus, config);
+        }
+        break_due
+    }
+
+    /// Starts an open-ended focus session. It doesn't count toward the
+    /// long-break cycle.
+    pub fn start_stopwatch(&mut self, config: &TimerConfig) {
+        self.config = *config;
+        self.session_end_instant = None;
+        self.interruptions = 0;
+        self.state = TimerState::Counting { elapsed_secs: 0 };
+    }
+
+    /// Counts a distraction, if a focus session is under way.
+    pub fn mark_interruption(&mut self) {
+        let in_focus = match self.state {
+            TimerState::Running { session_type, .. } | TimerState::Paused { session_type, .. } => {
+                session_type == SessionType::Focus
+            }
+            TimerState::Counting { .. } => true,
+            TimerState::Idle | TimerState::Finished { .. } => false,
+        };
+        if in_focus {
+            self.interruptions += 1;
+        }
+    }
+
+    /// Ends a stopwatch session, returning the seconds counted.
+    pub fn stop_stopwatch(&mut self) -> Option<u32> {
+        match self.state {
+            TimerState::Counting { elapsed_secs } => {
+                self.state = TimerState::Idle;
+                Some(elapsed_secs)
+            }
+            _ => None,
+        }
+    }
+
+    /// The cadence in `config` decides the next type, so a changed setting
+    /// applies at the very next break.
+    pub fn start_next(&mut self, config: &TimerConfig) {
+        self.config = *config;
         let next_type = self.next_session_type();
-        self.start(next_type);
+        self.start(next_type, config);
     }
 
     pub fn next_session_type(&self) -> SessionType {
         match &self.state {
             TimerState::Finished { session_type } => match session_type {
                 SessionType::Focus => {
-                    if self.focus_sessions_completed > 0
-                        && self.focus_sessions_completed % SESSIONS_BEFORE_LONG_BREAK == 0
-                    {
+                    let cadence = self.config.sessions_before_long_break.max(1);
+                    let long_break_due = match self.cycle_mode {
+                        CycleMode::Running => {
+                            self.focus_sessions_completed > 0
+                                && self.focus_sessions_completed.is_multiple_of(cadence)
+                        }
+                        CycleMode::FullCycle => self.cycle_sessions >= cadence,
+                    };
+                    if long_break_due {
                         SessionType::LongBreak
                     } else {
                         SessionType::ShortBreak
@@ -67,48 +278,144 @@ impl Timer {
         }
     }
 
+    /// Focus time left before the next long break, counting what's left of the
+    /// current focus session.
+    pub fn focus_secs_until_long_break(&self, config: &TimerConfig) -> u32 {
+        if self.next_session_type() == SessionType::LongBreak {
+            return 0;
+        }
+        let cadence = config.sessions_before_long_break.max(1);
+        // a focus session past a full cycle still has a long break after it
+        let sessions_left = cadence.saturating_sub(self.cycle_position(cadence)).max(1);
+        match &self.state {
+            TimerState::Running {
+                remaining_secs,
+                session_type: SessionType::Focus,
+            }
+            | TimerState::Paused {
+                remaining_secs,
+                session_type: SessionType::Focus,
+            } => remaining_secs + (sessions_left - 1) * config.focus_secs,
+            _ => sessions_left * config.focus_secs,
+        }
+    }
+
     /// returns true if session just finished
     pub fn tick(&mut self) -> bool {
+        self.tick_at(Instant::now())
+    }
+
+    /// Recomputes the remaining time from the wall clock, so a late or missed
+    /// tick doesn't lose seconds.
+    pub fn tick_at(&mut self, now: Instant) -> bool {
+        if let TimerState::Counting { elapsed_secs } = &mut self.state {
+            *elapsed_secs += 1;
+            return false;
+        }
         if let TimerState::Running {
             remaining_secs,
             session_type,
         } = &self.state
         {
             let session_type = *session_type;
-            if *remaining_secs <= 1 {
+            let remaining_secs = match self.session_end_instant {
+                Some(end) => whole_secs(end.saturating_duration_since(now)),
+                // no deadline to go by, so count the tick itself
+                None => remaining_secs.saturating_sub(1),
+            };
+            if remaining_secs == 0 {
                 if session_type == SessionType::Focus {
                     self.focus_sessions_completed += 1;
+                    self.cycle_sessions += 1;
+                    self.cycle_focus_secs += self.total_duration_secs();
                 }
+                self.session_end_instant = None;
                 self.state = TimerState::Finished { session_type };
                 return true;
             }
             self.state = TimerState::Running {
-                remaining_secs: remaining_secs - 1,
+                remaining_secs,
                 session_type,
             };
         }
         false
     }
 
+    /// Catches the deadline up after a gap between ticks, e.g. a suspend.
+    /// `wall_elapsed` is the time since the last tick by the system clock and
+    /// `mono_elapsed` by `Instant`, which doesn't advance during suspend on
+    /// some platforms. Returns true if the session ran out during the gap.
+    pub fn reconcile_gap(
+        &mut self,
+        wall_elapsed: Duration,
+        mono_elapsed: Duration,
+        now: Instant,
+    ) -> bool {
+        if wall_elapsed < SUSPEND_GAP || !self.is_running() {
+            return false;
+        }
+        let Some(end) = self.session_end_instant else {
+            return false;
+        };
+        let unseen = wall_elapsed.saturating_sub(mono_elapsed);
+        let end = end.checked_sub(unseen).unwrap_or(now);
+        self.session_end_instant = Some(end);
+        end <= now
+    }
+
     pub fn pause(&mut self) {
+        self.pause_at(Instant::now());
+    }
+
+    pub fn pause_at(&mut self, now: Instant) {
         if let TimerState::Running {
             remaining_secs,
             session_type,
         } = &self.state
         {
+            let remaining = match self.session_end_instant.take() {
+                Some(end) => end.saturating_duration_since(now),
+                None => Duration::from_secs(*remaining_secs as u64),
+            };
             self.state = TimerState::Paused {
-                remaining_secs: *remaining_secs,
+                remaining_secs: whole_secs(remaining),
                 session_type: *session_type,
             };
+            self.paused_remaining = Some(remaining);
+            self.pause_secs = 0;
+        }
+    }
+
+    /// Pauses as of `idle` ago, when input stopped, so the time away isn't
+    /// counted as focus. Never goes back further than the session's start.
+    pub fn pause_idle(&mut self, idle: Duration, now: Instant) {
+        let away = idle.min(Duration::from_secs(self.elapsed_secs() as u64));
+        self.pause_at(now.checked_sub(away).unwrap_or(now));
+    }
+
+    /// counts a second of pause; returns how long the timer has been paused
+    pub fn tick_paused(&mut self) -> u32 {
+        if self.is_paused() {
+            self.pause_secs += 1;
         }
+        self.pause_secs
     }
 
     pub fn resume(&mut self) {
+        self.resume_at(Instant::now());
+    }
+
+    pub fn resume_at(&mut self, now: Instant) {
         if let TimerState::Paused {
             remaining_secs,
             session_type,
         } = &self.state
         {
+            let remaining = self
+                .paused_remaining
+                .take()
+                .unwrap_or(Duration::from_secs(*remaining_secs as u64));
+            self.session_end_instant = Some(now + remaining);
             self.state = TimerState::Running {
                 remaining_secs: *remaining_secs,
                 session_type: *session_type,
@@ -116,46 +423,186 @@ impl Timer {
         }
     }
 
+    /// Starts the long-break cycle over the first time it's called on a new day.
+    pub fn reset_cycle_if_new_day(&mut self, today: NaiveDate) -> bool {
+        if self.last_cycle_reset_date == Some(today) {
+            return false;
+        }
+        self.last_cycle_reset_date = Some(today);
+        self.reset_cycle();
+        true
+    }
+
+    /// Puts the next long break a full cycle away; a running session carries on.
+    /// With `FullCycle` the running total is left alone.
+    pub fn reset_cycle(&mut self) {
+        self.cycle_sessions = 0;
+        self.cycle_focus_secs = 0;
+        if self.cycle_mode == CycleMode::Running {
+            self.focus_sessions_completed = 0;
+        }
+    }
+
+    pub fn cycle_progress(&self) -> CycleProgress {
+        CycleProgress {
+            focus_sessions_completed: self.focus_sessions_completed,
+            cycle_sessions: self.cycle_sessions,
+            cycle_focus_secs: self.cycle_focus_secs,
+            finished_cycle: self.finished_cycle,
+            reset_date: self.last_cycle_reset_date,
+        }
+    }
+
+    /// Picks the cycle back up where a previous run left it.
+    pub fn restore_cycle(&mut self, progress: CycleProgress) {
+        self.focus_sessions_completed = progress.focus_sessions_completed;
+        self.cycle_sessions = progress.cycle_sessions;
+        self.cycle_focus_secs = progress.cycle_focus_secs;
+        self.finished_cycle = progress.finished_cycle;
+        self.last_cycle_reset_date = progress.reset_date;
+    }
+
+    /// Focus sessions done in the current cycle of `cadence`.
+    pub fn cycle_position(&self, cadence: u32) -> u32 {
+        let cadence = cadence.max(1);
+        match self.cycle_mode {
+            CycleMode::Running => self.focus_sessions_completed % cadence,
+            CycleMode::FullCycle => self.cycle_sessions.min(cadence),
+        }
+    }
+
+    /// Takes back a completed focus session of `duration_secs`, e.g. one
+    /// being undone or extended.
+    pub fn uncount_focus(&mut self, duration_secs: u32) {
+        self.focus_sessions_completed = self.focus_sessions_completed.saturating_sub(1);
+        self.cycle_sessions = self.cycle_sessions.saturating_sub(1);
+        self.cycle_focus_secs = self.cycle_focus_secs.saturating_sub(duration_secs);
+    }
+
+    /// Drops the current session unfinished. Only focus sessions that run to
+    /// the end count toward the long-break cycle, so skipping one leaves the
+    /// count where it was: the long break still comes after the configured
+    /// number of finished sessions, not of sessions started.
     pub fn reset(&mut self) {
         self.state = TimerState::Idle;
+        self.session_end_instant = None;
+        self.paused_remaining = None;
     }
 
-    pub fn remaining_display(&self) -> (u32, u32) {
-        let secs = match &self.state {
-            TimerState::Running { remaining_secs, .. } => *remaining_secs,
-            TimerState::Paused { remaining_secs, .. } => *remaining_secs,
-            _ => 0,
+    /// The running or paused countdown as of `now`, `None` for anything else.
+    pub fn snapshot(&self, now: NaiveDateTime) -> Option<Snapshot> {
+        let session_type = match self.state {
+            TimerState::Running { session_type, .. } | TimerState::Paused { session_type, .. } => {
+                session_type
+            }
+            _ => return None,
         };
+        Some(Snapshot {
+            session_type,
+            ends_at: self.ends_at(now),
+            remaining_secs: self.remaining_secs(),
+            config: self.config,
+            extended_secs: self.extended_secs,
+            interruptions: self.interruptions,
+        })
+    }
+
+    /// Picks a saved countdown back up. One whose deadline passed in the
+    /// meantime comes back running with nothing left, so the next tick finishes it.
+    pub fn restore(&mut self, snapshot: &Snapshot, now: NaiveDateTime) {
+        self.restore_at(snapshot, now, Instant::now());
+    }
+
+    pub fn restore_at(&mut self, snapshot: &Snapshot, now: NaiveDateTime, now_instant: Instant) {
+        self.config = snapshot.config;
+        self.extended_secs = snapshot.extended_secs;
+        self.interruptions = snapshot.interruptions;
+        self.pause_secs = 0;
+        let session_type = snapshot.session_type;
+        match snapshot.ends_at {
+            Some(ends_at) => {
+                let left = (ends_at - now).to_std().unwrap_or_default();
+                self.session_end_instant = Some(now_instant + left);
+                self.paused_remaining = None;
+                self.state = TimerState::Running {
+                    remaining_secs: whole_secs(left),
+                    session_type,
+                };
+            }
+            None => {
+                self.session_end_instant = None;
+                self.paused_remaining = Some(Duration::from_secs(snapshot.remaining_secs as u64));
+                self.state = TimerState::Paused {
+                    remaining_secs: snapshot.remaining_secs,
+                    session_type,
+                };
+            }
+        }
+    }
+
+    /// Seconds left in a running or paused countdown, zero otherwise.
+    pub fn remaining_secs(&self) -> u32 {
+        match self.state {
+            TimerState::Running { remaining_secs, .. }
+            | TimerState::Paused { remaining_secs, .. } => remaining_secs,
+            _ => 0,
+        }
+    }
+
+    pub fn remaining_display(&self) -> (u32, u32) {
+        let secs = self.remaining_secs();
         (secs / 60, secs % 60)
     }
 
-    pub fn total_duration_secs(&self) -> u32 {
-        let session_type = match &self.state {
-            TimerState::Running { session_type, .. } => Some(session_type),
-            TimerState::Paused { session_type, .. } => Some(session_type),
+    /// When a running session will end by the clock, given the time `now`.
+    pub fn ends_at(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self.state {
+            TimerState::Running { remaining_secs, .. } => {
+                Some(now + chrono::Duration::seconds(remaining_secs as i64))
+            }
             _ => None,
-        };
-        match session_type {
-            Some(SessionType::Focus) => FOCUS_DURATION_SECS,
-            Some(SessionType::ShortBreak) => SHORT_BREAK_SECS,
-            Some(SessionType::LongBreak) => LONG_BREAK_SECS,
-            None => FOCUS_DURATION_SECS,
         }
     }
 
+    /// Seconds into the current session, whether counted up by a stopwatch
+    /// or gone from a countdown.
+    pub fn elapsed_secs(&self) -> u32 {
+        match self.state {
+            TimerState::Idle => 0,
+            TimerState::Counting { elapsed_secs } => elapsed_secs,
+            _ => self
+                .total_duration_secs()
+                .saturating_sub(self.remaining_secs()),
+        }
+    }
+
+    pub fn total_duration_secs(&self) -> u32 {
+        if let TimerState::Counting { elapsed_secs } = self.state {
+            return elapsed_secs;
+        }
+        let session_type = self.current_session_type().unwrap_or(SessionType::Focus);
+        self.config.duration_for(session_type) + self.extended_secs
+    }
+
     /// 0.0..1.0 elapsed fraction
     pub fn progress(&self) -> f32 {
-        let remaining = match &self.state {
-            TimerState::Running { remaining_secs, .. } => *remaining_secs,
-            TimerState::Paused { remaining_secs, .. } => *remaining_secs,
-            TimerState::Finished { .. } => 0,
-            TimerState::Idle => return 0.0,
-        };
         let total = self.total_duration_secs();
-        if total == 0 {
+        if total == 0 || matches!(self.state, TimerState::Idle | TimerState::Counting { .. }) {
             return 0.0;
         }
-        1.0 - (remaining as f32 / total as f32)
+        1.0 - (self.remaining_secs() as f32 / total as f32)
+    }
+
+    /// Like `progress`, but read off the deadline so it moves between ticks.
+    pub fn progress_at(&self, now: Instant) -> f32 {
+        let total = self.total_duration_secs();
+        match (&self.state, self.session_end_instant) {
+            (TimerState::Running { .. }, Some(end)) if total > 0 => {
+                let remaining = end.saturating_duration_since(now).as_secs_f32();
+                (1.0 - remaining / total as f32).clamp(0.0, 1.0)
+            }
+            _ => self.progress(),
+        }
     }
 
     pub fn current_session_type(&self) -> Option<SessionType> {
@@ -163,6 +610,7 @@ impl Timer {
             TimerState::Running { session_type, .. } => Some(*session_type),
             TimerState::Paused { session_type, .. } => Some(*session_type),
             TimerState::Finished { session_type } => Some(*session_type),
+            TimerState::Counting { .. } => Some(SessionType::Focus),
             TimerState::Idle => None,
         }
     }
@@ -178,20 +626,106 @@ impl Timer {
     pub fn is_finished(&self) -> bool {
         matches!(self.state, TimerState::Finished { .. })
     }
+
+    pub fn is_counting(&self) -> bool {
+        matches!(self.state, TimerState::Counting { .. })
+    }
+}
+
+/// Rounds to the nearest second, so a tick landing a few ms early or late
+/// still shows the expected count.
+fn whole_secs(duration: Duration) -> u32 {
+    ((duration.as_millis() + 500) / 1000).min(u32::MAX as u128) as u32
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::FOCUS_DURATION_SECS;
+
+    /// The instant `secs` into the running session.
+    fn after(timer: &Timer, secs: u32) -> Instant {
+        let total = Duration::from_secs(timer.total_duration_secs() as u64);
+        timer.session_end_instant.unwrap() - total + Duration::from_secs(secs as u64)
+    }
 
     #[test]
     fn test_tick_decrements() {
         let mut timer = Timer::new();
-        timer.start(SessionType::Focus);
-        let finished = timer.tick();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        let finished = timer.tick_at(after(&timer, 1));
         assert!(!finished);
-        let (m, s) = timer.remaining_display();
-        assert_eq!(m * 60 + s, FOCUS_DURATION_SECS - 1);
+        assert_eq!(timer.remaining_secs(), FOCUS_DURATION_SECS - 1);
+    }
+
+    #[test]
+    fn test_delayed_tick_uses_wall_clock() {
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        // the event loop stalled for 7 seconds; one tick catches up
+        timer.tick_at(after(&timer, 7));
+        assert_eq!(timer.remaining_secs(), FOCUS_DURATION_SECS - 7);
+        // a slightly early tick doesn't drop a second
+        timer.tick_at(after(&timer, 8) - Duration::from_millis(20));
+        assert_eq!(timer.remaining_secs(), FOCUS_DURATION_SECS - 8);
+
+        let end = timer.session_end_instant.unwrap();
+        assert!(timer.tick_at(end + Duration::from_secs(30)));
+        assert!(timer.is_finished());
+        assert_eq!(timer.focus_sessions_completed, 1);
+    }
+
+    #[test]
+    fn test_reconcile_gap_after_suspend() {
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        let now = after(&timer, 60);
+        timer.tick_at(now);
+
+        // a short hiccup is left to the regular tick
+        assert!(!timer.reconcile_gap(Duration::from_secs(5), Duration::from_secs(5), now));
+
+        // slept 10 minutes while the monotonic clock stood still
+        let now = now + Duration::from_secs(1);
+        let slept = Duration::from_secs(600);
+        assert!(!timer.reconcile_gap(slept + Duration::from_secs(1), Duration::from_secs(1), now));
+        timer.tick_at(now);
+        assert_eq!(timer.remaining_secs(), FOCUS_DURATION_SECS - 661);
+
+        // slept past the end of the session
+        assert!(timer.reconcile_gap(Duration::from_secs(3600), Duration::ZERO, now));
+        assert!(timer.tick_at(now));
+    }
+
+    #[test]
+    fn test_reconcile_gap_when_clock_kept_running() {
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        let end = timer.session_end_instant.unwrap();
+        // the monotonic clock counted the suspend itself, so nothing needs shifting
+        let now = end + Duration::from_secs(5);
+        let gap = Duration::from_secs(FOCUS_DURATION_SECS as u64 + 5);
+        assert!(timer.reconcile_gap(gap, gap, now));
+        assert_eq!(timer.session_end_instant, Some(end));
+    }
+
+    #[test]
+    fn test_pause_keeps_sub_second_remainder() {
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        let paused_at = after(&timer, 10) + Duration::from_millis(400);
+        timer.pause_at(paused_at);
+        assert_eq!(timer.remaining_secs(), FOCUS_DURATION_SECS - 10);
+
+        // time spent paused doesn't count
+        let resumed_at = paused_at + Duration::from_secs(120);
+        timer.resume_at(resumed_at);
+        assert_eq!(
+            timer.session_end_instant.unwrap() - resumed_at,
+            Duration::from_secs((FOCUS_DURATION_SECS - 10) as u64) - Duration::from_millis(400)
+        );
+        timer.tick_at(resumed_at + Duration::from_millis(600));
+        assert_eq!(timer.remaining_secs(), FOCUS_DURATION_SECS - 11);
     }
 
     #[test]
@@ -206,18 +740,98 @@ mod tests {
         assert!(timer.is_finished());
     }
 
+    #[test]
+    fn test_stopwatch_counts_up() {
+        let mut timer = Timer::new();
+        timer.focus_sessions_completed = 2;
+        timer.start_stopwatch(&TimerConfig::default());
+        assert!(timer.is_counting());
+        assert_eq!(timer.current_session_type(), Some(SessionType::Focus));
+
+        for _ in 0..90 {
+            assert!(!timer.tick());
+        }
+        assert_eq!(timer.elapsed_secs(), 90);
+        assert_eq!(timer.total_duration_secs(), 90);
+        assert_eq!(timer.progress(), 0.0);
+
+        assert_eq!(timer.stop_stopwatch(), Some(90));
+        assert!(matches!(timer.state, TimerState::Idle));
+        assert_eq!(timer.stop_stopwatch(), None);
+        // open-ended focus stays out of the long-break cycle
+        assert_eq!(timer.focus_sessions_completed, 2);
+    }
+
     #[test]
     fn test_pause_resume() {
         let mut timer = Timer::new();
-        timer.start(SessionType::Focus);
-        timer.tick(); // 24:59
-        timer.pause();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        timer.tick_at(after(&timer, 1)); // 24:59
+        timer.pause_at(after(&timer, 1));
         assert!(timer.is_paused());
-        let (m, s) = timer.remaining_display();
+        let remaining = timer.remaining_secs();
+        assert_eq!(timer.elapsed_secs(), 1);
         timer.resume();
         assert!(timer.is_running());
-        let (m2, s2) = timer.remaining_display();
-        assert_eq!((m, s), (m2, s2));
+        assert_eq!(timer.remaining_secs(), remaining);
+    }
+
+    #[test]
+    fn test_restore_running_snapshot() {
+        let now = NaiveDate::from_ymd_opt(2026, 3, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        timer.tick_at(after(&timer, 600));
+        timer.mark_interruption();
+        let snapshot = timer.snapshot(now).unwrap();
+        assert_eq!(
+            snapshot.ends_at,
+            Some(now + chrono::Duration::seconds(FOCUS_DURATION_SECS as i64 - 600))
+        );
+
+        // back two minutes later
+        let mut restored = Timer::new();
+        let later = now + chrono::Duration::seconds(120);
+        let instant = Instant::now();
+        restored.restore_at(&snapshot, later, instant);
+        assert!(restored.is_running());
+        assert_eq!(restored.remaining_secs(), FOCUS_DURATION_SECS - 720);
+        assert_eq!(restored.interruptions, 1);
+        assert!(restored.snapshot(later).unwrap().same_course(&snapshot));
+
+        // long after the deadline
+        let mut overdue = Timer::new();
+        overdue.restore_at(&snapshot, now + chrono::Duration::hours(2), instant);
+        assert_eq!(overdue.remaining_secs(), 0);
+        assert!(overdue.tick_at(instant));
+        assert_eq!(overdue.focus_sessions_completed, 1);
+    }
+
+    #[test]
+    fn test_restore_paused_snapshot() {
+        let now = NaiveDate::from_ymd_opt(2026, 3, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let mut timer = Timer::new();
+        timer.start(SessionType::ShortBreak, &TimerConfig::default());
+        timer.pause_at(after(&timer, 30));
+        let snapshot = timer.snapshot(now).unwrap();
+        assert_eq!(snapshot.ends_at, None);
+
+        let mut restored = Timer::new();
+        restored.restore(&snapshot, now + chrono::Duration::hours(5));
+        assert!(restored.is_paused());
+        assert_eq!(restored.remaining_secs(), timer.remaining_secs());
+        assert_eq!(
+            restored.current_session_type(),
+            Some(SessionType::ShortBreak)
+        );
+
+        assert!(Timer::new().snapshot(now).is_none());
     }
 
     #[test]
@@ -230,6 +844,163 @@ mod tests {
         assert_eq!(timer.next_session_type(), SessionType::LongBreak);
     }
 
+    #[test]
+    fn test_cycle_resets_on_day_rollover() {
+        let day1 = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        let day2 = day1.succ_opt().unwrap();
+
+        let mut timer = Timer::new();
+        assert!(timer.reset_cycle_if_new_day(day1));
+        timer.focus_sessions_completed = 3;
+        assert!(!timer.reset_cycle_if_new_day(day1));
+        assert_eq!(timer.focus_sessions_completed, 3);
+
+        timer.state = TimerState::Finished {
+            session_type: SessionType::Focus,
+        };
+        assert!(timer.reset_cycle_if_new_day(day2));
+        assert_eq!(timer.focus_sessions_completed, 0);
+        // yesterday's last focus is followed by a short break, not a long one
+        assert_eq!(timer.next_session_type(), SessionType::ShortBreak);
+    }
+
+    fn finish_current(timer: &mut Timer) {
+        let end = timer.session_end_instant.unwrap();
+        assert!(timer.tick_at(end));
+    }
+
+    #[test]
+    fn test_long_break_after_configured_cadence() {
+        let config = TimerConfig {
+            focus_secs: 3,
+            short_break_secs: 2,
+            long_break_secs: 2,
+            sessions_before_long_break: 3,
+        };
+        let mut timer = Timer::new();
+        let mut sequence = Vec::new();
+        for _ in 0..6 {
+            timer.start_next(&config);
+            sequence.push(timer.current_session_type().unwrap());
+            finish_current(&mut timer);
+        }
+        timer.start_next(&config);
+        sequence.push(timer.current_session_type().unwrap());
+
+        use SessionType::*;
+        assert_eq!(
+            sequence,
+            vec![Focus, ShortBreak, Focus, ShortBreak, Focus, LongBreak, Focus]
+        );
+    }
+
+    #[test]
+    fn test_cycle_modes_across_reset() {
+        let config = TimerConfig {
+            focus_secs: 3,
+            short_break_secs: 2,
+            long_break_secs: 2,
+            sessions_before_long_break: 3,
+        };
+        for (mode, total) in [(CycleMode::Running, 3), (CycleMode::FullCycle, 5)] {
+            let mut timer = Timer::new();
+            timer.cycle_mode = mode;
+            for _ in 0..4 {
+                timer.start_next(&config);
+                finish_current(&mut timer);
+            }
+            // two focus sessions in, the sitting is cut short
+            timer.reset_cycle();
+            assert_eq!(timer.cycle_position(3), 0);
+
+            let mut focus_before_long_break = 0;
+            loop {
+                timer.start_next(&config);
+                match timer.current_session_type().unwrap() {
+                    SessionType::Focus => focus_before_long_break += 1,
+                    SessionType::LongBreak => break,
+                    SessionType::ShortBreak => {}
+                }
+                finish_current(&mut timer);
+            }
+            assert_eq!(focus_before_long_break, 3, "{:?}", mode);
+            assert_eq!(timer.focus_sessions_completed, total, "{:?}", mode);
+            assert_eq!(timer.cycle_sessions, 0);
+        }
+    }
+
+    #[test]
+    fn test_full_cycle_survives_cadence_change() {
+        let config = TimerConfig {
+            sessions_before_long_break: 2,
+            ..TimerConfig::default()
+        };
+        for (mode, next) in [
+            (CycleMode::Running, SessionType::ShortBreak),
+            (CycleMode::FullCycle, SessionType::LongBreak),
+        ] {
+            let mut timer = Timer::new();
+            timer.cycle_mode = mode;
+            timer.focus_sessions_completed = 3;
+            timer.cycle_sessions = 3;
+            timer.state = TimerState::Finished {
+                session_type: SessionType::Focus,
+            };
+            // 3 of 4 done, then the cycle shrinks to 2
+            timer.start_next(&config);
+            assert_eq!(timer.current_session_type(), Some(next), "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn test_cadence_change_applies_to_next_break() {
+        let mut timer = Timer::new();
+        timer.focus_sessions_completed = 3;
+        timer.state = TimerState::Finished {
+            session_type: SessionType::Focus,
+        };
+        let config = TimerConfig {
+            sessions_before_long_break: 3,
+            ..TimerConfig::default()
+        };
+        timer.start_next(&config);
+        assert_eq!(timer.current_session_type(), Some(SessionType::LongBreak));
+    }
+
+    #[test]
+    fn test_focus_secs_until_long_break() {
+        let config = TimerConfig::default();
+        let mut timer = Timer::new();
+        assert_eq!(
+            timer.focus_secs_until_long_break(&config),
+            4 * FOCUS_DURATION_SECS
+        );
+
+        timer.focus_sessions_completed = 2;
+        timer.start(SessionType::Focus, &config);
+        timer.tick_at(after(&timer, 1));
+        assert_eq!(
+            timer.focus_secs_until_long_break(&config),
+            2 * FOCUS_DURATION_SECS - 1
+        );
+
+        // shorter cycle takes effect immediately
+        let short_cycle = TimerConfig {
+            sessions_before_long_break: 3,
+            ..config
+        };
+        assert_eq!(
+            timer.focus_secs_until_long_break(&short_cycle),
+            FOCUS_DURATION_SECS - 1
+        );
+
+        timer.focus_sessions_completed = 4;
+        timer.state = TimerState::Finished {
+            session_type: SessionType::Focus,
+        };
+        assert_eq!(timer.focus_secs_until_long_break(&config), 0);
+    }
+
     #[test]
     fn test_short_break_after_focus() {
         let mut timer = Timer::new();
@@ -240,6 +1011,170 @@ mod tests {
         assert_eq!(timer.next_session_type(), SessionType::ShortBreak);
     }
 
+    #[test]
+    fn test_skip_break_keeps_cycle_count() {
+        let config = TimerConfig {
+            focus_secs: 3,
+            short_break_secs: 2,
+            long_break_secs: 2,
+            sessions_before_long_break: 2,
+        };
+        let mut timer = Timer::new();
+        timer.start_next(&config);
+        finish_current(&mut timer);
+        assert!(timer.skip_break(&config));
+        assert_eq!(timer.current_session_type(), Some(SessionType::Focus));
+        assert_eq!(timer.focus_sessions_completed, 1);
+
+        // only focus sessions that finish count, so the long break still lands on the second
+        finish_current(&mut timer);
+        assert_eq!(timer.focus_sessions_completed, 2);
+        assert_eq!(timer.next_session_type(), SessionType::LongBreak);
+
+        // nothing to skip during the break or once it's over
+        timer.start_next(&config);
+        assert!(!timer.skip_break(&config));
+        finish_current(&mut timer);
+        assert!(!timer.skip_break(&config));
+        assert_eq!(timer.focus_sessions_completed, 2);
+    }
+
+    #[test]
+    fn test_pause_idle_gives_back_time_away() {
+        let config = TimerConfig::default();
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &config);
+        let now = after(&timer, 20 * 60);
+        timer.tick_at(now);
+        timer.pause_idle(Duration::from_secs(5 * 60), now);
+        assert!(timer.is_paused());
+        assert_eq!(timer.remaining_secs(), 10 * 60);
+
+        // idle for longer than the session has run
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &config);
+        let now = after(&timer, 60);
+        timer.tick_at(now);
+        timer.pause_idle(Duration::from_secs(5 * 60), now);
+        assert_eq!(timer.remaining_secs(), config.focus_secs);
+    }
+
+    #[test]
+    fn test_long_break_closes_cycle() {
+        let config = TimerConfig {
+            focus_secs: 3,
+            short_break_secs: 2,
+            long_break_secs: 2,
+            sessions_before_long_break: 2,
+        };
+        let mut timer = Timer::new();
+        timer.start_next(&config);
+        finish_current(&mut timer);
+        // an extended session counts once, at its full length
+        timer.extend(5);
+        assert_eq!(timer.cycle_focus_secs, 0);
+        finish_current(&mut timer);
+        timer.start_next(&config);
+        finish_current(&mut timer);
+        timer.start_next(&config);
+        finish_current(&mut timer);
+        assert_eq!(timer.cycle_focus_secs, 11);
+
+        timer.start_next(&config);
+        assert_eq!(timer.current_session_type(), Some(SessionType::LongBreak));
+        assert_eq!(
+            timer.finished_cycle,
+            Some(CycleTotals {
+                sessions: 2,
+                focus_secs: 11,
+            })
+        );
+        assert_eq!(timer.cycle_focus_secs, 0);
+
+        // a long break with nothing before it has no cycle to show
+        timer.reset_cycle();
+        timer.start(SessionType::LongBreak, &config);
+        assert_eq!(timer.finished_cycle, None);
+    }
+
+    #[test]
+    fn test_cycle_survives_restart() {
+        let config = TimerConfig::default();
+        let yesterday = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let mut timer = Timer::new();
+        timer.last_cycle_reset_date = Some(yesterday);
+        for _ in 0..3 {
+            timer.start(SessionType::Focus, &config);
+            finish_current(&mut timer);
+        }
+        let saved = timer.cycle_progress();
+
+        // the next launch is one focus session away from the long break
+        let mut restarted = Timer::new();
+        restarted.restore_cycle(saved);
+        restarted.start(SessionType::Focus, &config);
+        finish_current(&mut restarted);
+        assert_eq!(restarted.next_session_type(), SessionType::LongBreak);
+
+        // unless the cycle starts over with each day
+        let mut next_day = Timer::new();
+        next_day.restore_cycle(saved);
+        assert!(next_day.reset_cycle_if_new_day(yesterday.succ_opt().unwrap()));
+        next_day.start(SessionType::Focus, &config);
+        finish_current(&mut next_day);
+        assert_eq!(next_day.next_session_type(), SessionType::ShortBreak);
+
+        // a long break under way still has its cycle to sum up
+        restarted.start(SessionType::LongBreak, &config);
+        let mut mid_break = Timer::new();
+        mid_break.restore_cycle(restarted.cycle_progress());
+        assert_eq!(
+            mid_break.finished_cycle,
+            Some(CycleTotals {
+                sessions: 4,
+                focus_secs: 4 * config.focus_secs,
+            })
+        );
+    }
+
+    #[test]
+    fn test_skipped_focus_does_not_advance_cycle() {
+        let config = TimerConfig {
+            focus_secs: 3,
+            short_break_secs: 2,
+            long_break_secs: 2,
+            sessions_before_long_break: 2,
+        };
+        for mode in [CycleMode::Running, CycleMode::FullCycle] {
+            let mut timer = Timer::new();
+            timer.cycle_mode = mode;
+            timer.start_next(&config);
+            finish_current(&mut timer);
+            timer.start_next(&config);
+            assert_eq!(timer.current_session_type(), Some(SessionType::ShortBreak));
+            finish_current(&mut timer);
+
+            // the second focus session is given up halfway through
+            timer.start_next(&config);
+            timer.tick_at(after(&timer, 1));
+            timer.reset();
+            assert!(matches!(timer.state, TimerState::Idle));
+            assert_eq!(timer.focus_sessions_completed, 1, "{:?}", mode);
+            assert_eq!(timer.cycle_position(2), 1, "{:?}", mode);
+
+            // so the one after it is what earns the long break
+            timer.start_next(&config);
+            assert_eq!(timer.current_session_type(), Some(SessionType::Focus));
+            finish_current(&mut timer);
+            assert_eq!(
+                timer.next_session_type(),
+                SessionType::LongBreak,
+                "{:?}",
+                mode
+            );
+        }
+    }
+
     #[test]
     fn test_focus_after_break() {
         let mut timer = Timer::new();
@@ -252,7 +1187,7 @@ mod tests {
     #[test]
     fn test_progress() {
         let mut timer = Timer::new();
-        timer.start(SessionType::Focus);
+        timer.start(SessionType::Focus, &TimerConfig::default());
         assert!((timer.progress() - 0.0).abs() < f32::EPSILON);
 
         // halfway
@@ -262,4 +1197,153 @@ mod tests {
         };
         assert!((timer.progress() - 0.5).abs() < 0.01);
     }
+
+    #[test]
+    fn test_progress_between_ticks() {
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        let total = FOCUS_DURATION_SECS as f32;
+        let now = after(&timer, 90) + Duration::from_millis(500);
+        assert!((timer.progress_at(now) - 90.5 / total).abs() < 1e-4);
+        // the whole-second progress waits for the tick
+        timer.tick_at(now);
+        assert!((timer.progress() - 90.0 / total).abs() < 1e-4);
+
+        timer.pause_at(now);
+        assert_eq!(timer.progress_at(now), timer.progress());
+    }
+
+    #[test]
+    fn test_start_uses_config_durations() {
+        let config = TimerConfig {
+            focus_secs: 50 * 60,
+            short_break_secs: 10 * 60,
+            long_break_secs: 30 * 60,
+            sessions_before_long_break: 4,
+        };
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &config);
+        assert_eq!(timer.remaining_display(), (50, 0));
+        assert_eq!(timer.total_duration_secs(), 50 * 60);
+
+        timer.start(SessionType::ShortBreak, &config);
+        assert_eq!(timer.remaining_display(), (10, 0));
+    }
+
+    #[test]
+    fn test_tick_paused_counts_only_while_paused() {
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        assert_eq!(timer.tick_paused(), 0);
+
+        timer.pause();
+        timer.tick_paused();
+        assert_eq!(timer.tick_paused(), 2);
+
+        // a fresh pause starts counting from zero again
+        timer.resume();
+        timer.pause();
+        assert_eq!(timer.tick_paused(), 1);
+    }
+
+    #[test]
+    fn test_ends_at_only_while_running() {
+        let now = NaiveDate::from_ymd_opt(2026, 3, 2)
+            .unwrap()
+            .and_hms_opt(15, 22, 0)
+            .unwrap();
+        let mut timer = Timer::new();
+        assert_eq!(timer.ends_at(now), None);
+
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        assert_eq!(
+            timer.ends_at(now).unwrap().time(),
+            chrono::NaiveTime::from_hms_opt(15, 47, 0).unwrap()
+        );
+
+        timer.pause();
+        assert_eq!(timer.ends_at(now), None);
+    }
+
+    #[test]
+    fn test_reset_cycle() {
+        let config = TimerConfig {
+            focus_secs: 3,
+            short_break_secs: 2,
+            long_break_secs: 2,
+            sessions_before_long_break: 1,
+        };
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &config);
+        finish_current(&mut timer);
+        assert_eq!(timer.next_session_type(), SessionType::LongBreak);
+
+        timer.reset_cycle();
+        assert_eq!(timer.focus_sessions_completed, 0);
+        assert_eq!(timer.next_session_type(), SessionType::ShortBreak);
+        assert_eq!(timer.focus_secs_until_long_break(&config), 3);
+    }
+
+    #[test]
+    fn test_extend_finished_timer() {
+        let config = TimerConfig::default();
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &config);
+        finish_current(&mut timer);
+        assert_eq!(timer.focus_sessions_completed, 1);
+
+        let now = Instant::now();
+        timer.extend_at(300, now);
+        assert!(timer.is_running());
+        assert_eq!(timer.remaining_secs(), 300);
+        // counted again once the extension runs out
+        assert_eq!(timer.focus_sessions_completed, 0);
+        assert_eq!(timer.total_duration_secs(), config.focus_secs + 300);
+
+        assert!(!timer.tick_at(now + Duration::from_secs(299)));
+        assert!(timer.tick_at(now + Duration::from_secs(300)));
+        assert_eq!(timer.focus_sessions_completed, 1);
+        assert_eq!(timer.total_duration_secs(), config.focus_secs + 300);
+
+        // the next session starts at its normal length
+        timer.start_next(&config);
+        assert_eq!(timer.total_duration_secs(), config.short_break_secs);
+    }
+
+    #[test]
+    fn test_interruptions_only_count_during_focus() {
+        let config = TimerConfig::default();
+        let mut timer = Timer::new();
+        timer.mark_interruption();
+        assert_eq!(timer.interruptions, 0);
+
+        timer.start(SessionType::Focus, &config);
+        timer.mark_interruption();
+        timer.pause();
+        timer.mark_interruption();
+        assert_eq!(timer.interruptions, 2);
+
+        // kept through the end so it can be recorded, then cleared by the next start
+        timer.resume();
+        finish_current(&mut timer);
+        assert_eq!(timer.interruptions, 2);
+        timer.start_next(&config);
+        timer.mark_interruption();
+        assert_eq!(timer.interruptions, 0);
+    }
+
+    #[test]
+    fn test_extend_running_timer() {
+        let mut timer = Timer::new();
+        timer.start(SessionType::Focus, &TimerConfig::default());
+        let end = timer.session_end_instant.unwrap();
+        timer.tick_at(end - Duration::from_secs(30));
+
+        timer.extend(300);
+        assert_eq!(timer.remaining_secs(), 330);
+        assert_eq!(
+            timer.session_end_instant,
+            Some(end + Duration::from_secs(300))
+        );
+    }
 }
@@ -0,0 +1,96 @@
+use iced::futures::Stream;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The tray icon is registered and can bring the window back
+    Ready,
+    Show,
+    Quit,
+}
+
+/// Runs a tray icon for as long as the stream is polled.
+#[cfg(target_os = "linux")]
+pub fn events() -> impl Stream<Item = TrayEvent> {
+    use ksni::TrayMethods;
+
+    iced::stream::channel(8, async |mut sender| {
+        let tray = FerrisTray {
+            sender: sender.clone(),
+        };
+        match tray.spawn().await {
+            Ok(handle) => {
+                let _ = sender.try_send(TrayEvent::Ready);
+                let _guard = ShutdownOnDrop(handle);
+                std::future::pending::<()>().await;
+            }
+            Err(e) => eprintln!("Failed to create tray icon: {e}"),
+        }
+    })
+}
+
+/// No tray support here, so `Ready` is never sent and minimizing falls back
+/// to the taskbar.
+#[cfg(not(target_os = "linux"))]
+pub fn events() -> impl Stream<Item = TrayEvent> {
+    iced::futures::stream::empty()
+}
+
+#[cfg(target_os = "linux")]
+struct FerrisTray {
+    sender: iced::futures::channel::mpsc::Sender<TrayEvent>,
+}
+
+#[cfg(target_os = "linux")]
+impl FerrisTray {
+    fn send(&mut self, event: TrayEvent) {
+        let _ = self.sender.try_send(event);
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl ksni::Tray for FerrisTray {
+    fn id(&self) -> String {
+        env!("CARGO_PKG_NAME").into()
+    }
+
+    fn title(&self) -> String {
+        "Ferris Focus".into()
+    }
+
+    fn icon_name(&self) -> String {
+        "ferris-focus".into()
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        self.send(TrayEvent::Show);
+    }
+
+    fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
+        use ksni::menu::StandardItem;
+        vec![
+            StandardItem {
+                label: "Show".into(),
+                activate: Box::new(|tray: &mut Self| tray.send(TrayEvent::Show)),
+                ..Default::default()
+            }
+            .into(),
+            StandardItem {
+                label: "Quit".into(),
+                activate: Box::new(|tray: &mut Self| tray.send(TrayEvent::Quit)),
+                ..Default::default()
+            }
+            .into(),
+        ]
+    }
+}
+
+/// Removes the icon when the subscription is dropped (e.g. setting turned off).
+#[cfg(target_os = "linux")]
+struct ShutdownOnDrop(ksni::Handle<FerrisTray>);
+
+#[cfg(target_os = "linux")]
+impl Drop for ShutdownOnDrop {
+    fn drop(&mut self) {
+        drop(self.0.shutdown());
+    }
+}
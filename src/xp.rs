@@ -1,31 +1,56 @@
 use chrono::NaiveDate;
 
-use crate::models::FerrisStage;
+use crate::models::{FerrisStage, XpConfig, FOCUS_DURATION_SECS};
 
-const BASE_XP: u32 = 100;
-const STREAK_BONUS_PER_DAY: u32 = 10;
-const MAX_STREAK_BONUS: u32 = 200;
-const XP_PER_LEVEL: u32 = 500;
+/// XP cost of going from level 1 to 2; each later level costs one more step of this
+const LEVEL_XP_BASE: u64 = 500;
 
-pub fn calculate_xp(current_streak: u32) -> u32 {
-    let bonus = (current_streak * STREAK_BONUS_PER_DAY).min(MAX_STREAK_BONUS);
-    BASE_XP + bonus
+pub fn calculate_xp(config: &XpConfig, current_streak: u32) -> u32 {
+    let bonus = current_streak
+        .saturating_mul(config.streak_bonus_per_day)
+        .min(config.max_streak_bonus);
+    config.base_xp.saturating_add(bonus)
+}
+
+/// Stopwatch XP: the regular session reward scaled by time against a
+/// standard 25 minute session.
+pub fn stopwatch_xp(config: &XpConfig, elapsed_secs: u32, current_streak: u32) -> u32 {
+    let xp = calculate_xp(config, current_streak) as u64 * elapsed_secs as u64
+        / FOCUS_DURATION_SECS as u64;
+    xp.min(u32::MAX as u64) as u32
+}
+
+/// Total XP needed to reach `level`: `BASE * n * (n + 1) / 2` with `n = level - 1`.
+/// Every other level calculation derives from this so they can't drift apart.
+pub fn xp_for_level(level: u32) -> u32 {
+    let n = level.saturating_sub(1) as u64;
+    (LEVEL_XP_BASE * n * (n + 1) / 2).min(u32::MAX as u64) as u32
 }
 
 pub fn calculate_level(total_xp: u32) -> u32 {
-    (total_xp / XP_PER_LEVEL) + 1
+    let mut level = 1;
+    while xp_for_level(level + 1) <= total_xp && xp_for_level(level + 1) < u32::MAX {
+        level += 1;
+    }
+    level
 }
 
 pub fn xp_for_next_level(level: u32) -> u32 {
-    level * XP_PER_LEVEL
+    xp_for_level(level + 1)
+}
+
+/// XP earned within the current level and the XP the whole level takes.
+pub fn level_xp(total_xp: u32) -> (u32, u32) {
+    let level = calculate_level(total_xp);
+    let level_start = xp_for_level(level);
+    let level_span = xp_for_next_level(level) - level_start;
+    (total_xp - level_start, level_span)
 }
 
 /// 0.0..1.0 progress within current level
 pub fn level_progress(total_xp: u32) -> f32 {
-    let level = calculate_level(total_xp);
-    let xp_at_current_level = (level - 1) * XP_PER_LEVEL;
-    let xp_in_level = total_xp - xp_at_current_level;
-    xp_in_level as f32 / XP_PER_LEVEL as f32
+    let (earned, span) = level_xp(total_xp);
+    earned as f32 / span as f32
 }
 
 pub fn ferris_stage(level: u32) -> FerrisStage {
@@ -34,48 +59,154 @@ pub fn ferris_stage(level: u32) -> FerrisStage {
         2..=3 => FerrisStage::Hatchling,
         4..=6 => FerrisStage::Junior,
         7..=9 => FerrisStage::Senior,
-        _ => FerrisStage::King,
+        10 => FerrisStage::King,
+        11..=15 => FerrisStage::Emperor,
+        16..=20 => FerrisStage::Legend,
+        _ => FerrisStage::Mythic,
     }
 }
 
+/// Whether reaching `level` turns Ferris into the next stage
+pub fn reaches_new_stage(level: u32) -> bool {
+    level > 1 && ferris_stage(level) != ferris_stage(level - 1)
+}
+
+/// A streak freeze is earned every this many levels
+pub const LEVELS_PER_FREEZE: u32 = 5;
+
+pub fn freezes_earned(old_level: u32, new_level: u32) -> u32 {
+    (new_level / LEVELS_PER_FREEZE).saturating_sub(old_level / LEVELS_PER_FREEZE)
+}
+
+/// Returns the new streak and whether a freeze was consumed to bridge a missed day.
 pub fn update_streak(
     last_session_date: Option<NaiveDate>,
     today: NaiveDate,
     current_streak: u32,
-) -> u32 {
+    freezes_available: u32,
+) -> (u32, bool) {
     match last_session_date {
-        None => 1, // first session
+        None => (1, false), // first session
         Some(last_date) => {
             let diff = (today - last_date).num_days();
             match diff {
-                0 => current_streak.max(1), // same day
-                1 => current_streak + 1,    // consecutive
-                _ => 1,                     // missed, reset
+                0 => (current_streak.max(1), false),                  // same day
+                1 => (current_streak + 1, false),                     // consecutive
+                2 if freezes_available > 0 => (current_streak, true), // one missed day, frozen
+                _ => (1, false),                                      // missed, reset
             }
         }
     }
 }
 
+/// The streak ends at midnight unless a focus session is completed today.
+pub fn streak_at_risk(
+    last_session_date: Option<NaiveDate>,
+    today: NaiveDate,
+    current_streak: u32,
+) -> bool {
+    current_streak > 0 && last_session_date.is_some_and(|last| (today - last).num_days() == 1)
+}
+
+/// (current, longest) streak from scratch over sorted, distinct session days.
+/// A streak still counts as current if its last day is today or yesterday.
+pub fn streaks_from_dates(dates: &[NaiveDate], today: NaiveDate) -> (u32, u32) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut prev: Option<NaiveDate> = None;
+    for &date in dates {
+        run = match prev {
+            Some(p) if (date - p).num_days() == 1 => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        prev = Some(date);
+    }
+
+    let current = match prev {
+        Some(last) if (today - last).num_days() <= 1 => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::NaiveDate;
 
+    #[test]
+    fn test_streaks_from_dates() {
+        let d = |day| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        assert_eq!(streaks_from_dates(&[], d(10)), (0, 0));
+
+        // 1-3 and 6-9, with a gap on 4-5
+        let dates = [d(1), d(2), d(3), d(6), d(7), d(8), d(9)];
+        assert_eq!(streaks_from_dates(&dates, d(9)), (4, 4));
+        assert_eq!(streaks_from_dates(&dates, d(10)), (4, 4));
+        // missed yesterday, so the run is over
+        assert_eq!(streaks_from_dates(&dates, d(11)), (0, 4));
+
+        let dates = [d(1), d(2), d(3), d(4), d(8)];
+        assert_eq!(streaks_from_dates(&dates, d(8)), (1, 4));
+    }
+
+    #[test]
+    fn test_streak_at_risk() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let yesterday = today.pred_opt().unwrap();
+        assert!(streak_at_risk(Some(yesterday), today, 4));
+        assert!(!streak_at_risk(Some(today), today, 5));
+        assert!(!streak_at_risk(Some(yesterday), today, 0));
+        // already broken, nothing left to save
+        assert!(!streak_at_risk(yesterday.pred_opt(), today, 4));
+        assert!(!streak_at_risk(None, today, 0));
+    }
+
     #[test]
     fn test_base_xp() {
-        assert_eq!(calculate_xp(0), 100);
+        assert_eq!(calculate_xp(&XpConfig::default(), 0), 100);
     }
 
     #[test]
     fn test_streak_bonus() {
-        assert_eq!(calculate_xp(5), 150); // 100 + 5*10
-        assert_eq!(calculate_xp(10), 200); // 100 + 10*10
+        assert_eq!(calculate_xp(&XpConfig::default(), 5), 150); // 100 + 5*10
+        assert_eq!(calculate_xp(&XpConfig::default(), 10), 200); // 100 + 10*10
     }
 
     #[test]
     fn test_streak_bonus_cap() {
-        assert_eq!(calculate_xp(50), 300); // 100 + min(500, 200) = 300
-        assert_eq!(calculate_xp(100), 300); // same cap
+        assert_eq!(calculate_xp(&XpConfig::default(), 50), 300); // 100 + min(500, 200) = 300
+        assert_eq!(calculate_xp(&XpConfig::default(), 100), 300); // same cap
+    }
+
+    #[test]
+    fn test_custom_xp_config() {
+        let harder = XpConfig {
+            base_xp: 40,
+            streak_bonus_per_day: 2,
+            max_streak_bonus: 10,
+        };
+        assert_eq!(calculate_xp(&harder, 3), 46);
+        assert_eq!(calculate_xp(&harder, 30), 50);
+        assert_eq!(stopwatch_xp(&harder, FOCUS_DURATION_SECS / 2, 0), 20);
+    }
+
+    #[test]
+    fn test_stopwatch_xp_scales_with_time() {
+        assert_eq!(stopwatch_xp(&XpConfig::default(), 0, 0), 0);
+        assert_eq!(
+            stopwatch_xp(&XpConfig::default(), FOCUS_DURATION_SECS, 0),
+            100
+        );
+        assert_eq!(
+            stopwatch_xp(&XpConfig::default(), FOCUS_DURATION_SECS * 2, 5),
+            300
+        );
+        assert_eq!(
+            stopwatch_xp(&XpConfig::default(), FOCUS_DURATION_SECS / 5, 0),
+            20
+        );
     }
 
     #[test]
@@ -83,8 +214,43 @@ mod tests {
         assert_eq!(calculate_level(0), 1);
         assert_eq!(calculate_level(499), 1);
         assert_eq!(calculate_level(500), 2);
-        assert_eq!(calculate_level(999), 2);
-        assert_eq!(calculate_level(1000), 3);
+        assert_eq!(calculate_level(1499), 2);
+        assert_eq!(calculate_level(1500), 3);
+        assert_eq!(calculate_level(3000), 4);
+    }
+
+    #[test]
+    fn test_xp_for_level_is_quadratic() {
+        assert_eq!(xp_for_level(1), 0);
+        assert_eq!(xp_for_level(2), 500);
+        assert_eq!(xp_for_level(3), 1500);
+        assert_eq!(xp_for_level(4), 3000);
+        assert_eq!(xp_for_next_level(3), xp_for_level(4));
+    }
+
+    #[test]
+    fn test_level_cost_increases_monotonically() {
+        let mut prev_cost = 0;
+        for level in 1..100 {
+            let cost = xp_for_level(level + 1) - xp_for_level(level);
+            assert!(cost > prev_cost, "level {} costs {}", level, cost);
+            prev_cost = cost;
+        }
+
+        let mut prev_level = 1;
+        for xp in (0..200_000).step_by(250) {
+            let level = calculate_level(xp);
+            assert!(level >= prev_level);
+            prev_level = level;
+        }
+    }
+
+    #[test]
+    fn test_level_progress_stays_in_range() {
+        for xp in (0..200_000).step_by(37) {
+            let progress = level_progress(xp);
+            assert!((0.0..1.0).contains(&progress), "xp {} -> {}", xp, progress);
+        }
     }
 
     #[test]
@@ -95,33 +261,79 @@ mod tests {
         assert_eq!(ferris_stage(4), FerrisStage::Junior);
         assert_eq!(ferris_stage(7), FerrisStage::Senior);
         assert_eq!(ferris_stage(10), FerrisStage::King);
-        assert_eq!(ferris_stage(50), FerrisStage::King);
+        assert_eq!(ferris_stage(11), FerrisStage::Emperor);
+        assert_eq!(ferris_stage(15), FerrisStage::Emperor);
+        assert_eq!(ferris_stage(16), FerrisStage::Legend);
+        assert_eq!(ferris_stage(20), FerrisStage::Legend);
+        assert_eq!(ferris_stage(21), FerrisStage::Mythic);
+        assert_eq!(ferris_stage(50), FerrisStage::Mythic);
+    }
+
+    #[test]
+    fn test_stage_changes_at_new_boundaries() {
+        // the level-up modal compares the stage before and after each level
+        for (level, from, to) in [
+            (11, FerrisStage::King, FerrisStage::Emperor),
+            (16, FerrisStage::Emperor, FerrisStage::Legend),
+            (21, FerrisStage::Legend, FerrisStage::Mythic),
+        ] {
+            assert_eq!(ferris_stage(level - 1), from);
+            assert_eq!(ferris_stage(level), to);
+        }
+    }
+
+    #[test]
+    fn test_reaches_new_stage() {
+        let new_stages: Vec<u32> = (1..=25).filter(|&l| reaches_new_stage(l)).collect();
+        assert_eq!(new_stages, vec![2, 4, 7, 10, 11, 16, 21]);
     }
 
     #[test]
     fn test_streak_continues() {
         let yesterday = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
         let today = NaiveDate::from_ymd_opt(2026, 2, 19).unwrap();
-        assert_eq!(update_streak(Some(yesterday), today, 5), 6);
+        assert_eq!(update_streak(Some(yesterday), today, 5, 0), (6, false));
     }
 
     #[test]
     fn test_streak_resets() {
         let two_days_ago = NaiveDate::from_ymd_opt(2026, 2, 17).unwrap();
         let today = NaiveDate::from_ymd_opt(2026, 2, 19).unwrap();
-        assert_eq!(update_streak(Some(two_days_ago), today, 5), 1);
+        assert_eq!(update_streak(Some(two_days_ago), today, 5, 0), (1, false));
+    }
+
+    #[test]
+    fn test_streak_freeze_bridges_missed_day() {
+        let two_days_ago = NaiveDate::from_ymd_opt(2026, 2, 17).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 2, 19).unwrap();
+        assert_eq!(update_streak(Some(two_days_ago), today, 5, 1), (5, true));
+    }
+
+    #[test]
+    fn test_streak_freeze_doesnt_cover_longer_gaps() {
+        let three_days_ago = NaiveDate::from_ymd_opt(2026, 2, 16).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 2, 19).unwrap();
+        assert_eq!(update_streak(Some(three_days_ago), today, 5, 2), (1, false));
+    }
+
+    #[test]
+    fn test_freezes_earned() {
+        assert_eq!(freezes_earned(1, 4), 0);
+        assert_eq!(freezes_earned(4, 5), 1);
+        assert_eq!(freezes_earned(5, 9), 0);
+        assert_eq!(freezes_earned(4, 11), 2);
     }
 
     #[test]
     fn test_streak_same_day() {
         let today = NaiveDate::from_ymd_opt(2026, 2, 19).unwrap();
-        assert_eq!(update_streak(Some(today), today, 3), 3);
+        assert_eq!(update_streak(Some(today), today, 3, 0), (3, false));
     }
 
     #[test]
     fn test_first_session_streak() {
         let today = NaiveDate::from_ymd_opt(2026, 2, 19).unwrap();
-        assert_eq!(update_streak(None, today, 0), 1);
+        assert_eq!(update_streak(None, today, 0, 0), (1, false));
     }
 
     #[test]
@@ -129,6 +341,20 @@ mod tests {
         assert!((level_progress(0) - 0.0).abs() < f32::EPSILON);
         assert!((level_progress(250) - 0.5).abs() < 0.01);
         assert!((level_progress(500) - 0.0).abs() < f32::EPSILON); // Level 2, 0 progress
-        assert!((level_progress(750) - 0.5).abs() < 0.01);
+        assert!((level_progress(1000) - 0.5).abs() < 0.01); // Level 2 spans 500..1500
+    }
+
+    #[test]
+    fn test_level_xp() {
+        assert_eq!(level_xp(0), (0, 500));
+        assert_eq!(level_xp(499), (499, 500));
+        assert_eq!(level_xp(1000), (500, 1000));
+        // whatever the curve, a level's span is the gap between its thresholds
+        for total_xp in [0, 700, 5_000, 123_456] {
+            let level = calculate_level(total_xp);
+            let (earned, span) = level_xp(total_xp);
+            assert_eq!(span, xp_for_next_level(level) - xp_for_level(level));
+            assert_eq!(earned, total_xp - xp_for_level(level));
+        }
     }
 }
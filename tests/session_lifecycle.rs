@@ -0,0 +1,212 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use std::time::Duration;
+
+use ferris_focus::achievements::Achievement;
+use ferris_focus::db;
+use ferris_focus::engine::{self, Event, SessionStart, Skipped};
+use ferris_focus::models::{SessionType, Settings, UserProfile};
+use ferris_focus::timer::{Timer, TimerState};
+use ferris_focus::xp;
+
+fn open_db() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    db::init_schema(&conn).unwrap();
+    conn
+}
+
+fn at(day: u32, hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 3, day)
+        .unwrap()
+        .and_hms_opt(hour, 0, 0)
+        .unwrap()
+}
+
+fn start(started_at: &str) -> SessionStart {
+    SessionStart {
+        started_at: started_at.to_string(),
+        ..SessionStart::default()
+    }
+}
+
+/// Starts a session and runs it straight to its deadline.
+fn finish(timer: &mut Timer, session_type: SessionType, settings: &Settings) {
+    timer.start(session_type, &settings.timer);
+    let end = timer.session_end_instant.unwrap();
+    assert!(timer.tick_at(end));
+}
+
+#[test]
+fn focus_session_is_saved_and_rewarded() {
+    let conn = open_db();
+    let settings = Settings::default();
+    let mut profile = UserProfile::default();
+    let mut timer = Timer::new();
+
+    finish(&mut timer, SessionType::Focus, &settings);
+    let completion = engine::complete_session(
+        Some(&conn),
+        &mut profile,
+        &settings,
+        &timer,
+        SessionStart {
+            started_at: "2026-03-02T09:00:00".to_string(),
+            tag: Some("rust".to_string()),
+            intention: Some("finish the lexer".to_string()),
+        },
+        at(2, 9),
+    )
+    .unwrap();
+
+    assert!(completion.id.is_some());
+    assert_eq!(
+        db::get_total_stats(&conn).unwrap(),
+        (1, settings.timer.focus_secs)
+    );
+    assert_eq!(
+        db::get_today_session_count(&conn, "2026-03-02", 0).unwrap(),
+        1
+    );
+    let saved = &db::get_all_sessions(&conn).unwrap()[0];
+    assert_eq!(saved.tag.as_deref(), Some("rust"));
+    assert_eq!(saved.intention.as_deref(), Some("finish the lexer"));
+    assert_eq!(Some(saved.xp_earned), completion.xp);
+
+    assert_eq!(completion.xp, Some(xp::calculate_xp(&settings.xp, 1)));
+    assert_eq!(profile.total_xp, xp::calculate_xp(&settings.xp, 1));
+    assert_eq!(profile.current_streak, 1);
+    assert_eq!(db::get_profile(&conn).unwrap(), profile);
+
+    assert_eq!(
+        completion.events,
+        vec![Event::Unlocked(Achievement::FirstSession)]
+    );
+    assert_eq!(
+        db::get_achievements(&conn).unwrap(),
+        vec![(Achievement::FirstSession, "2026-03-02T09:00:00".to_string())]
+    );
+}
+
+#[test]
+fn streak_advances_across_days() {
+    let conn = open_db();
+    let settings = Settings::default();
+    let mut profile = UserProfile::default();
+    let mut timer = Timer::new();
+
+    for day in 2..=4 {
+        finish(&mut timer, SessionType::Focus, &settings);
+        engine::complete_session(
+            Some(&conn),
+            &mut profile,
+            &settings,
+            &timer,
+            start(&format!("2026-03-{:02}T09:00:00", day)),
+            at(day, 9),
+        )
+        .unwrap();
+    }
+
+    let stored = db::get_profile(&conn).unwrap();
+    assert_eq!(stored.current_streak, 3);
+    assert_eq!(stored.longest_streak, 3);
+    assert_eq!(
+        stored.total_xp,
+        xp::calculate_xp(&settings.xp, 1)
+            + xp::calculate_xp(&settings.xp, 2)
+            + xp::calculate_xp(&settings.xp, 3)
+    );
+}
+
+#[test]
+fn break_is_saved_without_xp() {
+    let conn = open_db();
+    let settings = Settings::default();
+    let mut profile = UserProfile::default();
+    let mut timer = Timer::new();
+
+    finish(&mut timer, SessionType::ShortBreak, &settings);
+    let completion = engine::complete_session(
+        Some(&conn),
+        &mut profile,
+        &settings,
+        &timer,
+        start("2026-03-02T09:25:00"),
+        at(2, 9),
+    )
+    .unwrap();
+
+    assert_eq!(completion.session_type, SessionType::ShortBreak);
+    assert_eq!(completion.xp, None);
+    assert_eq!(db::get_total_stats(&conn).unwrap(), (0, 0));
+    assert_eq!(db::get_profile(&conn).unwrap(), UserProfile::default());
+}
+
+#[test]
+fn stopwatch_is_saved_as_focus() {
+    let conn = open_db();
+    let settings = Settings::default();
+    let mut profile = UserProfile::default();
+
+    let completion = engine::complete_stopwatch(
+        Some(&conn),
+        &mut profile,
+        &settings,
+        600,
+        2,
+        start("2026-03-02T09:00:00"),
+        at(2, 9),
+    )
+    .unwrap();
+
+    assert_eq!(completion.session_type, SessionType::Focus);
+    assert_eq!(completion.xp, Some(xp::stopwatch_xp(&settings.xp, 600, 1)));
+    assert_eq!(db::get_total_stats(&conn).unwrap(), (1, 600));
+    assert_eq!(db::get_total_interruptions(&conn).unwrap(), 2);
+    assert_eq!(db::get_profile(&conn).unwrap().current_streak, 1);
+}
+
+#[test]
+fn skipped_focus_is_recorded_as_abandoned() {
+    let conn = open_db();
+    let settings = Settings::default();
+    let mut timer = Timer::new();
+
+    timer.start(SessionType::Focus, &settings.timer);
+    let end = timer.session_end_instant.unwrap();
+    let ten_minutes_in = settings.timer.focus_secs as u64 - 600;
+    timer.tick_at(end - Duration::from_secs(ten_minutes_in));
+    let skipped = engine::skip_session(Some(&conn), &mut timer, start("2026-03-02T09:00:00"));
+
+    assert_eq!(skipped.unwrap(), Skipped::Focus(Some(600)));
+    assert!(matches!(timer.state, TimerState::Idle));
+    assert_eq!(timer.focus_sessions_completed, 0);
+    let sessions = db::get_all_sessions(&conn).unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert!(!sessions[0].completed);
+    assert_eq!(sessions[0].duration_secs, 600);
+}
+
+#[test]
+fn skipped_break_rolls_into_focus() {
+    let conn = open_db();
+    let settings = Settings::default();
+    let mut timer = Timer::new();
+
+    for break_type in [SessionType::ShortBreak, SessionType::LongBreak] {
+        timer.start(break_type, &settings.timer);
+        timer.pause();
+        let skipped = engine::skip_session(Some(&conn), &mut timer, start("2026-03-02T09:25:00"));
+
+        // pause timeout and close stop here, with nothing left running
+        assert_eq!(skipped.unwrap(), Skipped::Break);
+        assert!(matches!(timer.state, TimerState::Idle));
+
+        // the Skip button goes on the way Start does
+        timer.start_next(&settings.timer);
+        assert_eq!(timer.current_session_type(), Some(SessionType::Focus));
+        assert!(timer.is_running());
+        assert_eq!(timer.remaining_secs(), settings.timer.focus_secs);
+    }
+    assert!(db::get_all_sessions(&conn).unwrap().is_empty());
+}